@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use crate::application::{auction_service::AuctionService, scheduler::{self, Countdown, Ticker}};
+use crate::domain::{
+    entities::{Auction, AuctionFormat},
+    repositories::AuctionRepository,
+    events::GameEvent,
+};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+// Every 10 seconds, then every second during the last 10
+const COUNTDOWN_STEPS: &[(i64, i64)] = &[(10, 10)];
+
+// Closes auctions whose clock ran out and broadcasts the countdown of the running ones
+pub struct AuctionScheduler {
+    auction_repo: Arc<dyn AuctionRepository + Send + Sync>,
+    auction_service: Arc<AuctionService>,
+    tx: tokio::sync::broadcast::Sender<GameEvent>,
+    countdown: Countdown,
+}
+
+impl AuctionScheduler {
+    pub fn new(
+        auction_repo: Arc<dyn AuctionRepository + Send + Sync>,
+        auction_service: Arc<AuctionService>,
+        tx: tokio::sync::broadcast::Sender<GameEvent>,
+    ) -> Self {
+        Self { auction_repo, auction_service, tx, countdown: Countdown::new(COUNTDOWN_STEPS) }
+    }
+
+    pub fn spawn(self: Arc<Self>) {
+        scheduler::spawn(self, TICK_INTERVAL);
+    }
+
+    fn announce(&self, auction: &Auction, remaining_seconds: i64) {
+        if !self.countdown.should_announce(auction.id, remaining_seconds) {
+            return;
+        }
+
+        let _ = self.tx.send(GameEvent::AuctionCountdown {
+            game_id: auction.game_id,
+            auction_id: auction.id,
+            remaining_seconds,
+        });
+    }
+}
+
+#[async_trait]
+impl Ticker for AuctionScheduler {
+    const NAME: &'static str = "Auction scheduler";
+
+    async fn tick(&self) -> Result<(), anyhow::Error> {
+        let auctions = self.auction_repo.find_all_active().await?;
+        let now = time::OffsetDateTime::now_utc();
+
+        self.countdown.retain(|id| auctions.iter().any(|a| a.id == *id));
+
+        for auction in auctions {
+            let Some(ends_at) = auction.ends_at else { continue };
+            let remaining = (ends_at - now).whole_seconds();
+
+            if remaining > 0 {
+                self.announce(&auction, remaining);
+                if auction.format == AuctionFormat::Dutch {
+                    let auction_id = auction.id;
+                    if let Err(e) = self.auction_service.refresh_dutch_price(auction).await {
+                        tracing::warn!("Failed to lower Dutch auction {}: {}", auction_id, e);
+                    }
+                }
+                continue;
+            }
+
+            self.countdown.forget(auction.id);
+            let auction_id = auction.id;
+            if let Err(e) = self.auction_service.finalize(auction).await {
+                tracing::warn!("Failed to close auction {}: {}", auction_id, e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_countdown_bucket() {
+        let countdown = Countdown::new(COUNTDOWN_STEPS);
+        assert_eq!(countdown.bucket(45), 50);
+        assert_eq!(countdown.bucket(20), 20);
+        assert_eq!(countdown.bucket(11), 20);
+        assert_eq!(countdown.bucket(10), 10);
+        assert_eq!(countdown.bucket(7), 7);
+        assert_eq!(countdown.bucket(1), 1);
+        assert_eq!(countdown.bucket(0), 0);
+        assert_eq!(countdown.bucket(-3), 0);
+    }
+
+    #[test]
+    fn test_countdown_announces_each_step_once() {
+        let countdown = Countdown::new(COUNTDOWN_STEPS);
+        let auction_id = Uuid::new_v4();
+
+        assert!(countdown.should_announce(auction_id, 25));
+        assert!(!countdown.should_announce(auction_id, 21));
+        assert!(countdown.should_announce(auction_id, 20));
+        assert!(countdown.should_announce(auction_id, 3));
+        assert!(countdown.should_announce(auction_id, 2));
+
+        countdown.forget(auction_id);
+        assert!(countdown.should_announce(auction_id, 2));
+    }
+}
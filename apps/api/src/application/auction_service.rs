@@ -2,16 +2,22 @@ use std::sync::Arc;
 use uuid::Uuid;
 use bigdecimal::BigDecimal;
 use crate::domain::{
-    entities::Auction,
-    repositories::{AuctionRepository, PropertyRepository, ParticipantRepository},
+    entities::{Auction, AuctionBid, AuctionFormat, AuctionLotType, AuctionRules, BidAction, NewAuction, GameParticipant, OwnershipChange, TransactionCategory, TransferDetails, TransferLeg},
+    repositories::{AuctionRepository, CardRepository, GameRepository, PropertyRepository, ParticipantRepository},
+    errors::ConcurrencyConflict,
     events::GameEvent,
 };
 use crate::application::transaction_service::TransactionService;
 
+// Every auction opens at 10m
+const OPENING_BID: i64 = 10;
+
 pub struct AuctionService {
     auction_repo: Arc<dyn AuctionRepository + Send + Sync>,
+    game_repo: Arc<dyn GameRepository + Send + Sync>,
     participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
-    property_repo: Arc<dyn PropertyRepository + Send + Sync>, // To transfer
+    property_repo: Arc<dyn PropertyRepository + Send + Sync>,
+    card_repo: Arc<dyn CardRepository + Send + Sync>,
     transaction_service: Arc<TransactionService>,
     tx: tokio::sync::broadcast::Sender<GameEvent>,
 }
@@ -19,19 +25,23 @@ pub struct AuctionService {
 impl AuctionService {
     pub fn new(
         auction_repo: Arc<dyn AuctionRepository + Send + Sync>,
+        game_repo: Arc<dyn GameRepository + Send + Sync>,
         participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
         property_repo: Arc<dyn PropertyRepository + Send + Sync>,
+        card_repo: Arc<dyn CardRepository + Send + Sync>,
         transaction_service: Arc<TransactionService>,
         tx: tokio::sync::broadcast::Sender<GameEvent>,
     ) -> Self {
-        Self { auction_repo, participant_repo, property_repo, transaction_service, tx }
+        Self { auction_repo, game_repo, participant_repo, property_repo, card_repo, transaction_service, tx }
     }
 
     pub async fn get_active_auction(&self, game_id: Uuid) -> Result<Option<Auction>, anyhow::Error> {
         self.auction_repo.find_active_by_game(game_id).await
     }
 
-    pub async fn start_auction(&self, game_id: Uuid, property_id: Uuid) -> Result<Auction, anyhow::Error> {
+    /// Opens an auction that closes by itself after `duration_seconds` (the house rule when not given).
+    /// `user_id` is the seller when the lot is one of their own cards.
+    pub async fn start_auction(&self, game_id: Uuid, user_id: Uuid, request: NewAuction) -> Result<Auction, anyhow::Error> {
         // Check if active auction exists? Or allow multiple? 
         // Rules say "if bank auctions property..." usually one at a time.
         // Assuming one active auction per game is simpler for UI.
@@ -39,100 +49,574 @@ impl AuctionService {
             return Err(anyhow::anyhow!("There is already an active auction"));
         }
 
-        let auction = Auction {
+        let game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        let duration = request.duration_seconds.unwrap_or(game.house_rules.auction.duration_seconds);
+        if duration <= 0 {
+            return Err(anyhow::anyhow!("Auction duration must be positive"));
+        }
+        let now = time::OffsetDateTime::now_utc();
+
+        let mut auction = Auction {
             id: Uuid::new_v4(),
             game_id,
-            property_id,
-            current_bid: BigDecimal::from(10), // Starts at 10m
+            lot_type: request.lot_type,
+            property_id: None,
+            card_id: None,
+            inventory_id: None,
+            seller_participant_id: None,
+            slot_index: None,
+            current_bid: BigDecimal::from(OPENING_BID),
             highest_bidder_id: None,
             status: "ACTIVE".to_string(),
-            created_at: Some(time::OffsetDateTime::now_utc()),
-            ends_at: None, // Or set timeout
+            version: 0,
+            created_at: Some(now),
+            ends_at: Some(now + time::Duration::seconds(duration)),
+            format: request.format,
+            start_price: None,
+            revealed_bids: None,
+        };
+
+        // Pin down the lot, and what it is worth as a Dutch starting point
+        let list_price = match request.lot_type {
+            AuctionLotType::Property => {
+                let property_id = request.property_id.ok_or_else(|| anyhow::anyhow!("property_id is required"))?;
+                let property = self.property_repo.find_property_by_id(property_id).await?
+                    .ok_or_else(|| anyhow::anyhow!("Property not found"))?;
+                if self.property_repo.find_ownership_by_game(game_id).await?.iter().any(|pp| pp.property_id == property_id) {
+                    return Err(anyhow::anyhow!("Only unowned properties can be auctioned"));
+                }
+                auction.property_id = Some(property_id);
+                Some(property.price)
+            }
+            AuctionLotType::ParticipantCard => {
+                let inventory_id = request.inventory_id.ok_or_else(|| anyhow::anyhow!("inventory_id is required"))?;
+                let seller = self.participant_repo.find_by_game_id(game_id).await?
+                    .into_iter()
+                    .find(|p| p.user_id == user_id)
+                    .ok_or_else(|| anyhow::anyhow!("Participant not found"))?;
+                let card = self.card_repo.get_inventory(seller.id).await?
+                    .into_iter()
+                    .find(|c| c.id == inventory_id)
+                    .ok_or_else(|| anyhow::anyhow!("You can only auction your own cards"))?;
+                auction.card_id = Some(card.card_id);
+                auction.inventory_id = Some(inventory_id);
+                auction.seller_participant_id = Some(seller.id);
+                None
+            }
+            AuctionLotType::BovedaSlot => {
+                let slot_index = request.slot_index.ok_or_else(|| anyhow::anyhow!("slot_index is required"))?;
+                let slot = self.card_repo.get_boveda_market(game_id).await?
+                    .into_iter()
+                    .find(|m| m.slot_index == slot_index)
+                    .ok_or_else(|| anyhow::anyhow!("Slot empty"))?;
+                auction.card_id = Some(slot.card_id);
+                auction.slot_index = Some(slot_index);
+                slot.cost
+            }
+            AuctionLotType::House | AuctionLotType::Hotel => None,
         };
 
+        if request.format == AuctionFormat::Dutch {
+            let price = request.start_price
+                .or_else(|| list_price.map(|p| p * BigDecimal::from(2)))
+                .ok_or_else(|| anyhow::anyhow!("A Dutch auction for this lot needs a start_price"))?;
+            if price < OPENING_BID {
+                return Err(anyhow::anyhow!("A Dutch auction has to start at {} or more", OPENING_BID));
+            }
+            auction.current_bid = price.clone();
+            auction.start_price = Some(price);
+        }
+
         let created = self.auction_repo.create(auction).await?;
         // Broadcast Event
         let _ = self.tx.send(GameEvent::AuctionUpdated(created.clone()));
         Ok(created)
     }
 
-    pub async fn place_bid(&self, auction_id: Uuid, bidder_user_id: Uuid, amount: BigDecimal) -> Result<Auction, anyhow::Error> {
-        let mut auction = self.auction_repo.find_by_id(auction_id).await?
+    /// The player whose turn it is passes on the unowned property they landed on,
+    /// which goes up for auction under the game's default rules
+    pub async fn decline_purchase(&self, game_id: Uuid, user_id: Uuid) -> Result<Auction, anyhow::Error> {
+        let game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        if game.current_turn_user_id != Some(user_id) {
+            return Err(anyhow::anyhow!("It is not your turn"));
+        }
+
+        let participant = self.participant_repo.find_by_game_id(game_id).await?
+            .into_iter()
+            .find(|p| p.user_id == user_id)
+            .ok_or_else(|| anyhow::anyhow!("Participant not found"))?;
+        let property = self.property_repo.find_all_properties().await?
+            .into_iter()
+            .find(|p| p.board_position == Some(participant.position))
+            .ok_or_else(|| anyhow::anyhow!("There is no property to buy on this square"))?;
+
+        self.start_auction(game_id, user_id, NewAuction {
+            lot_type: AuctionLotType::Property,
+            property_id: Some(property.id),
+            inventory_id: None,
+            slot_index: None,
+            duration_seconds: None,
+            format: AuctionFormat::Open,
+            start_price: None,
+        }).await
+    }
+
+    /// Bidding history. Sealed amounts stay hidden until the auction closes.
+    pub async fn get_bids(&self, auction_id: Uuid) -> Result<Vec<AuctionBid>, anyhow::Error> {
+        let auction = self.auction_repo.find_by_id(auction_id).await?
             .ok_or_else(|| anyhow::anyhow!("Auction not found"))?;
+        let mut bids = self.auction_repo.find_bids(auction_id).await?;
+        if auction.format == AuctionFormat::Sealed && auction.status == "ACTIVE" {
+            for bid in &mut bids {
+                bid.amount = None;
+            }
+        }
+        Ok(bids)
+    }
 
-        if auction.status != "ACTIVE" {
-            return Err(anyhow::anyhow!("Auction is not active"));
+    /// Keeps a Dutch auction's asking price in step with its clock (called by the auction scheduler)
+    pub async fn refresh_dutch_price(&self, auction: Auction) -> Result<(), anyhow::Error> {
+        let (Some(start_price), Some(started_at)) = (auction.start_price.clone(), auction.created_at) else {
+            return Ok(());
+        };
+        // Already taken, the close is on its way
+        if auction.highest_bidder_id.is_some() {
+            return Ok(());
+        }
+        let game = self.game_repo.find_by_id(auction.game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        let price = game.house_rules.auction.dutch_price(&start_price, started_at, time::OffsetDateTime::now_utc(), &BigDecimal::from(OPENING_BID));
+        if price == auction.current_bid {
+            return Ok(());
         }
 
-        // Get Participant ID
-        let participants = self.participant_repo.find_by_game_id(auction.game_id).await?;
-        let bidder = participants.iter().find(|p| p.user_id == bidder_user_id)
-             .ok_or_else(|| anyhow::anyhow!("Participant not found"))?;
+        let mut auction = auction;
+        auction.current_bid = price;
+        // A taker who got in first keeps their row; the stale snapshot is dropped
+        if let Some(updated) = self.auction_repo.update_active(auction).await? {
+            let _ = self.tx.send(GameEvent::AuctionUpdated(updated));
+        }
+        Ok(())
+    }
+
+    pub async fn place_bid(&self, auction_id: Uuid, bidder_user_id: Uuid, amount: BigDecimal) -> Result<Auction, anyhow::Error> {
+        let (auction, rules, bidder, bids) = self.load_for_action(auction_id, bidder_user_id).await?;
+
+        if bidder.balance < rules.min_balance {
+            return Err(anyhow::anyhow!("You need at least {} to take part in an auction", rules.min_balance));
+        }
 
-        // Check if amount > current_bid
-        if amount <= auction.current_bid {
-             return Err(anyhow::anyhow!("Bid must be higher than current"));
+        match auction.format {
+            AuctionFormat::Open => self.bid_open(auction, rules, bidder, amount).await,
+            AuctionFormat::Sealed => self.bid_sealed(auction, rules, bidder, &bids, amount).await,
+            AuctionFormat::Dutch => self.take_dutch(auction, rules, bidder).await,
         }
+    }
 
-        // Check balance (Participant must have money?)
+    async fn bid_open(&self, mut auction: Auction, rules: AuctionRules, bidder: GameParticipant, amount: BigDecimal) -> Result<Auction, anyhow::Error> {
+        // The opening price can be bid as is; after that each bid beats the last by the increment
+        let minimum = match auction.highest_bidder_id {
+            Some(_) => &auction.current_bid + &rules.min_increment,
+            None => auction.current_bid.clone(),
+        };
+        if amount < minimum {
+            return Err(anyhow::anyhow!("Bid must be at least {}", minimum));
+        }
         if bidder.balance < amount {
-             // Rule says "can only participate if has min 10m", but usually also needs to afford bid? 
-             // Or they can mortgage during auction? 
-             // Let's enforce balance >= bid for safety.
-             return Err(anyhow::anyhow!("Insufficient funds for this bid"));
+            return Err(anyhow::anyhow!("Insufficient funds for this bid"));
+        }
+
+        // Anti-sniping: a late bid puts time back on the clock
+        if let Some(ends_at) = auction.ends_at {
+            if let Some(extended) = rules.extended_deadline(ends_at, time::OffsetDateTime::now_utc()) {
+                auction.ends_at = Some(extended);
+            }
         }
 
-        auction.current_bid = amount;
+        auction.current_bid = amount.clone();
         auction.highest_bidder_id = Some(bidder.id);
-        
-        let updated = self.auction_repo.update(auction).await?;
+
+        // Only lands on the standing bid it was checked against; a bid or close in between wins
+        let updated = self.auction_repo.update_active(auction).await?
+            .ok_or(ConcurrencyConflict { entity: "auction" })?;
+        self.record(&updated, bidder.id, BidAction::Bid, Some(amount)).await?;
         // Broadcast
         let _ = self.tx.send(GameEvent::AuctionUpdated(updated.clone()));
-        Ok(updated)
+
+        self.close_if_decided(updated, &rules).await
+    }
+
+    // One hidden bid per player; the auction row keeps showing the opening price until close
+    async fn bid_sealed(&self, auction: Auction, rules: AuctionRules, bidder: GameParticipant, bids: &[AuctionBid], amount: BigDecimal) -> Result<Auction, anyhow::Error> {
+        if bids.iter().any(|b| b.participant_id == bidder.id) {
+            return Err(anyhow::anyhow!("You already placed your sealed bid"));
+        }
+        if amount < auction.current_bid {
+            return Err(anyhow::anyhow!("Bid must be at least {}", auction.current_bid));
+        }
+        if bidder.balance < amount {
+            return Err(anyhow::anyhow!("Insufficient funds for this bid"));
+        }
+
+        // Bump the version like an open bid, so a close that read the bids before this one conflicts
+        let updated = self.auction_repo.update_active(auction).await?
+            .ok_or(ConcurrencyConflict { entity: "auction" })?;
+        self.record(&updated, bidder.id, BidAction::Bid, Some(amount)).await?;
+        let _ = self.tx.send(GameEvent::AuctionUpdated(updated.clone()));
+        self.close_if_decided(updated, &rules).await
+    }
+
+    // The first player to take the current asking price wins
+    async fn take_dutch(&self, mut auction: Auction, rules: AuctionRules, bidder: GameParticipant) -> Result<Auction, anyhow::Error> {
+        if auction.highest_bidder_id.is_some() {
+            return Err(anyhow::anyhow!("Someone else took it first"));
+        }
+        let price = match (&auction.start_price, auction.created_at) {
+            (Some(start), Some(started_at)) => rules.dutch_price(start, started_at, time::OffsetDateTime::now_utc(), &BigDecimal::from(OPENING_BID)),
+            _ => auction.current_bid.clone(),
+        };
+        if bidder.balance < price {
+            return Err(anyhow::anyhow!("Insufficient funds for this bid"));
+        }
+
+        let auction_id = auction.id;
+        auction.current_bid = price.clone();
+        auction.highest_bidder_id = Some(bidder.id);
+        let Some(updated) = self.auction_repo.update_active(auction).await? else {
+            // Either another taker won the race, or the clock lowered the price under us
+            let current = self.auction_repo.find_by_id(auction_id).await?;
+            return Err(match current {
+                Some(a) if a.status == "ACTIVE" && a.highest_bidder_id.is_none() => ConcurrencyConflict { entity: "auction" }.into(),
+                _ => anyhow::anyhow!("Someone else took it first"),
+            });
+        };
+        self.record(&updated, bidder.id, BidAction::Bid, Some(price)).await?;
+
+        self.finalize(updated).await
+    }
+
+    /// Drop out of the bidding. A standing bid stays valid, so the highest bidder cannot pass.
+    pub async fn pass(&self, auction_id: Uuid, user_id: Uuid) -> Result<Auction, anyhow::Error> {
+        let (auction, rules, participant, _) = self.load_for_action(auction_id, user_id).await?;
+
+        if auction.highest_bidder_id == Some(participant.id) {
+            return Err(anyhow::anyhow!("You hold the highest bid"));
+        }
+
+        self.record(&auction, participant.id, BidAction::Pass, None).await?;
+        let _ = self.tx.send(GameEvent::AuctionUpdated(auction.clone()));
+        self.close_if_decided(auction, &rules).await
+    }
+
+    /// Drop out and take back any bids; the best remaining bid becomes the standing one again
+    pub async fn withdraw(&self, auction_id: Uuid, user_id: Uuid) -> Result<Auction, anyhow::Error> {
+        let (mut auction, rules, participant, mut bids) = self.load_for_action(auction_id, user_id).await?;
+
+        let withdrawal = bid_entry(&auction, participant.id, BidAction::Withdraw, None);
+        bids.push(withdrawal.clone());
+
+        if auction.highest_bidder_id == Some(participant.id) {
+            match standing_bid(&bids) {
+                Some((bidder_id, amount)) => {
+                    auction.highest_bidder_id = Some(bidder_id);
+                    auction.current_bid = amount;
+                }
+                None => {
+                    auction.highest_bidder_id = None;
+                    auction.current_bid = BigDecimal::from(OPENING_BID);
+                }
+            }
+            auction = self.auction_repo.update_active(auction).await?
+                .ok_or(ConcurrencyConflict { entity: "auction" })?;
+        }
+        // Recorded once the standing bid is settled, so a lost race leaves no stray withdrawal
+        self.auction_repo.add_bid(withdrawal).await?;
+
+        let _ = self.tx.send(GameEvent::AuctionUpdated(auction.clone()));
+        self.close_if_decided(auction, &rules).await
     }
 
-    pub async fn end_auction(&self, auction_id: Uuid) -> Result<Auction, anyhow::Error> {
-        let mut auction = self.auction_repo.find_by_id(auction_id).await?
+    // Shared checks for bid/pass/withdraw: the auction is open and the participant is still in it
+    async fn load_for_action(&self, auction_id: Uuid, user_id: Uuid) -> Result<(Auction, AuctionRules, GameParticipant, Vec<AuctionBid>), anyhow::Error> {
+        let auction = self.auction_repo.find_by_id(auction_id).await?
             .ok_or_else(|| anyhow::anyhow!("Auction not found"))?;
-        
+
+        if auction.status != "ACTIVE" {
+            return Err(anyhow::anyhow!("Auction is not active"));
+        }
+        if auction.ends_at.is_some_and(|t| t <= time::OffsetDateTime::now_utc()) {
+            return Err(anyhow::anyhow!("Auction has already closed"));
+        }
+
+        let participant = self.participant_repo.find_by_game_id(auction.game_id).await?
+            .into_iter()
+            .find(|p| p.user_id == user_id)
+            .ok_or_else(|| anyhow::anyhow!("Participant not found"))?;
+        if participant.bankrupt_at.is_some() {
+            return Err(anyhow::anyhow!("Bankrupt participants cannot take part in auctions"));
+        }
+        if auction.seller_participant_id == Some(participant.id) {
+            return Err(anyhow::anyhow!("You cannot bid on your own lot"));
+        }
+
+        let bids = self.auction_repo.find_bids(auction.id).await?;
+        if bids.iter().any(|b| b.participant_id == participant.id && b.action != BidAction::Bid) {
+            return Err(anyhow::anyhow!("You are already out of this auction"));
+        }
+
+        let game = self.game_repo.find_by_id(auction.game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        Ok((auction, game.house_rules.0.auction, participant, bids))
+    }
+
+    async fn record(&self, auction: &Auction, participant_id: Uuid, action: BidAction, amount: Option<BigDecimal>) -> Result<AuctionBid, anyhow::Error> {
+        self.auction_repo.add_bid(bid_entry(auction, participant_id, action, amount)).await
+    }
+
+    // Closes early once all but one have dropped out and that one holds the bid (or nobody is left).
+    // Sealed auctions close as soon as everyone still in has bid.
+    async fn close_if_decided(&self, auction: Auction, rules: &AuctionRules) -> Result<Auction, anyhow::Error> {
+        // Fresh history: includes the entry just recorded and anything that came in meanwhile
+        let bids = self.auction_repo.find_bids(auction.id).await?;
+        let participants: Vec<GameParticipant> = self.participant_repo.find_by_game_id(auction.game_id).await?
+            .into_iter()
+            .filter(|p| Some(p.id) != auction.seller_participant_id)
+            .collect();
+        let remaining = remaining_bidders(&participants, &bids, auction.highest_bidder_id, &rules.min_balance);
+        let decided = match (auction.format, remaining.as_slice()) {
+            (_, []) => true,
+            // Sealed: once every player still in has handed in a bid
+            (AuctionFormat::Sealed, _) => remaining.iter().all(|id| bids.iter().any(|b| b.participant_id == *id)),
+            (_, [only]) => auction.highest_bidder_id == Some(*only),
+            _ => false,
+        };
+
+        if decided { self.finalize(auction).await } else { Ok(auction) }
+    }
+
+    /// Manual close, host only. Timed auctions are normally closed by the auction scheduler.
+    pub async fn end_auction(&self, auction_id: Uuid, user_id: Uuid) -> Result<Auction, anyhow::Error> {
+        let auction = self.auction_repo.find_by_id(auction_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Auction not found"))?;
+
+        let game = self.game_repo.find_by_id(auction.game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        if game.host_user_id != user_id {
+            return Err(anyhow::anyhow!("Only the host can close an auction"));
+        }
+
+        self.finalize(auction).await
+    }
+
+    /// Closes the auction and hands the property to the highest bidder, whatever the format.
+    /// The close commits in the sale's batch, so a manual close and the scheduler never both pay out.
+    pub async fn finalize(&self, mut auction: Auction) -> Result<Auction, anyhow::Error> {
         if auction.status != "ACTIVE" {
              return Err(anyhow::anyhow!("Auction not active"));
         }
 
-        // Finalize
-        if let Some(winner_id) = auction.highest_bidder_id {
-            // Deduct Money
-            self.transaction_service.transfer(
-                auction.game_id,
-                Some(winner_id),
-                None,
-                auction.current_bid.clone(),
-                Some("Won Auction".to_string())
-            ).await?;
-
-            // Transfer Property
-            // Ensure any stale ownership is removed first to avoid UNIQUE constraint violations
-            self.property_repo.delete_ownership(auction.game_id, auction.property_id).await?;
-
-            let pp = crate::domain::entities::ParticipantProperty {
-                id: Uuid::new_v4(),
-                game_id: auction.game_id,
-                participant_id: winner_id,
-                property_id: auction.property_id,
-                is_mortgaged: false,
-                house_count: 0,
-                hotel_count: 0,
-                property_name: None,
-                group_color: None,
-            };
-            self.property_repo.assign_property(pp).await?;
-        }
-
-        auction.status = "FINISHED".to_string();
+        // Sealed: open the envelopes, the highest bid wins at its own price
+        let mut revealed = None;
+        if auction.format == AuctionFormat::Sealed {
+            let bids = self.auction_repo.find_bids(auction.id).await?;
+            if let Some((winner_id, amount)) = standing_bid(&bids) {
+                auction.highest_bidder_id = Some(winner_id);
+                auction.current_bid = amount;
+            }
+            revealed = Some(bids);
+        }
         auction.ends_at = Some(time::OffsetDateTime::now_utc());
-        
-        let updated = self.auction_repo.update(auction).await?;
-        let _ = self.tx.send(GameEvent::AuctionUpdated(updated.clone()));
-        Ok(updated)
+
+        let mut closed = match auction.highest_bidder_id {
+            Some(winner_id) => match self.sell_lot(&auction, winner_id).await {
+                Ok(()) => self.auction_repo.find_by_id(auction.id).await?
+                    .ok_or_else(|| anyhow::anyhow!("Auction not found"))?,
+                // Lost to a bid or another close: nothing was sold, the caller may retry
+                Err(e) if e.is::<ConcurrencyConflict>() => return Err(e),
+                Err(e) => {
+                    // The winner can no longer pay or the lot is gone: the sale is void
+                    auction.status = "CANCELLED".to_string();
+                    let cancelled = self.auction_repo.update_active(auction).await?
+                        .ok_or_else(|| anyhow::anyhow!("Auction not active"))?;
+                    let _ = self.tx.send(GameEvent::AuctionUpdated(cancelled));
+                    return Err(e);
+                }
+            },
+            None => {
+                auction.status = "FINISHED".to_string();
+                self.auction_repo.update_active(auction).await?
+                    .ok_or_else(|| anyhow::anyhow!("Auction not active"))?
+            }
+        };
+        closed.revealed_bids = revealed;
+
+        let _ = self.tx.send(GameEvent::AuctionUpdated(closed.clone()));
+        Ok(closed)
+    }
+
+    // Winner pays, receives the lot and the auction closes in one batch; what changes hands depends on
+    // the lot type. The batch fails as a whole if the lot is gone (card used, slot bought, property taken)
+    // or the auction moved on since it was read.
+    async fn sell_lot(&self, auction: &Auction, winner_id: Uuid) -> Result<(), anyhow::Error> {
+        let missing = || anyhow::anyhow!("Auction lot is incomplete");
+        let mut details = self.sale_details(auction);
+        let (seller_id, change) = match auction.lot_type {
+            AuctionLotType::Property => {
+                let property_id = auction.property_id.ok_or_else(missing)?;
+                details = details.property(property_id);
+                (None, OwnershipChange::Assign { property_id, participant_id: winner_id })
+            }
+            AuctionLotType::ParticipantCard => {
+                let seller_id = auction.seller_participant_id.ok_or_else(missing)?;
+                let inventory_id = auction.inventory_id.ok_or_else(missing)?;
+                (Some(seller_id), OwnershipChange::TransferCard { inventory_id, from_participant_id: seller_id, to_participant_id: winner_id })
+            }
+            AuctionLotType::BovedaSlot => {
+                let (slot_index, card_id) = (auction.slot_index.ok_or_else(missing)?, auction.card_id.ok_or_else(missing)?);
+                (None, OwnershipChange::TakeBovedaCard { slot_index, card_id, participant_id: winner_id })
+            }
+            AuctionLotType::House | AuctionLotType::Hotel => {
+                (None, OwnershipChange::GrantBuildingRight { participant_id: winner_id, kind: auction.lot_type, auction_id: auction.id })
+            }
+        };
+        if let Some(card_id) = auction.card_id {
+            details = details.card(card_id);
+        }
+
+        let result = self.transaction_service.execute_batch(
+            auction.game_id,
+            vec![self.payment(auction, winner_id, seller_id, details)],
+            vec![change, OwnershipChange::CloseAuction { auction: auction.clone() }],
+        ).await?;
+
+        for pp in result.ownership {
+            let _ = self.tx.send(GameEvent::PropertyUpdated(pp));
+        }
+        if auction.lot_type == AuctionLotType::BovedaSlot {
+            // The market refills the slot the next time it is read
+            let _ = self.tx.send(GameEvent::MarketUpdated { game_id: auction.game_id });
+        }
+        Ok(())
+    }
+
+    fn sale_details(&self, auction: &Auction) -> TransferDetails {
+        TransferDetails::new(TransactionCategory::Auction, format!("Won Auction ({})", auction.lot_type)).auction(auction.id)
+    }
+
+    // The winning bid, to the Bank or to the selling player
+    fn payment(&self, auction: &Auction, winner_id: Uuid, seller_id: Option<Uuid>, details: TransferDetails) -> TransferLeg {
+        TransferLeg {
+            from_participant_id: Some(winner_id),
+            to_participant_id: seller_id,
+            amount: auction.current_bid.clone(),
+            details,
+        }
+    }
+}
+
+fn bid_entry(auction: &Auction, participant_id: Uuid, action: BidAction, amount: Option<BigDecimal>) -> AuctionBid {
+    AuctionBid {
+        id: Uuid::new_v4(),
+        auction_id: auction.id,
+        game_id: auction.game_id,
+        participant_id,
+        action,
+        amount,
+        created_at: Some(time::OffsetDateTime::now_utc()),
+    }
+}
+
+/// Participants who can still win: not out, not bankrupt, and holding the minimum balance
+/// (the highest bidder stays in regardless, their bid already counts)
+fn remaining_bidders(participants: &[GameParticipant], bids: &[AuctionBid], highest_bidder_id: Option<Uuid>, min_balance: &BigDecimal) -> Vec<Uuid> {
+    participants.iter()
+        .filter(|p| p.bankrupt_at.is_none())
+        .filter(|p| Some(p.id) == highest_bidder_id || p.balance >= *min_balance)
+        .filter(|p| !bids.iter().any(|b| b.participant_id == p.id && b.action != BidAction::Bid))
+        .map(|p| p.id)
+        .collect()
+}
+
+/// Highest bid among participants who have not withdrawn; on a tie the earlier bid wins
+fn standing_bid(bids: &[AuctionBid]) -> Option<(Uuid, BigDecimal)> {
+    bids.iter()
+        .filter(|b| b.action == BidAction::Bid)
+        .filter(|b| !bids.iter().any(|w| w.participant_id == b.participant_id && w.action == BidAction::Withdraw))
+        .filter_map(|b| Some((b.participant_id, b.amount.clone()?)))
+        .fold(None, |best: Option<(Uuid, BigDecimal)>, bid| match best {
+            Some(best) if best.1 >= bid.1 => Some(best),
+            _ => Some(bid),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::AuctionRules;
+    use time::{Duration, OffsetDateTime};
+
+    #[test]
+    fn test_late_bids_extend_the_deadline() {
+        let rules = AuctionRules::default();
+        let now = OffsetDateTime::now_utc();
+
+        // Plenty of time left: unchanged
+        assert_eq!(rules.extended_deadline(now + Duration::seconds(30), now), None);
+        // Three seconds left: back to ten
+        assert_eq!(rules.extended_deadline(now + Duration::seconds(3), now), Some(now + Duration::seconds(10)));
+    }
+
+    #[test]
+    fn test_dutch_price_steps_down_to_the_floor() {
+        let rules = AuctionRules::default();
+        let start = OffsetDateTime::now_utc();
+        let (price, floor) = (BigDecimal::from(100), BigDecimal::from(10));
+
+        assert_eq!(rules.dutch_price(&price, start, start + Duration::seconds(2), &floor), BigDecimal::from(100));
+        assert_eq!(rules.dutch_price(&price, start, start + Duration::seconds(7), &floor), BigDecimal::from(80));
+        assert_eq!(rules.dutch_price(&price, start, start + Duration::minutes(5), &floor), floor);
+    }
+
+    #[test]
+    fn test_passes_and_withdrawals_narrow_the_field() {
+        let participant = |balance: i64| GameParticipant {
+            id: Uuid::new_v4(),
+            game_id: Uuid::nil(),
+            user_id: Uuid::new_v4(),
+            balance: BigDecimal::from(balance),
+            position: 0,
+            joined_at: None,
+            bankrupt_at: None,
+            bills: None,
+        };
+        let entry = |participant_id: Uuid, action: BidAction, amount: Option<i64>| AuctionBid {
+            id: Uuid::new_v4(),
+            auction_id: Uuid::nil(),
+            game_id: Uuid::nil(),
+            participant_id,
+            action,
+            amount: amount.map(BigDecimal::from),
+            created_at: None,
+        };
+        let (ana, beto, caro, broke) = (participant(500), participant(300), participant(200), participant(5));
+        let bids = vec![
+            entry(ana.id, BidAction::Bid, Some(50)),
+            entry(beto.id, BidAction::Bid, Some(80)),
+            entry(caro.id, BidAction::Pass, None),
+        ];
+        let participants = [ana.clone(), beto.clone(), caro, broke];
+        let min_balance = BigDecimal::from(10);
+
+        // Caro passed and the broke player cannot take part
+        assert_eq!(remaining_bidders(&participants, &bids, Some(beto.id), &min_balance), vec![ana.id, beto.id]);
+
+        // Beto takes their bid back: Ana's 50 stands again
+        let mut bids = bids;
+        bids.push(entry(beto.id, BidAction::Withdraw, None));
+        assert_eq!(standing_bid(&bids), Some((ana.id, BigDecimal::from(50))));
+        assert_eq!(remaining_bidders(&participants, &bids, Some(ana.id), &min_balance), vec![ana.id]);
     }
 }
@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+use bigdecimal::{BigDecimal, Zero};
+use crate::domain::{
+    entities::{BankerDashboard, Transaction, TransactionCategory, TransactionFilter},
+    repositories::{GameRepository, LoanRepository, ParticipantRepository, PaymentRequestRepository, TransactionRepository},
+    events::GameEvent,
+};
+
+// The banker runs the Bank in physical play: host by default, reassignable by the host
+pub struct BankerService {
+    game_repo: Arc<dyn GameRepository + Send + Sync>,
+    participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+    transaction_repo: Arc<dyn TransactionRepository + Send + Sync>,
+    payment_request_repo: Arc<dyn PaymentRequestRepository + Send + Sync>,
+    loan_repo: Arc<dyn LoanRepository + Send + Sync>,
+    tx: tokio::sync::broadcast::Sender<GameEvent>,
+}
+
+impl BankerService {
+    pub fn new(
+        game_repo: Arc<dyn GameRepository + Send + Sync>,
+        participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+        transaction_repo: Arc<dyn TransactionRepository + Send + Sync>,
+        payment_request_repo: Arc<dyn PaymentRequestRepository + Send + Sync>,
+        loan_repo: Arc<dyn LoanRepository + Send + Sync>,
+        tx: tokio::sync::broadcast::Sender<GameEvent>,
+    ) -> Self {
+        Self { game_repo, participant_repo, transaction_repo, payment_request_repo, loan_repo, tx }
+    }
+
+    /// Host hands the Bank to another participant (or takes it back)
+    pub async fn set_banker(&self, game_id: Uuid, user_id: Uuid, banker_user_id: Uuid) -> Result<Uuid, anyhow::Error> {
+        let mut game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        if game.host_user_id != user_id {
+            return Err(anyhow::anyhow!("Only host can assign the banker"));
+        }
+
+        let is_participant = self.participant_repo.find_by_game_id(game_id).await?
+            .iter().any(|p| p.user_id == banker_user_id);
+        if !is_participant && banker_user_id != game.host_user_id {
+            return Err(anyhow::anyhow!("Banker must be a participant of this game"));
+        }
+
+        // Store None for the host so the default keeps following a host change
+        game.banker_user_id = (banker_user_id != game.host_user_id).then_some(banker_user_id);
+        let game = self.game_repo.update(game).await?;
+
+        let banker = game.banker_id();
+        let _ = self.tx.send(GameEvent::BankerChanged { game_id, banker_user_id: banker });
+        Ok(banker)
+    }
+
+    pub async fn dashboard(&self, game_id: Uuid, user_id: Uuid) -> Result<BankerDashboard, anyhow::Error> {
+        let game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        let banker_user_id = game.banker_id();
+        if banker_user_id != user_id && game.host_user_id != user_id {
+            return Err(anyhow::anyhow!("Only the banker or the host can see the banker dashboard"));
+        }
+
+        let pending_payment_requests = self.payment_request_repo.find_by_game(game_id).await?
+            .into_iter()
+            .filter(|r| r.status == "PENDING")
+            .collect();
+        let pending_bank_loans = self.loan_repo.find_by_game(game_id).await?
+            .into_iter()
+            .filter(|l| l.status == "PENDING" && l.lender_participant_id.is_none())
+            .collect();
+
+        let transactions = self.transaction_repo.find_by_game(game_id, TransactionFilter::default()).await?;
+        let (bank_outflow_total, bank_inflow_total, bank_outflow_by_category) = bank_flows(&transactions);
+
+        Ok(BankerDashboard {
+            game_id,
+            banker_user_id,
+            pending_payment_requests,
+            pending_bank_loans,
+            bank_outflow_total,
+            bank_inflow_total,
+            bank_outflow_by_category,
+            jackpot_balance: game.jackpot_balance,
+        })
+    }
+}
+
+// Money leaving and entering the Bank. Jackpot payouts come out of Free Parking, not the Bank,
+// and adjustments only fix the books, so neither counts.
+fn bank_flows(transactions: &[Transaction]) -> (BigDecimal, BigDecimal, BTreeMap<String, BigDecimal>) {
+    let mut outflow = BigDecimal::zero();
+    let mut inflow = BigDecimal::zero();
+    let mut by_category: BTreeMap<String, BigDecimal> = BTreeMap::new();
+
+    for t in transactions {
+        if matches!(t.category, TransactionCategory::Jackpot | TransactionCategory::Adjustment) {
+            continue;
+        }
+        match (t.from_participant_id, t.to_participant_id) {
+            (None, Some(_)) => {
+                outflow += &t.amount;
+                *by_category.entry(t.category.to_string()).or_default() += &t.amount;
+            }
+            (Some(_), None) => inflow += &t.amount,
+            _ => {}
+        }
+    }
+
+    (outflow, inflow, by_category)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(from: Option<Uuid>, to: Option<Uuid>, amount: i32, category: TransactionCategory) -> Transaction {
+        Transaction {
+            id: Uuid::new_v4(),
+            game_id: Uuid::nil(),
+            from_participant_id: from,
+            to_participant_id: to,
+            amount: BigDecimal::from(amount),
+            description: None,
+            created_at: None,
+            category,
+            property_id: None,
+            card_id: None,
+            trade_id: None,
+            auction_id: None,
+            loan_id: None,
+            group_id: None,
+            jackpot_delta: BigDecimal::zero(),
+            reverses_transaction_id: None,
+            reversed_by_transaction_id: None,
+        }
+    }
+
+    #[test]
+    fn test_bank_flows() {
+        let alice = Some(Uuid::new_v4());
+        let bob = Some(Uuid::new_v4());
+
+        let (outflow, inflow, by_category) = bank_flows(&[
+            entry(None, alice, 1500, TransactionCategory::Salary),
+            entry(None, alice, 200, TransactionCategory::Salary),
+            entry(None, bob, 300, TransactionCategory::Loan),
+            entry(alice, None, 100, TransactionCategory::Tax),
+            entry(alice, bob, 50, TransactionCategory::Rent),
+            entry(None, bob, 400, TransactionCategory::Jackpot),
+        ]);
+
+        assert_eq!(outflow, BigDecimal::from(2000));
+        assert_eq!(inflow, BigDecimal::from(100));
+        assert_eq!(by_category["salary"], BigDecimal::from(1700));
+        assert_eq!(by_category["loan"], BigDecimal::from(300));
+        assert!(!by_category.contains_key("jackpot"));
+    }
+}
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use bigdecimal::{BigDecimal, Zero};
+use crate::domain::{
+    entities::{GameStatus, OwnershipChange, TransactionCategory, TransferDetails, TransferLeg, WinReason},
+    repositories::{GameRepository, ParticipantRepository, PropertyRepository},
+    events::GameEvent,
+};
+use crate::application::{standings_service::StandingsService, transaction_service::TransactionService};
+
+pub struct BankruptcyService {
+    game_repo: Arc<dyn GameRepository + Send + Sync>,
+    participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+    property_repo: Arc<dyn PropertyRepository + Send + Sync>,
+    transaction_service: Arc<TransactionService>,
+    standings_service: Arc<StandingsService>,
+    tx: tokio::sync::broadcast::Sender<GameEvent>,
+}
+
+impl BankruptcyService {
+    pub fn new(
+        game_repo: Arc<dyn GameRepository + Send + Sync>,
+        participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+        property_repo: Arc<dyn PropertyRepository + Send + Sync>,
+            transaction_service: Arc<TransactionService>,
+        standings_service: Arc<StandingsService>,
+        tx: tokio::sync::broadcast::Sender<GameEvent>,
+    ) -> Self {
+        Self { game_repo, participant_repo, property_repo, transaction_service, standings_service, tx }
+    }
+
+    /// Take the debtor out of the game: remaining cash and properties go to the creditor
+    /// (or back to the Bank), their turn is dropped, and the last player standing wins.
+    pub async fn declare_bankruptcy(&self, game_id: Uuid, debtor_id: Uuid, creditor_id: Option<Uuid>) -> Result<(), anyhow::Error> {
+        let participants = self.participant_repo.find_by_game_id(game_id).await?;
+        let debtor = participants.iter().find(|p| p.id == debtor_id)
+            .ok_or_else(|| anyhow::anyhow!("Participant not found"))?;
+
+        if debtor.bankrupt_at.is_some() {
+            return Err(anyhow::anyhow!("Participant is already bankrupt"));
+        }
+
+        // 1. Seize assets, default their loans and drop them from the turn order, all in one batch
+        let mut legs = Vec::new();
+        if debtor.balance > BigDecimal::zero() {
+            legs.push(TransferLeg {
+                from_participant_id: Some(debtor.id),
+                to_participant_id: creditor_id,
+                amount: debtor.balance.clone(),
+                details: TransferDetails::new(TransactionCategory::Bankruptcy, "Bankruptcy: remaining cash"),
+            });
+        }
+
+        let mut ownership: Vec<OwnershipChange> = self.property_repo.find_participant_properties(game_id, debtor.id).await?
+            .into_iter()
+            .map(|pp| match creditor_id {
+                Some(to) => OwnershipChange::Transfer { property_id: pp.property_id, from_participant_id: debtor.id, to_participant_id: to },
+                None => OwnershipChange::Release { property_id: pp.property_id, from_participant_id: debtor.id },
+            })
+            .collect();
+        ownership.push(OwnershipChange::DeclareBankrupt { participant_id: debtor.id });
+
+        let result = self.transaction_service.execute_batch(game_id, legs, ownership).await?;
+        for pp in result.ownership {
+            let _ = self.tx.send(GameEvent::PropertyUpdated(pp));
+        }
+        for loan in result.loans {
+            let _ = self.tx.send(GameEvent::LoanUpdated(loan));
+        }
+
+        let game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        tracing::info!("Participant {} in game {} is bankrupt (creditor {:?})", debtor.id, game_id, creditor_id);
+        let _ = self.tx.send(GameEvent::ParticipantBankrupt { game_id, participant_id: debtor.id, creditor_participant_id: creditor_id });
+        if let Some(current) = game.current_turn_user_id {
+            let _ = self.tx.send(GameEvent::TurnUpdated { game_id, current_turn_user_id: current });
+        }
+
+        // 2. Last one standing wins
+        let solvent: Vec<Uuid> = participants.iter()
+            .filter(|p| p.id != debtor.id && p.bankrupt_at.is_none())
+            .map(|p| p.id)
+            .collect();
+        if solvent.len() == 1 && game.status == GameStatus::ACTIVE.to_string() {
+            self.standings_service.end_game(game_id, WinReason::Bankruptcy, Some(solvent[0])).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -3,30 +3,35 @@ use uuid::Uuid;
 use tokio::sync::broadcast;
 use rand::prelude::IndexedRandom; 
 use crate::domain::{
-    repositories::{CardRepository, TransactionRepository, GameRepository, ParticipantRepository},
-    entities::{Card, ParticipantCard, GameBovedaMarket, Transaction}, 
+    repositories::{CardRepository, GameRepository, ParticipantRepository},
+    entities::{Card, CashMode, OwnershipChange, ParticipantCard, GameBovedaMarket, TransactionCategory, TransferDetails, TransferLeg, WinReason}, 
     events::GameEvent, 
 };
-use bigdecimal::BigDecimal;
+use crate::application::{cash_service::CashService, standings_service::StandingsService, transaction_service::TransactionService};
+use bigdecimal::{BigDecimal, ToPrimitive};
 
 #[derive(Clone)]
 pub struct CardService {
     card_repo: Arc<dyn CardRepository + Send + Sync>,
-    transaction_repo: Arc<dyn TransactionRepository + Send + Sync>,
     game_repo: Arc<dyn GameRepository + Send + Sync>,
     participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+    transaction_service: Arc<TransactionService>,
+    standings_service: Arc<StandingsService>,
+    cash_service: Arc<CashService>,
     tx: broadcast::Sender<GameEvent>,
 }
 
 impl CardService {
     pub fn new(
         card_repo: Arc<dyn CardRepository + Send + Sync>,
-        transaction_repo: Arc<dyn TransactionRepository + Send + Sync>,
         game_repo: Arc<dyn GameRepository + Send + Sync>,
         participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+        transaction_service: Arc<TransactionService>,
+        standings_service: Arc<StandingsService>,
+        cash_service: Arc<CashService>,
         tx: broadcast::Sender<GameEvent>,
     ) -> Self {
-        Self { card_repo, transaction_repo, game_repo, participant_repo, tx }
+        Self { card_repo, game_repo, participant_repo, transaction_service, standings_service, cash_service, tx }
     }
 
     // --- Standard Cards (Arca/Fortuna) ---
@@ -76,35 +81,44 @@ impl CardService {
                     .into_iter().find(|p| p.user_id == user_id)
                     .ok_or(anyhow::anyhow!("User not participant"))?;
 
-                 // Create Transaction
-                 let _ = self.transaction_repo.execute_transfer(Transaction {
-                     id: Uuid::new_v4(),
+                 self.transaction_service.transfer(
                      game_id,
-                     from_participant_id: None,
-                     to_participant_id: Some(detail.id),
-                     amount: amt.clone(),
-                     description: Some(card.title.clone()),
-                     created_at: Some(time::OffsetDateTime::now_utc())
-                 }).await?;
+                     None, // Bank
+                     Some(detail.id),
+                     amt.clone(),
+                     TransferDetails::new(TransactionCategory::Card, card.title.clone()).card(card.id),
+                 ).await?;
             } 
             else if card.action_type.as_deref() == Some("pay_bank") {
                  let detail = self.participant_repo.find_details_by_game_id(game_id).await?
                     .into_iter().find(|p| p.user_id == user_id)
                     .ok_or(anyhow::anyhow!("User not participant"))?;
 
-                 let _ = self.transaction_repo.execute_transfer(Transaction {
-                     id: Uuid::new_v4(),
+                 // Paying the Bank may feed the jackpot
+                 self.transaction_service.transfer(
                      game_id,
-                     from_participant_id: Some(detail.id),
-                     to_participant_id: None,
-                     amount: amt.clone(),
-                     description: Some(card.title.clone()),
-                     created_at: Some(time::OffsetDateTime::now_utc())
-                 }).await?;
+                     Some(detail.id),
+                     None, // Bank
+                     amt.clone(),
+                     TransferDetails::new(TransactionCategory::Card, card.title.clone()).card(card.id),
+                 ).await?;
+             }
+            else if card.action_type.as_deref() == Some("collect_denomination") {
+                 // "Todos los de 50": only meaningful when the game tracks bills
+                 let game = self.game_repo.find_by_id(game_id).await?
+                    .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+                 if game.house_rules.cash_mode == CashMode::Denominations {
+                     let detail = self.participant_repo.find_details_by_game_id(game_id).await?
+                        .into_iter().find(|p| p.user_id == user_id)
+                        .ok_or(anyhow::anyhow!("User not participant"))?;
+                     let denomination = amt.to_i64().ok_or_else(|| anyhow::anyhow!("Invalid denomination"))?;
+                     let details = TransferDetails::new(TransactionCategory::Card, card.title.clone()).card(card.id);
+                     self.cash_service.collect_denomination(game_id, detail.id, denomination, details).await?;
+                 }
              }
         }
         
-        return Ok(card.clone());
+        Ok(card.clone())
     }
 
     // --- Boveda Market ---
@@ -140,14 +154,14 @@ impl CardService {
         // Uniqueness Logic
         let owned_ids = self.card_repo.find_all_participant_cards_in_game(game_id).await?;
         let market_cards = self.card_repo.get_boveda_market(game_id).await?;
-        let active_ids: Vec<Uuid> = market_cards.iter().map(|m| m.card_id).chain(owned_ids.into_iter()).collect();
+        let active_ids: Vec<Uuid> = market_cards.iter().map(|m| m.card_id).chain(owned_ids).collect();
 
         // Available Pool
         let mut available_deck: Vec<Card> = all_boveda.into_iter().filter(|c| !active_ids.contains(&c.id)).collect();
 
         if available_deck.is_empty() {
              tracing::warn!("No more unique Boveda cards available to refill market.");
-             return Ok(self.card_repo.get_boveda_market(game_id).await?);
+             return self.card_repo.get_boveda_market(game_id).await;
         }
         
         // Shuffle to randomize
@@ -208,27 +222,32 @@ impl CardService {
         // Assuming this applies to properties, but if it applies here:
         // Let's stick to La Bóveda effect for now as explicit in Boveda description.
 
-        // 3. Deduct Funds
-        self.transaction_repo.execute_transfer(Transaction {
-             id: Uuid::new_v4(),
-             game_id,
-             from_participant_id: Some(detail.id),
-             to_participant_id: recipient_id,
-             amount: final_cost,
-             description: Some(format!("Bought Boveda Card: {}", item.title.as_deref().unwrap_or("Unknown"))),
-             created_at: Some(time::OffsetDateTime::now_utc())
-        }).await?;
-
-        // 4. Add to Inventory
-        let pc = self.card_repo.add_to_inventory(detail.id, item.card_id).await?;
-
-        // 5. Remove from Market
-        self.card_repo.clear_boveda_market_slot(game_id, slot_index).await?;
-        
-        // 6. Refresh Market immediately
+        // 3. Pay and move the card from the slot into the inventory together
+        // (paying the Bank may feed the jackpot)
+        let result = self.transaction_service.execute_batch(
+            game_id,
+            vec![TransferLeg {
+                from_participant_id: Some(detail.id),
+                to_participant_id: recipient_id,
+                amount: final_cost,
+                details: TransferDetails::new(
+                    TransactionCategory::Card,
+                    format!("Bought Boveda Card: {}", item.title.as_deref().unwrap_or("Unknown")),
+                ).card(item.card_id),
+            }],
+            vec![OwnershipChange::TakeBovedaCard { slot_index, card_id: item.card_id, participant_id: detail.id }],
+        ).await?;
+        let inventory_id = result.cards.first().copied()
+            .ok_or_else(|| anyhow::anyhow!("Card was not added to the inventory"))?;
+        let pc = self.card_repo.get_inventory(detail.id).await?
+            .into_iter()
+            .find(|c| c.id == inventory_id)
+            .ok_or_else(|| anyhow::anyhow!("Card was not added to the inventory"))?;
+
+        // 4. Refresh Market immediately
         self.refresh_boveda_market(game_id).await?;
 
-        // 7. Broadcast Market Update
+        // 5. Broadcast Market Update
         let _ = self.tx.send(GameEvent::MarketUpdated { game_id });
 
         Ok(pc)
@@ -270,7 +289,7 @@ impl CardService {
          let title = card_item.title.as_deref().unwrap_or("");
          
          // --- INSTANT WIN CHECK ---
-         let win_cards = vec![
+         let win_cards = [
              "Tren de Victorias", 
              "Casa del Éxito", 
              "Campeón Doble", 
@@ -281,18 +300,10 @@ impl CardService {
          ];
 
          if win_cards.contains(&title) {
-             // Trigger Win
-             if let Some(mut game) = self.game_repo.find_by_id(game_id).await? {
-                 game.status = "FINISHED".to_string(); // Or use Enum if imported
-                 game.ended_at = Some(time::OffsetDateTime::now_utc());
-                 self.game_repo.update(game).await?;
-                 
-                 // Broadcast Game Ended Event
-                 let _ = self.tx.send(GameEvent::GameUpdated { id: game_id, status: "FINISHED".to_string() });
-                 
-                 tracing::info!("Game {} won by user {} via card {}", game_id, user_id, title);
-                 return Ok(());
-             }
+             // Trigger Win (ends the game, stores standings and broadcasts GameEnded)
+             self.standings_service.end_game(game_id, WinReason::Card, Some(detail.id)).await?;
+             tracing::info!("Game {} won by user {} via card {}", game_id, user_id, title);
+             return Ok(());
          }
          
          // Handle regular effects
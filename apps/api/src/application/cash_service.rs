@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use bigdecimal::BigDecimal;
+use crate::domain::{
+    cash::DENOMINATIONS,
+    entities::{CashMode, GameSession, Transaction, TransferDetails, TransferLeg},
+    repositories::{GameRepository, ParticipantRepository},
+    events::GameEvent,
+};
+use crate::application::transaction_service::TransactionService;
+
+// Denomination mode: money as physical bills instead of a single balance
+pub struct CashService {
+    game_repo: Arc<dyn GameRepository + Send + Sync>,
+    participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+    transaction_service: Arc<TransactionService>,
+    tx: tokio::sync::broadcast::Sender<GameEvent>,
+}
+
+impl CashService {
+    pub fn new(
+        game_repo: Arc<dyn GameRepository + Send + Sync>,
+        participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+        transaction_service: Arc<TransactionService>,
+        tx: tokio::sync::broadcast::Sender<GameEvent>,
+    ) -> Self {
+        Self { game_repo, participant_repo, transaction_service, tx }
+    }
+
+    /// Host or banker switches the game between plain balances and bills. Converting to bills
+    /// changes every balance into the fewest bills; converting back just drops the wallets.
+    pub async fn set_cash_mode(&self, game_id: Uuid, user_id: Uuid, mode: CashMode) -> Result<GameSession, anyhow::Error> {
+        let mut game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        if game.host_user_id != user_id && game.banker_id() != user_id {
+            return Err(anyhow::anyhow!("Only the host or the banker can change the cash mode"));
+        }
+        if game.house_rules.cash_mode == mode {
+            return Ok(game);
+        }
+
+        game.house_rules.cash_mode = mode;
+        let game = self.game_repo.set_cash_mode(game).await?;
+
+        let _ = self.tx.send(GameEvent::CashModeChanged { game_id, cash_mode: mode });
+        Ok(game)
+    }
+
+    /// Take every bill of one denomination from everyone else ("todos los de 50")
+    pub async fn collect_denomination(&self, game_id: Uuid, participant_id: Uuid, denomination: i64, details: TransferDetails) -> Result<Vec<Transaction>, anyhow::Error> {
+        if !DENOMINATIONS.contains(&denomination) {
+            return Err(anyhow::anyhow!("There is no {} bill", denomination));
+        }
+
+        let game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        if game.house_rules.cash_mode != CashMode::Denominations {
+            return Err(anyhow::anyhow!("The game does not track bills"));
+        }
+
+        let participants = self.participant_repo.find_by_game_id(game_id).await?;
+        if !participants.iter().any(|p| p.id == participant_id) {
+            return Err(anyhow::anyhow!("Participant not found"));
+        }
+
+        let legs: Vec<TransferLeg> = participants.iter()
+            .filter(|p| p.id != participant_id && p.bankrupt_at.is_none())
+            .filter_map(|p| {
+                let bills = p.bills.as_ref()?.0.all_of(denomination);
+                (!bills.0.is_empty()).then(|| TransferLeg {
+                    from_participant_id: Some(p.id),
+                    to_participant_id: Some(participant_id),
+                    amount: BigDecimal::from(bills.total()),
+                    details: details.clone().bills(bills),
+                })
+            })
+            .collect();
+
+        if legs.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(self.transaction_service.execute_batch(game_id, legs, Vec::new()).await?.transactions)
+    }
+}
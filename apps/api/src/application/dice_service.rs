@@ -2,10 +2,10 @@ use crate::domain::events::GameEvent;
 use tokio::sync::broadcast;
 use std::sync::Arc;
 use uuid::Uuid;
-use crate::domain::entities::{DiceRoll}; 
+use crate::domain::entities::{DiceRoll, InterestPeriod, TransactionCategory, TransferDetails}; 
 use crate::infrastructure::postgres::dice_repository::PostgresDiceRepository;
 use crate::domain::repositories::ParticipantRepository;
-use crate::application::transaction_service::TransactionService;
+use crate::application::{loan_service::LoanService, transaction_service::TransactionService};
 use bigdecimal::BigDecimal;
 use rand::Rng;
 
@@ -13,6 +13,7 @@ pub struct DiceService {
     dice_repo: Arc<PostgresDiceRepository>,
     participant_repo: Arc<dyn ParticipantRepository + Send + Sync>, 
     transaction_service: Arc<TransactionService>,
+    loan_service: Arc<LoanService>,
     tx: broadcast::Sender<GameEvent>,
 }
 
@@ -21,9 +22,10 @@ impl DiceService {
         dice_repo: Arc<PostgresDiceRepository>, 
         participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
         transaction_service: Arc<TransactionService>,
+        loan_service: Arc<LoanService>,
         tx: broadcast::Sender<GameEvent>
     ) -> Self {
-        Self { dice_repo, participant_repo, transaction_service, tx }
+        Self { dice_repo, participant_repo, transaction_service, loan_service, tx }
     }
 
     pub async fn roll_dice(&self, game_id: Uuid, user_id: Uuid, sides: i32, count: i32, auto_salary: bool) -> Result<DiceRoll, anyhow::Error> {
@@ -60,9 +62,14 @@ impl DiceService {
                         None, // From Bank 
                         Some(participant.id), 
                         BigDecimal::from(200), 
-                        Some("Salary (Passed Go)".to_string())
+                        TransferDetails::new(TransactionCategory::Salary, "Salary (Passed Go)")
                     ).await;
                 }
+
+                // Lap-based loan interest accrues whether or not salary is automatic
+                if let Err(e) = self.loan_service.on_period_end(game_id, participant.id, InterestPeriod::Lap).await {
+                    tracing::error!("Loan accrual failed for participant {}: {}", participant.id, e);
+                }
             }
             
             // Handle "Vayase a la Carcel" (Space 30)
@@ -1,17 +1,24 @@
 use std::sync::Arc;
 use uuid::Uuid;
-use bigdecimal::BigDecimal;
+use bigdecimal::{BigDecimal, Zero};
 use rand::{rng, Rng};
 use rand::distr::Alphanumeric;
 use crate::domain::{
-    entities::{GameSession, GameParticipant, GameStatus},
+    cash::Bills,
+    entities::{TransactionCategory, TransferDetails, GameSession, GameParticipant, GameStatus, GameMode, HouseRules, CashMode, InterestPeriod, WinReason},
     repositories::{GameRepository, ParticipantRepository},
 };
+use crate::application::{loan_service::LoanService, standings_service::StandingsService};
+
+// What the Bank hands every player on joining
+const INITIAL_FUNDING: i64 = 1500;
 
 pub struct GameService {
     game_repo: Arc<dyn GameRepository + Send + Sync>,
     participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
     transaction_service: Arc<crate::application::transaction_service::TransactionService>,
+    standings_service: Arc<StandingsService>,
+    loan_service: Arc<LoanService>,
     tx: tokio::sync::broadcast::Sender<crate::domain::events::GameEvent>,
 }
 
@@ -20,23 +27,17 @@ impl GameService {
         game_repo: Arc<dyn GameRepository + Send + Sync>,
         participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
         transaction_service: Arc<crate::application::transaction_service::TransactionService>,
+        standings_service: Arc<StandingsService>,
+        loan_service: Arc<LoanService>,
         tx: tokio::sync::broadcast::Sender<crate::domain::events::GameEvent>,
     ) -> Self {
-        Self { game_repo, participant_repo, transaction_service, tx }
+        Self { game_repo, participant_repo, transaction_service, standings_service, loan_service, tx }
     }
 
     pub async fn create_game(&self, host_user_id: Uuid) -> Result<GameSession, anyhow::Error> {
-        // Generate random 4-char code
-        let code: String = rng()
-            .sample_iter(&Alphanumeric)
-            .take(4)
-            .map(char::from)
-            .collect();
-        let code = code.to_uppercase(); // Ensure uppercase for better UX
-
         let game = GameSession {
             id: Uuid::new_v4(),
-            code,
+            code: generate_code(),
             host_user_id,
             name: "New Monopoly Game".to_string(), // Default name
             status: GameStatus::WAITING.to_string(),
@@ -45,6 +46,14 @@ impl GameService {
             ended_at: None,
             current_turn_user_id: None,
             turn_order: None,
+            winner_participant_id: None,
+            win_reason: None,
+            house_rules: Default::default(),
+            deadline_at: None,
+            rematch_game_id: None,
+            banker_user_id: None,
+            bank_balance: None,
+            version: 0,
         };
 
         let created_game = self.game_repo.create(game).await?;
@@ -55,6 +64,70 @@ impl GameService {
         Ok(created_game)
     }
 
+    /// Start a new WAITING game from a finished one: same host, name, house rules and players.
+    /// With `reverse_turn_order` the previous order is kept, backwards, instead of rolling initiative again.
+    pub async fn rematch(&self, game_id: Uuid, user_id: Uuid, reverse_turn_order: bool) -> Result<GameSession, anyhow::Error> {
+        let old_game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        if old_game.host_user_id != user_id {
+            return Err(anyhow::anyhow!("Only host can start a rematch"));
+        }
+        if old_game.status != GameStatus::FINISHED.to_string() {
+            return Err(anyhow::anyhow!("Only finished games can be rematched"));
+        }
+        if old_game.rematch_game_id.is_some() {
+            return Err(anyhow::anyhow!("A rematch was already created for this game"));
+        }
+
+        let turn_order = if reverse_turn_order {
+            old_game.turn_order.clone().map(|order| {
+                let mut list = order.0;
+                list.reverse();
+                sqlx::types::Json(list)
+            })
+        } else {
+            None
+        };
+
+        let game = GameSession {
+            id: Uuid::new_v4(),
+            code: generate_code(),
+            host_user_id: old_game.host_user_id,
+            name: old_game.name.clone(),
+            status: GameStatus::WAITING.to_string(),
+            jackpot_balance: BigDecimal::from(0),
+            created_at: Some(time::OffsetDateTime::now_utc()),
+            ended_at: None,
+            current_turn_user_id: None,
+            turn_order,
+            winner_participant_id: None,
+            win_reason: None,
+            house_rules: old_game.house_rules.clone(),
+            deadline_at: None,
+            rematch_game_id: None,
+            banker_user_id: None,
+            bank_balance: None,
+            version: 0,
+        };
+
+        // Host first, then everybody else in their original join order
+        let mut participants = self.participant_repo.find_by_game_id(game_id).await?;
+        participants.sort_by_key(|p| (p.user_id != old_game.host_user_id, p.joined_at));
+        let user_ids = participants.into_iter().map(|p| p.user_id).collect();
+
+        // All or nothing: a second rematch of the same game conflicts instead of leaving a stray game behind
+        let new_game = self.game_repo.create_rematch(old_game, game, user_ids, BigDecimal::from(INITIAL_FUNDING)).await?;
+
+        let _ = self.tx.send(crate::domain::events::GameEvent::RematchCreated {
+            game_id,
+            new_game_id: new_game.id,
+            code: new_game.code.clone(),
+        });
+
+        Ok(new_game)
+    }
+
     pub async fn join_game_with_code(&self, code: String, user_id: Uuid) -> Result<GameParticipant, anyhow::Error> {
         let game = self.game_repo.find_by_code(&code).await?
             .ok_or_else(|| anyhow::anyhow!("Game not found with code {}", code))?;
@@ -85,6 +158,9 @@ impl GameService {
             balance: BigDecimal::from(0), // Start with 0, then transfer 1500
             position: 0,
             joined_at: None,
+            bankrupt_at: None,
+            // An empty wallet, filled by the initial funding below
+            bills: (game.house_rules.cash_mode == CashMode::Denominations).then(|| sqlx::types::Json(Bills::default())),
         };
 
         let p = self.participant_repo.add_participant(participant).await?;
@@ -94,8 +170,8 @@ impl GameService {
             game_id,
             None, // From Bank
             Some(p.id),
-            BigDecimal::from(1500),
-            Some("Initial Funding".to_string())
+            BigDecimal::from(INITIAL_FUNDING),
+            TransferDetails::new(TransactionCategory::Salary, "Initial Funding")
         ).await?;
 
         // Broadcast Event
@@ -103,7 +179,7 @@ impl GameService {
             id: p.id,
             user_id: p.user_id,
             game_id: p.game_id,
-            balance: BigDecimal::from(1500), // Optimistic update for event? Or fetch fresh?
+            balance: BigDecimal::from(INITIAL_FUNDING), // Optimistic update for event? Or fetch fresh?
             // Actually, transfer updates underlying repo, but `p` is stale.
             // Let's assume frontend handles it via Transaction created event or we send updated P.
             // For now, let's send 1500 explicitly in event so UI shows it immediately.
@@ -135,6 +211,7 @@ impl GameService {
         name: Option<String>, 
         status: Option<String>,
         initiative_rolls: Option<std::collections::HashMap<Uuid, i32>>,
+        house_rules: Option<HouseRules>,
     ) -> Result<GameSession, anyhow::Error> {
         let mut game = self.game_repo.find_by_id(game_id).await?
             .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
@@ -147,7 +224,22 @@ impl GameService {
             game.name = n;
         }
 
+        if let Some(rules) = house_rules {
+            if game.status != GameStatus::WAITING.to_string() {
+                return Err(anyhow::anyhow!("House rules can only be changed before the game starts"));
+            }
+            game.house_rules = sqlx::types::Json(rules);
+        }
+
+        let starting = status.as_deref() == Some("ACTIVE") && game.status != "ACTIVE";
         if let Some(s) = status {
+             // Host ends the game: persist other edits, then rank players and pick the winner
+             if s == GameStatus::FINISHED.to_string() && game.status != s {
+                 self.game_repo.update(game).await?;
+                 let result = self.standings_service.end_game(game_id, WinReason::HostDecision, None).await?;
+                 return Ok(result.game);
+             }
+
              // Handle Game Start
              if s == "ACTIVE" && game.status != "ACTIVE" {
                  // 1. Get Participants
@@ -156,9 +248,21 @@ impl GameService {
                      return Err(anyhow::anyhow!("Cannot start game with no participants"));
                  }
 
-                 // 2. Roll Initiative
+                 // 2. Roll Initiative, unless a preset order (rematch) still matches the players
+                 let preset_order = game.turn_order.as_ref()
+                     .map(|order| order.0.clone())
+                     .filter(|order| initiative_rolls.is_none()
+                         && order.len() == participants.len()
+                         && participants.iter().all(|p| order.contains(&p.user_id)));
+
                  let mut initiatives: Vec<(Uuid, i32)> = Vec::new();
-                 if let Some(rolls) = initiative_rolls {
+                 if let Some(order) = preset_order {
+                     // Fake descending rolls so the sort below keeps the preset order
+                     let count = order.len() as i32;
+                     for (i, uid) in order.into_iter().enumerate() {
+                         initiatives.push((uid, count - i as i32));
+                     }
+                 } else if let Some(rolls) = initiative_rolls {
                      for p in &participants {
                          let roll = rolls.get(&p.user_id).cloned().unwrap_or(0);
                          initiatives.push((p.user_id, roll));
@@ -171,12 +275,20 @@ impl GameService {
                  }
                  
                  // Sort descending by roll
-                 initiatives.sort_by(|a, b| b.1.cmp(&a.1));
+                 initiatives.sort_by_key(|&(_, roll)| std::cmp::Reverse(roll));
                  
                  let turn_order: Vec<Uuid> = initiatives.iter().map(|(uid, _)| *uid).collect();
                  
                  game.turn_order = Some(sqlx::types::Json(turn_order.clone()));
                  game.current_turn_user_id = Some(turn_order[0]);
+
+                 // Timed mode: the clock starts now
+                 if game.house_rules.mode == GameMode::Timed {
+                     let minutes = game.house_rules.time_limit_minutes
+                         .filter(|m| *m > 0)
+                         .ok_or_else(|| anyhow::anyhow!("Timed games need a positive time limit"))?;
+                     game.deadline_at = Some(time::OffsetDateTime::now_utc() + time::Duration::minutes(minutes));
+                 }
                  
                  // Broadcast Turn Update immediately (or let GameUpdated handle it)
                  let _ = self.tx.send(crate::domain::events::GameEvent::TurnUpdated { 
@@ -186,12 +298,23 @@ impl GameService {
              }
              
              game.status = s;
-             if game.status == GameStatus::FINISHED.to_string() {
-                 game.ended_at = Some(time::OffsetDateTime::now_utc());
-             }
         }
-        
-        self.game_repo.update(game).await
+
+        let jackpot_seed = game.house_rules.jackpot.seed_amount.clone();
+        let updated = self.game_repo.update(game).await?;
+
+        if starting {
+            // What is left in the box after the opening funds becomes the Bank's reserve
+            self.transaction_service.open_bank(game_id).await?;
+
+            // The Bank puts the opening seed into the jackpot
+            if jackpot_seed > BigDecimal::zero() {
+                self.transaction_service.seed_jackpot(game_id, jackpot_seed).await?;
+            }
+            return self.get_game(game_id).await;
+        }
+
+        Ok(updated)
     }
 
     pub async fn delete_game(&self, game_id: Uuid, user_id: Uuid) -> Result<(), anyhow::Error> {
@@ -202,7 +325,9 @@ impl GameService {
              return Err(anyhow::anyhow!("Only host can delete game"));
         }
 
-        self.game_repo.delete(game_id).await
+        self.game_repo.delete(game_id).await?;
+        let _ = self.tx.send(crate::domain::events::GameEvent::GameDeleted { game_id });
+        Ok(())
     }
     pub async fn get_game(&self, game_id: Uuid) -> Result<GameSession, anyhow::Error> {
         self.game_repo.find_by_id(game_id).await?
@@ -223,11 +348,21 @@ impl GameService {
         self.game_repo.find_played_by_user(user_id).await
     }
 
-    pub async fn update_participant_position(&self, game_id: Uuid, user_id: Uuid, position: i32) -> Result<(), anyhow::Error> {
-        // Validate game exists
-        let _ = self.game_repo.find_by_id(game_id).await?
+    /// Move a token by hand: players move their own, the host can move anyone's
+    pub async fn update_participant_position(&self, game_id: Uuid, requester_id: Uuid, user_id: Uuid, position: i32) -> Result<(), anyhow::Error> {
+        let game = self.game_repo.find_by_id(game_id).await?
             .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
 
+        if requester_id != user_id && game.host_user_id != requester_id {
+            return Err(anyhow::anyhow!("You can only move your own token"));
+        }
+        if !(0..40).contains(&position) {
+            return Err(anyhow::anyhow!("Position must be between 0 and 39"));
+        }
+        if !self.participant_repo.find_by_game_id(game_id).await?.iter().any(|p| p.user_id == user_id) {
+            return Err(anyhow::anyhow!("Participant not found"));
+        }
+
         // Update Position
         self.participant_repo.update_position(game_id, user_id, position).await?;
 
@@ -257,6 +392,20 @@ impl GameService {
         if game.current_turn_user_id != Some(user_id) {
              return Err(anyhow::anyhow!("It is not your turn!"));
         }
+
+        // Per-turn loan interest; a default can bankrupt the player and move the turn on by itself
+        if let Some(participant) = self.participant_repo.find_by_game_id(game_id).await?
+            .into_iter().find(|p| p.user_id == user_id)
+        {
+            if let Err(e) = self.loan_service.on_period_end(game_id, participant.id, InterestPeriod::Turn).await {
+                tracing::error!("Loan accrual failed for participant {}: {}", participant.id, e);
+            }
+            game = self.game_repo.find_by_id(game_id).await?
+                .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+            if game.current_turn_user_id != Some(user_id) || game.status != GameStatus::ACTIVE.to_string() {
+                return Ok(game);
+            }
+        }
         
         let order = game.turn_order.clone().ok_or_else(|| anyhow::anyhow!("No turn order defined"))?;
         let list = &order.0; // access inner vec via .0 (Json wrapper)
@@ -266,6 +415,13 @@ impl GameService {
             
         let next_idx = (idx + 1) % list.len();
         let next_user = list[next_idx];
+
+        // Timed mode with "finish the round": the deadline passed and the round just completed
+        let deadline_passed = game.deadline_at.is_some_and(|d| d <= time::OffsetDateTime::now_utc());
+        if next_idx == 0 && deadline_passed && game.house_rules.finish_round_at_deadline {
+            let result = self.standings_service.end_game(game_id, WinReason::TimeLimit, None).await?;
+            return Ok(result.game);
+        }
         
         game.current_turn_user_id = Some(next_user);
         
@@ -281,14 +437,76 @@ impl GameService {
     }
 }
 
-#[cfg(test)]
+// Random 4-char join code, uppercase for better UX
+fn generate_code() -> String {
+    let code: String = rng()
+        .sample_iter(&Alphanumeric)
+        .take(4)
+        .map(char::from)
+        .collect();
+    code.to_uppercase()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::repositories::{MockGameRepository, MockParticipantRepository, MockTransactionRepository, MockCardRepository};
+    use crate::domain::repositories::{MockGameRepository, MockParticipantRepository, MockTransactionRepository, MockCardRepository, MockPropertyRepository, MockStandingsRepository, MockLoanRepository, MockBankIouRepository};
     use crate::application::transaction_service::TransactionService;
     use mockall::predicate::*;
 
+    fn standings_service(tx: &tokio::sync::broadcast::Sender<crate::domain::events::GameEvent>) -> Arc<StandingsService> {
+        Arc::new(StandingsService::new(
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockParticipantRepository::new()),
+            Arc::new(MockPropertyRepository::new()),
+            Arc::new(MockStandingsRepository::new()),
+            Arc::new(MockLoanRepository::new()),
+            tx.clone(),
+        ))
+    }
+
+    fn loan_service(tx: &tokio::sync::broadcast::Sender<crate::domain::events::GameEvent>, tx_service: Arc<TransactionService>) -> Arc<LoanService> {
+        let bankruptcy_service = Arc::new(crate::application::bankruptcy_service::BankruptcyService::new(
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockParticipantRepository::new()),
+            Arc::new(MockPropertyRepository::new()),
+            tx_service.clone(),
+            standings_service(tx),
+            tx.clone(),
+        ));
+        Arc::new(LoanService::new(
+            Arc::new(MockLoanRepository::new()),
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockParticipantRepository::new()),
+            tx_service,
+            bankruptcy_service,
+            tx.clone(),
+        ))
+    }
+
+    fn waiting_game(host_user_id: Uuid) -> GameSession {
+        GameSession {
+            id: Uuid::new_v4(),
+            code: "ABCD".to_string(),
+            host_user_id,
+            name: "New Monopoly Game".to_string(),
+            status: "WAITING".to_string(),
+            jackpot_balance: BigDecimal::from(0),
+            created_at: None,
+            ended_at: None,
+            current_turn_user_id: None,
+            turn_order: None,
+            winner_participant_id: None,
+            win_reason: None,
+            house_rules: Default::default(),
+            deadline_at: None,
+            rematch_game_id: None,
+            banker_user_id: None,
+            bank_balance: None,
+            version: 0,
+        }
+    }
+
     #[tokio::test]
     async fn test_create_game_success() {
         let mut mock_game_repo = MockGameRepository::new();
@@ -322,6 +540,14 @@ mod tests {
                 ended_at: None,
                 current_turn_user_id: None,
                 turn_order: None,
+                winner_participant_id: None,
+                win_reason: None,
+                house_rules: Default::default(),
+                deadline_at: None,
+                rematch_game_id: None,
+                banker_user_id: None,
+                bank_balance: None,
+                version: 0,
             })));
 
         // 3. Expect find_by_game_id (idempotency check)
@@ -337,16 +563,18 @@ mod tests {
                 Ok(p)
             });
 
-        // 4. Expect Card Repo check for Bank Owner (called by TransactionService::transfer)
-        mock_card_repo.expect_find_owner_of_card_title()
-            .with(eq(game_id), eq("El Banco"))
-            .times(1)
-            .returning(|_, _| Ok(None));
+        // 4. No El Banco lookup: funding comes from the Bank, only payments to it are redirected
+        mock_card_repo.expect_find_owner_of_card_title().times(0);
+
+        // 5. The funding comes from the Bank, so its reserve rules are looked up (infinite by default)
+        let mut bank_game_repo = MockGameRepository::new();
+        bank_game_repo.expect_find_by_id()
+            .returning(move |id| Ok(Some(GameSession { id, ..waiting_game(host_id) })));
 
-        // 5. Expect Transfer (Initial Funding)
-        mock_tx_repo.expect_execute_transfer()
+        // 6. Expect Transfer (Initial Funding) as a single-leg batch
+        mock_tx_repo.expect_execute_batch()
              .times(1)
-             .returning(|t| Ok(t));
+             .returning(|batch| Ok(crate::domain::entities::BatchResult { transactions: batch.legs, ..Default::default() }));
 
         let (tx, _rx) = tokio::sync::broadcast::channel(10);
         
@@ -357,10 +585,13 @@ mod tests {
                                                        // transfer() uses card_repo for Bank Owner check.
                                                        // transfer() does NOT use participant_repo (it uses _participant_repo).
             Arc::new(mock_card_repo),
+            Arc::new(bank_game_repo),
+            Arc::new(MockBankIouRepository::new()),
+            standings_service(&tx),
             tx.clone()
         ));
 
-        let service = GameService::new(Arc::new(mock_game_repo), Arc::new(mock_part_repo), tx_service, tx);
+        let service = GameService::new(Arc::new(mock_game_repo), Arc::new(mock_part_repo), tx_service.clone(), standings_service(&tx), loan_service(&tx, tx_service), tx);
         let result = service.create_game(host_id).await;
 
         assert!(result.is_ok());
@@ -369,6 +600,57 @@ mod tests {
         assert_eq!(created.code.len(), 4);
     }
 
+    #[tokio::test]
+    async fn test_rematch_creates_the_game_in_one_call() {
+        let (host_id, early_id, late_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut finished = waiting_game(host_id);
+        finished.status = "FINISHED".to_string();
+        let game_id = finished.id;
+
+        let mut mock_game_repo = MockGameRepository::new();
+        mock_game_repo.expect_find_by_id().with(eq(game_id)).returning(move |_| Ok(Some(finished.clone())));
+        // Nothing is written piecemeal: the game, the funded players and the link commit together
+        mock_game_repo.expect_create().never();
+        mock_game_repo.expect_update().never();
+        mock_game_repo.expect_create_rematch()
+            .withf(move |old, game, user_ids, funding| {
+                old.id == game_id && game.status == "WAITING" && *user_ids == vec![host_id, early_id, late_id] && *funding == INITIAL_FUNDING
+            })
+            .times(1)
+            .returning(|_, game, _, _| Ok(game));
+
+        let mut mock_part_repo = MockParticipantRepository::new();
+        mock_part_repo.expect_find_by_game_id().with(eq(game_id)).returning(move |_| {
+            let joined = |user_id, minute| GameParticipant {
+                id: Uuid::new_v4(),
+                game_id,
+                user_id,
+                balance: BigDecimal::from(0),
+                position: 0,
+                joined_at: Some(time::OffsetDateTime::UNIX_EPOCH + time::Duration::minutes(minute)),
+                bankrupt_at: None,
+                bills: None,
+            };
+            Ok(vec![joined(late_id, 3), joined(host_id, 2), joined(early_id, 1)])
+        });
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(10);
+        let tx_service = Arc::new(TransactionService::new(
+            Arc::new(MockTransactionRepository::new()),
+            Arc::new(MockParticipantRepository::new()),
+            Arc::new(MockCardRepository::new()),
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockBankIouRepository::new()),
+            standings_service(&tx),
+            tx.clone()
+        ));
+
+        let service = GameService::new(Arc::new(mock_game_repo), Arc::new(mock_part_repo), tx_service.clone(), standings_service(&tx), loan_service(&tx, tx_service), tx);
+        let rematch = service.rematch(game_id, host_id, false).await.unwrap();
+
+        assert_ne!(rematch.id, game_id);
+    }
+
     #[tokio::test]
     async fn test_join_game_fails_if_active() {
         let mut mock_game_repo = MockGameRepository::new();
@@ -392,6 +674,14 @@ mod tests {
                 ended_at: None,
                 current_turn_user_id: None,
                 turn_order: None,
+                winner_participant_id: None,
+                win_reason: None,
+                house_rules: Default::default(),
+                deadline_at: None,
+                rematch_game_id: None,
+                banker_user_id: None,
+                bank_balance: None,
+                version: 0,
             })));
 
         let (tx, _rx) = tokio::sync::broadcast::channel(10);
@@ -399,10 +689,13 @@ mod tests {
             Arc::new(mock_tx_repo),
             Arc::new(MockParticipantRepository::new()),
             Arc::new(mock_card_repo),
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockBankIouRepository::new()),
+            standings_service(&tx),
             tx.clone()
         ));
 
-        let service = GameService::new(Arc::new(mock_game_repo), Arc::new(mock_part_repo), tx_service, tx);
+        let service = GameService::new(Arc::new(mock_game_repo), Arc::new(mock_part_repo), tx_service.clone(), standings_service(&tx), loan_service(&tx, tx_service), tx);
         let result = service.join_game(game_id, Uuid::new_v4()).await;
 
         assert!(result.is_err());
@@ -434,6 +727,14 @@ mod tests {
                 ended_at: None,
                 current_turn_user_id: None,
                 turn_order: None,
+                winner_participant_id: None,
+                win_reason: None,
+                house_rules: Default::default(),
+                deadline_at: None,
+                rematch_game_id: None,
+                banker_user_id: None,
+                bank_balance: None,
+                version: 0,
             })));
 
         mock_part_repo.expect_remove_participant()
@@ -445,10 +746,13 @@ mod tests {
             Arc::new(mock_tx_repo),
             Arc::new(MockParticipantRepository::new()),
             Arc::new(mock_card_repo),
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockBankIouRepository::new()),
+            standings_service(&tx),
             tx.clone()
         ));
 
-        let service = GameService::new(Arc::new(mock_game_repo), Arc::new(mock_part_repo), tx_service, tx);
+        let service = GameService::new(Arc::new(mock_game_repo), Arc::new(mock_part_repo), tx_service.clone(), standings_service(&tx), loan_service(&tx, tx_service), tx);
         let result = service.leave_game(game_id, user_id).await;
         assert!(result.is_ok());
     }
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use crate::domain::{
+    entities::IdempotencyRecord,
+    repositories::IdempotencyRepository,
+};
+
+pub enum IdempotencyClaim {
+    // First time this key is seen (or its claim went stale): run the request, then `complete` (or `release` on a lost race)
+    Fresh,
+    // Already answered: send the stored response again
+    Replay(IdempotencyRecord),
+    // The first request with this key is still running
+    InProgress,
+    // The key was used by another user, for another endpoint or with another body
+    Mismatch,
+}
+
+// A request that has not answered by then is presumed dead; a retry may run it again
+const IN_PROGRESS_TTL: time::Duration = time::Duration::minutes(2);
+
+// Retries of money-moving requests (flaky phone connections) must not execute twice
+pub struct IdempotencyService {
+    repo: Arc<dyn IdempotencyRepository + Send + Sync>,
+}
+
+impl IdempotencyService {
+    pub fn new(repo: Arc<dyn IdempotencyRepository + Send + Sync>) -> Self {
+        Self { repo }
+    }
+
+    pub async fn begin(&self, game_id: Uuid, user_id: Uuid, key: &str, endpoint: &str, body: &[u8]) -> Result<IdempotencyClaim, anyhow::Error> {
+        if key.is_empty() || key.len() > 255 {
+            return Err(anyhow::anyhow!("Idempotency-Key must be 1 to 255 characters"));
+        }
+
+        let request_hash = hex::encode(Sha256::digest(body));
+        let now = time::OffsetDateTime::now_utc();
+        let claimed = self.repo.claim(IdempotencyRecord {
+            game_id,
+            idempotency_key: key.to_string(),
+            user_id,
+            endpoint: endpoint.to_string(),
+            request_hash: request_hash.clone(),
+            status_code: None,
+            content_type: None,
+            response_body: None,
+            created_at: Some(now),
+        }, now - IN_PROGRESS_TTL).await?;
+        if claimed {
+            return Ok(IdempotencyClaim::Fresh);
+        }
+
+        Ok(match self.repo.find(game_id, key).await? {
+            Some(r) if r.user_id != user_id || r.endpoint != endpoint || r.request_hash != request_hash => IdempotencyClaim::Mismatch,
+            Some(r) if r.status_code.is_some() => IdempotencyClaim::Replay(r),
+            // Still running, or released a moment ago: either way the client should retry later
+            _ => IdempotencyClaim::InProgress,
+        })
+    }
+
+    pub async fn complete(&self, game_id: Uuid, key: &str, status_code: u16, content_type: Option<String>, body: Vec<u8>) -> Result<(), anyhow::Error> {
+        self.repo.complete(game_id, key, i32::from(status_code), content_type, body).await
+    }
+
+    pub async fn release(&self, game_id: Uuid, key: &str) -> Result<(), anyhow::Error> {
+        self.repo.release(game_id, key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::MockIdempotencyRepository;
+
+    #[tokio::test]
+    async fn test_begin_replays_answered_keys_only_to_the_same_request() {
+        let (game_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let stored = IdempotencyRecord {
+            game_id,
+            idempotency_key: "rent-42".to_string(),
+            user_id,
+            endpoint: "POST /games/x/transactions".to_string(),
+            request_hash: hex::encode(Sha256::digest(br#"{"amount":42}"#)),
+            status_code: Some(201),
+            content_type: Some("application/json".to_string()),
+            response_body: Some(b"{}".to_vec()),
+            created_at: None,
+        };
+
+        let mut repo = MockIdempotencyRepository::new();
+        repo.expect_claim().returning(|_, _| Ok(false));
+        repo.expect_find().returning(move |_, _| Ok(Some(stored.clone())));
+        let service = IdempotencyService::new(Arc::new(repo));
+
+        let body = br#"{"amount":42}"#;
+        let replay = service.begin(game_id, user_id, "rent-42", "POST /games/x/transactions", body).await.unwrap();
+        assert!(matches!(replay, IdempotencyClaim::Replay(r) if r.status_code == Some(201)));
+
+        let other_user = service.begin(game_id, Uuid::new_v4(), "rent-42", "POST /games/x/transactions", body).await.unwrap();
+        assert!(matches!(other_user, IdempotencyClaim::Mismatch));
+
+        // Same key, different payload: must not get the other payment's response
+        let other_body = service.begin(game_id, user_id, "rent-42", "POST /games/x/transactions", br#"{"amount":420}"#).await.unwrap();
+        assert!(matches!(other_body, IdempotencyClaim::Mismatch));
+
+        assert!(service.begin(game_id, user_id, "", "POST /games/x/transactions", body).await.is_err());
+    }
+}
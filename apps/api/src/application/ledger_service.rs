@@ -0,0 +1,394 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+use bigdecimal::{BigDecimal, Zero};
+use futures::{stream::BoxStream, StreamExt};
+use crate::domain::{
+    entities::{BalanceDiscrepancy, ExportFormat, LedgerEntry, LedgerSnapshot, ReconciliationReport, Transaction, TransactionCategory},
+    repositories::{GameRepository, ParticipantRepository, TransactionRepository},
+};
+
+// Audits the stored balances against the transactions table, which is the source of history
+pub struct LedgerService {
+    game_repo: Arc<dyn GameRepository + Send + Sync>,
+    participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+    transaction_repo: Arc<dyn TransactionRepository + Send + Sync>,
+}
+
+impl LedgerService {
+    pub fn new(
+        game_repo: Arc<dyn GameRepository + Send + Sync>,
+        participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+        transaction_repo: Arc<dyn TransactionRepository + Send + Sync>,
+    ) -> Self {
+        Self { game_repo, participant_repo, transaction_repo }
+    }
+
+    /// Host-only: replay every transaction of the game and compare with the stored balances and jackpot
+    pub async fn reconcile(&self, game_id: Uuid, user_id: Uuid) -> Result<ReconciliationReport, anyhow::Error> {
+        self.ensure_host(game_id, user_id, "reconcile").await?;
+        let snapshot = self.transaction_repo.ledger_snapshot(game_id).await?;
+        Ok(report(&snapshot))
+    }
+
+    /// Host-only: book an adjustment entry for every discrepancy so the history adds up to the
+    /// balances players currently see. Money does not move. Returns the report after the repair.
+    pub async fn repair(&self, game_id: Uuid, user_id: Uuid) -> Result<ReconciliationReport, anyhow::Error> {
+        self.ensure_host(game_id, user_id, "repair").await?;
+
+        let recorded = self.transaction_repo.repair_ledger(game_id, adjustments).await?;
+        if !recorded.is_empty() {
+            tracing::info!("Ledger of game {} repaired with {} adjustment(s)", game_id, recorded.len());
+        }
+
+        let snapshot = self.transaction_repo.ledger_snapshot(game_id).await?;
+        Ok(report(&snapshot))
+    }
+
+    async fn ensure_host(&self, game_id: Uuid, user_id: Uuid, action: &str) -> Result<(), anyhow::Error> {
+        let game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        if game.host_user_id != user_id {
+            return Err(anyhow::anyhow!("Only host can {} the ledger", action));
+        }
+        Ok(())
+    }
+
+    /// The whole game as CSV or JSON Lines, one line per entry with running balances per player.
+    /// Lines are produced as rows come out of the database, so large games are never held in memory.
+    /// Only the host and the players of the game may export it.
+    pub async fn export(&self, game_id: Uuid, user_id: Uuid, format: ExportFormat) -> Result<BoxStream<'static, Result<String, anyhow::Error>>, anyhow::Error> {
+        let game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        if game.host_user_id != user_id {
+            self.participant_repo.find_by_game_id(game_id).await?
+                .into_iter()
+                .find(|p| p.user_id == user_id)
+                .ok_or_else(|| anyhow::anyhow!("User is not a participant"))?;
+        }
+
+        let header = match format {
+            ExportFormat::Csv => Some(Ok(format!("{}\n", CSV_HEADER.join(",")))),
+            ExportFormat::Jsonl => None,
+        };
+
+        let lines = self.transaction_repo.stream_ledger(game_id)
+            .scan(HashMap::new(), move |balances, row| {
+                let line = row.and_then(|mut entry| {
+                    apply_running_balance(balances, &mut entry);
+                    format_entry(&entry, format)
+                });
+                futures::future::ready(Some(line))
+            });
+
+        Ok(futures::stream::iter(header).chain(lines).boxed())
+    }
+}
+
+const CSV_HEADER: [&str; 12] = [
+    "kind", "id", "occurred_at", "from", "to", "amount", "category", "description",
+    "from_participant_id", "to_participant_id", "from_balance_after", "to_balance_after",
+];
+
+// Balances start at zero like in `replay`; only transactions move them
+fn apply_running_balance(balances: &mut HashMap<Uuid, BigDecimal>, entry: &mut LedgerEntry) {
+    if entry.kind != "transaction" {
+        return;
+    }
+    let amount = entry.amount.clone().unwrap_or_default();
+    if let Some(from) = entry.from_participant_id {
+        let balance = balances.entry(from).or_default();
+        *balance -= &amount;
+        entry.from_balance_after = Some(balance.clone());
+    }
+    if let Some(to) = entry.to_participant_id {
+        let balance = balances.entry(to).or_default();
+        *balance += &amount;
+        entry.to_balance_after = Some(balance.clone());
+    }
+}
+
+fn format_entry(entry: &LedgerEntry, format: ExportFormat) -> Result<String, anyhow::Error> {
+    match format {
+        ExportFormat::Jsonl => Ok(format!("{}\n", serde_json::to_string(entry)?)),
+        ExportFormat::Csv => {
+            let occurred_at = entry.occurred_at
+                .and_then(|t| t.format(&time::format_description::well_known::Rfc3339).ok());
+            let fields = [
+                Some(entry.kind.clone()),
+                Some(entry.id.to_string()),
+                occurred_at,
+                entry.from_name.clone(),
+                entry.to_name.clone(),
+                entry.amount.as_ref().map(|a| a.to_string()),
+                entry.category.clone(),
+                entry.description.clone(),
+                entry.from_participant_id.map(|id| id.to_string()),
+                entry.to_participant_id.map(|id| id.to_string()),
+                entry.from_balance_after.as_ref().map(|b| b.to_string()),
+                entry.to_balance_after.as_ref().map(|b| b.to_string()),
+            ];
+            let cells: Vec<String> = fields.iter().map(|f| csv_cell(f.as_deref().unwrap_or(""))).collect();
+            Ok(format!("{}\n", cells.join(",")))
+        }
+    }
+}
+
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn report(snapshot: &LedgerSnapshot) -> ReconciliationReport {
+    let (expected_balances, expected_jackpot) = replay(&snapshot.transactions);
+
+    let discrepancies: Vec<BalanceDiscrepancy> = snapshot.participants.iter()
+        .filter_map(|p| {
+            let expected = expected_balances.get(&p.id).cloned().unwrap_or_default();
+            let difference = &p.balance - &expected;
+            (!difference.is_zero()).then(|| BalanceDiscrepancy {
+                participant_id: p.id,
+                user_id: p.user_id,
+                expected,
+                actual: p.balance.clone(),
+                difference,
+            })
+        })
+        .collect();
+
+    let jackpot_difference = &snapshot.jackpot_balance - &expected_jackpot;
+    let is_consistent = discrepancies.is_empty() && jackpot_difference.is_zero();
+
+    if !is_consistent {
+        tracing::warn!("Ledger drift in game {}: {} participant(s), jackpot off by {}", snapshot.game_id, discrepancies.len(), jackpot_difference);
+    }
+
+    ReconciliationReport {
+        game_id: snapshot.game_id,
+        transactions_replayed: snapshot.transactions.len(),
+        discrepancies,
+        expected_jackpot,
+        actual_jackpot: snapshot.jackpot_balance.clone(),
+        jackpot_difference,
+        is_consistent,
+    }
+}
+
+// The entries that make the history add up to the snapshot's balances and jackpot
+fn adjustments(snapshot: &LedgerSnapshot) -> Vec<Transaction> {
+    let report = report(snapshot);
+
+    let mut entries: Vec<Transaction> = report.discrepancies.iter()
+        .map(|d| {
+            // Positive drift: the participant holds more than the history explains
+            let (from, to) = if d.difference > BigDecimal::zero() {
+                (None, Some(d.participant_id))
+            } else {
+                (Some(d.participant_id), None)
+            };
+            adjustment(snapshot.game_id, from, to, d.difference.abs(), BigDecimal::zero())
+        })
+        .collect();
+
+    if !report.jackpot_difference.is_zero() {
+        entries.push(adjustment(snapshot.game_id, None, None, report.jackpot_difference.abs(), report.jackpot_difference));
+    }
+    entries
+}
+
+fn adjustment(game_id: Uuid, from: Option<Uuid>, to: Option<Uuid>, amount: BigDecimal, jackpot_delta: BigDecimal) -> Transaction {
+    Transaction {
+        id: Uuid::new_v4(),
+        game_id,
+        from_participant_id: from,
+        to_participant_id: to,
+        amount,
+        description: Some("Ledger reconciliation adjustment".to_string()),
+        created_at: Some(time::OffsetDateTime::now_utc()),
+        category: TransactionCategory::Adjustment,
+        property_id: None,
+        card_id: None,
+        trade_id: None,
+        auction_id: None,
+        loan_id: None,
+        group_id: None,
+        jackpot_delta,
+        reverses_transaction_id: None,
+        reversed_by_transaction_id: None,
+    }
+}
+
+// Balances start at zero (initial funding is a transaction); the jackpot follows the recorded deltas
+fn replay(transactions: &[Transaction]) -> (HashMap<Uuid, BigDecimal>, BigDecimal) {
+    let mut balances: HashMap<Uuid, BigDecimal> = HashMap::new();
+    let mut jackpot = BigDecimal::zero();
+
+    for t in transactions {
+        if let Some(from) = t.from_participant_id {
+            *balances.entry(from).or_default() -= &t.amount;
+        }
+        if let Some(to) = t.to_participant_id {
+            *balances.entry(to).or_default() += &t.amount;
+        }
+        jackpot += &t.jackpot_delta;
+    }
+
+    (balances, jackpot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(from: Option<Uuid>, to: Option<Uuid>, amount: i32, jackpot_delta: i32) -> Transaction {
+        let mut t = adjustment(Uuid::nil(), from, to, BigDecimal::from(amount), BigDecimal::from(jackpot_delta));
+        t.category = TransactionCategory::Manual;
+        t
+    }
+
+    #[test]
+    fn test_replay() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+
+        let (balances, jackpot) = replay(&[
+            entry(None, Some(alice), 1500, 0),
+            entry(None, Some(bob), 1500, 0),
+            entry(Some(alice), Some(bob), 200, 0), // rent
+            entry(Some(bob), None, 100, 100),      // tax into the jackpot
+            entry(None, None, 50, 50),             // jackpot-only entry
+            entry(None, Some(alice), 150, -150),   // jackpot claim
+        ]);
+
+        assert_eq!(balances[&alice], BigDecimal::from(1500 - 200 + 150));
+        assert_eq!(balances[&bob], BigDecimal::from(1500 + 200 - 100));
+        assert_eq!(jackpot, BigDecimal::zero());
+    }
+
+    #[test]
+    fn test_adjustments_cover_the_drift() {
+        use crate::domain::entities::GameParticipant;
+
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let participant = |id: Uuid, balance: i32| GameParticipant {
+            id,
+            game_id: Uuid::nil(),
+            user_id: Uuid::new_v4(),
+            balance: BigDecimal::from(balance),
+            position: 0,
+            joined_at: None,
+            bankrupt_at: None,
+            bills: None,
+        };
+        let snapshot = LedgerSnapshot {
+            game_id: Uuid::nil(),
+            // Alice holds 100 more than her history, Bob is right, the jackpot is 50 short
+            participants: vec![participant(alice, 1600), participant(bob, 1500)],
+            jackpot_balance: BigDecimal::from(50),
+            transactions: vec![
+                entry(None, Some(alice), 1500, 0),
+                entry(None, Some(bob), 1500, 0),
+                entry(None, None, 100, 100),
+            ],
+        };
+
+        let entries = adjustments(&snapshot);
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].from_participant_id, entries[0].to_participant_id), (None, Some(alice)));
+        assert_eq!(entries[0].amount, BigDecimal::from(100));
+        assert_eq!(entries[1].jackpot_delta, BigDecimal::from(-50));
+
+        // Replaying the adjustments as well leaves nothing to repair
+        let mut repaired = snapshot.clone();
+        repaired.transactions.extend(entries);
+        assert!(report(&repaired).is_consistent);
+        assert!(adjustments(&repaired).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_csv_with_running_balances() {
+        use crate::domain::entities::{GameParticipant, GameSession};
+        use crate::domain::repositories::{MockGameRepository, MockParticipantRepository, MockTransactionRepository};
+
+        let (alice, alice_user) = (Uuid::new_v4(), Uuid::new_v4());
+        let row = |kind: &str, from: Option<Uuid>, amount: Option<i32>, description: &str| LedgerEntry {
+            kind: kind.to_string(),
+            id: Uuid::nil(),
+            occurred_at: None,
+            from_participant_id: from,
+            to_participant_id: if kind == "transaction" && from.is_none() { Some(alice) } else { None },
+            from_name: Some(if from.is_some() { "Alice Doe" } else { "Bank" }.to_string()),
+            to_name: None,
+            amount: amount.map(BigDecimal::from),
+            category: None,
+            description: Some(description.to_string()),
+            from_balance_after: None,
+            to_balance_after: None,
+        };
+        let rows = vec![
+            row("transaction", None, Some(1500), "Initial Funding"),
+            row("dice_roll", Some(alice), None, "2d6 [3, 4] = 7"),
+            row("transaction", Some(alice), Some(200), "Rent, Av. Libertador"),
+        ];
+
+        let mut mock_tx_repo = MockTransactionRepository::new();
+        mock_tx_repo.expect_stream_ledger()
+            .returning(move |_| futures::stream::iter(rows.clone().into_iter().map(Ok)).boxed());
+        let mut mock_game_repo = MockGameRepository::new();
+        mock_game_repo.expect_find_by_id().returning(|id| Ok(Some(GameSession {
+            id,
+            code: "ABCD".to_string(),
+            host_user_id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            status: "FINISHED".to_string(),
+            jackpot_balance: BigDecimal::zero(),
+            created_at: None,
+            ended_at: None,
+            current_turn_user_id: None,
+            turn_order: None,
+            winner_participant_id: None,
+            win_reason: None,
+            house_rules: Default::default(),
+            deadline_at: None,
+            rematch_game_id: None,
+            banker_user_id: None,
+            bank_balance: None,
+            version: 0,
+        })));
+
+        let mut mock_participant_repo = MockParticipantRepository::new();
+        mock_participant_repo.expect_find_by_game_id().returning(move |game_id| Ok(vec![GameParticipant {
+            id: alice,
+            game_id,
+            user_id: alice_user,
+            balance: BigDecimal::from(1300),
+            position: 0,
+            joined_at: None,
+            bankrupt_at: None,
+            bills: None,
+        }]));
+
+        let service = LedgerService::new(Arc::new(mock_game_repo), Arc::new(mock_participant_repo), Arc::new(mock_tx_repo));
+
+        // Someone outside the game gets nothing
+        assert!(service.export(Uuid::new_v4(), Uuid::new_v4(), ExportFormat::Csv).await.is_err());
+
+        let lines: Vec<String> = service.export(Uuid::new_v4(), alice_user, ExportFormat::Csv).await.unwrap()
+            .map(|l| l.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("kind,id,occurred_at"));
+        assert!(lines[1].ends_with(",1500\n"));
+        // Dice rolls don't move money
+        assert!(lines[2].ends_with(",,\n"));
+        assert!(lines[3].contains("\"Rent, Av. Libertador\""));
+        assert!(lines[3].ends_with(",1300,\n"));
+    }
+}
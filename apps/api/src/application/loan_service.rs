@@ -0,0 +1,403 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use crate::domain::{
+    entities::{InterestPeriod, Loan, NewLoan, OwnershipChange, TransactionCategory, TransferDetails, TransferLeg},
+    repositories::{GameRepository, LoanRepository, ParticipantRepository},
+    events::GameEvent,
+};
+use crate::application::{bankruptcy_service::BankruptcyService, transaction_service::TransactionService};
+
+pub struct LoanService {
+    loan_repo: Arc<dyn LoanRepository + Send + Sync>,
+    game_repo: Arc<dyn GameRepository + Send + Sync>,
+    participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+    transaction_service: Arc<TransactionService>,
+    bankruptcy_service: Arc<BankruptcyService>,
+    tx: tokio::sync::broadcast::Sender<GameEvent>,
+}
+
+impl LoanService {
+    pub fn new(
+        loan_repo: Arc<dyn LoanRepository + Send + Sync>,
+        game_repo: Arc<dyn GameRepository + Send + Sync>,
+        participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+        transaction_service: Arc<TransactionService>,
+        bankruptcy_service: Arc<BankruptcyService>,
+        tx: tokio::sync::broadcast::Sender<GameEvent>,
+    ) -> Self {
+        Self { loan_repo, game_repo, participant_repo, transaction_service, bankruptcy_service, tx }
+    }
+
+    pub async fn get_loans(&self, game_id: Uuid) -> Result<Vec<Loan>, anyhow::Error> {
+        self.loan_repo.find_by_game(game_id).await
+    }
+
+    // The caller asks to borrow; nothing moves until the lender (or the banker, for the Bank) approves
+    pub async fn request_loan(&self, game_id: Uuid, user_id: Uuid, request: NewLoan) -> Result<Loan, anyhow::Error> {
+        if request.principal <= BigDecimal::zero() {
+            return Err(anyhow::anyhow!("Principal must be positive"));
+        }
+        if request.interest_percent < BigDecimal::zero() {
+            return Err(anyhow::anyhow!("Interest cannot be negative"));
+        }
+        if request.due_after_periods.is_some_and(|d| d <= 0) {
+            return Err(anyhow::anyhow!("Due period must be positive"));
+        }
+
+        let participants = self.participant_repo.find_by_game_id(game_id).await?;
+        let borrower = participants.iter().find(|p| p.user_id == user_id)
+            .ok_or_else(|| anyhow::anyhow!("Participant not found"))?;
+
+        if let Some(lender_id) = request.lender_participant_id {
+            if lender_id == borrower.id {
+                return Err(anyhow::anyhow!("Cannot borrow from yourself"));
+            }
+            if !participants.iter().any(|p| p.id == lender_id) {
+                return Err(anyhow::anyhow!("Lender is not in this game"));
+            }
+        }
+
+        let loan = Loan {
+            id: Uuid::new_v4(),
+            game_id,
+            lender_participant_id: request.lender_participant_id,
+            borrower_participant_id: borrower.id,
+            outstanding: request.principal.clone(),
+            principal: request.principal,
+            interest_percent: request.interest_percent,
+            interest_period: request.interest_period,
+            due_after_periods: request.due_after_periods,
+            periods_elapsed: 0,
+            status: "PENDING".to_string(),
+            version: 0,
+            created_at: Some(time::OffsetDateTime::now_utc()),
+            settled_at: None,
+        };
+
+        let created = self.loan_repo.create(loan).await?;
+        let _ = self.tx.send(GameEvent::LoanUpdated(created.clone()));
+        Ok(created)
+    }
+
+    pub async fn approve_loan(&self, game_id: Uuid, loan_id: Uuid, user_id: Uuid) -> Result<Loan, anyhow::Error> {
+        let loan = self.find_pending(game_id, loan_id).await?;
+        self.ensure_lender(&loan, user_id).await?;
+
+        // Disburse and activate together: a double click finds the loan no longer pending and pays nothing
+        let mut approved = loan.clone();
+        approved.status = "ACTIVE".to_string();
+        let leg = TransferLeg {
+            from_participant_id: loan.lender_participant_id,
+            to_participant_id: Some(loan.borrower_participant_id),
+            amount: loan.principal.clone(),
+            details: TransferDetails::new(TransactionCategory::Loan, "Loan disbursement").loan(loan.id),
+        };
+        let change = OwnershipChange::UpdateLoan { loan: approved, expected_status: "PENDING".to_string() };
+        let result = match self.transaction_service.execute_batch(game_id, vec![leg], vec![change]).await {
+            Ok(result) => result,
+            Err(e) => {
+                let decided = self.loan_repo.find_by_id(loan_id).await?.is_some_and(|l| l.status != "PENDING");
+                return Err(if decided { anyhow::anyhow!("Loan was already decided") } else { e });
+            }
+        };
+        let approved = result.loans.into_iter().next()
+            .ok_or_else(|| anyhow::anyhow!("Loan approval produced no loan"))?;
+
+        let _ = self.tx.send(GameEvent::LoanUpdated(approved.clone()));
+        Ok(approved)
+    }
+
+    // The lender declines, or the borrower withdraws the request
+    pub async fn reject_loan(&self, game_id: Uuid, loan_id: Uuid, user_id: Uuid) -> Result<Loan, anyhow::Error> {
+        let mut loan = self.find_pending(game_id, loan_id).await?;
+
+        let is_borrower = self.participant_repo.find_by_game_id(game_id).await?
+            .iter().any(|p| p.id == loan.borrower_participant_id && p.user_id == user_id);
+        if !is_borrower {
+            self.ensure_lender(&loan, user_id).await?;
+        }
+
+        loan.status = "REJECTED".to_string();
+        loan.settled_at = Some(time::OffsetDateTime::now_utc());
+        let updated = self.loan_repo.update(loan, "PENDING").await?
+            .ok_or_else(|| anyhow::anyhow!("Loan was already decided"))?;
+        let _ = self.tx.send(GameEvent::LoanUpdated(updated.clone()));
+        Ok(updated)
+    }
+
+    /// Borrower pays back part or (by default) all of what is outstanding
+    pub async fn repay(&self, game_id: Uuid, loan_id: Uuid, user_id: Uuid, amount: Option<BigDecimal>) -> Result<Loan, anyhow::Error> {
+        let loan = self.loan_repo.find_by_id(loan_id).await?
+            .filter(|l| l.game_id == game_id)
+            .ok_or_else(|| anyhow::anyhow!("Loan not found"))?;
+
+        if loan.status != "ACTIVE" {
+            return Err(anyhow::anyhow!("Loan is not active"));
+        }
+
+        let is_borrower = self.participant_repo.find_by_game_id(game_id).await?
+            .iter().any(|p| p.id == loan.borrower_participant_id && p.user_id == user_id);
+        if !is_borrower {
+            return Err(anyhow::anyhow!("Only the borrower can repay"));
+        }
+
+        let amount = amount.unwrap_or_else(|| loan.outstanding.clone());
+        if amount <= BigDecimal::zero() {
+            return Err(anyhow::anyhow!("Amount must be positive"));
+        }
+        let amount = amount.min(loan.outstanding.clone());
+
+        self.pay_down(loan, amount).await
+    }
+
+    /// Called when a borrower completes a lap or a turn: accrue interest on matching loans
+    /// and settle the ones that fall due. A loan that fails is logged and skipped, so it cannot
+    /// hold up the others or the turn.
+    pub async fn on_period_end(&self, game_id: Uuid, participant_id: Uuid, period: InterestPeriod) -> Result<(), anyhow::Error> {
+        let loans = self.loan_repo.find_active_by_borrower(game_id, participant_id).await?;
+
+        for loan in loans.into_iter().filter(|l| l.interest_period == period) {
+            let loan_id = loan.id;
+            if let Err(e) = self.end_period(loan).await {
+                tracing::error!("Loan {} failed to close its period: {}", loan_id, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn end_period(&self, loan: Loan) -> Result<(), anyhow::Error> {
+        let loan_id = loan.id;
+        let mut current = Some(loan);
+        // A repayment racing with us bumps the version: re-read and accrue on the fresh row
+        while let Some(mut loan) = current.filter(|l| l.status == "ACTIVE") {
+            let due = accrue_interest(&mut loan);
+            match self.loan_repo.update(loan, "ACTIVE").await? {
+                Some(loan) => {
+                    let _ = self.tx.send(GameEvent::LoanUpdated(loan.clone()));
+                    if due {
+                        self.settle_due(loan).await?;
+                    }
+                    break;
+                }
+                None => current = self.loan_repo.find_by_id(loan_id).await?,
+            }
+        }
+        Ok(())
+    }
+
+    // Collect in full if the borrower can afford it, otherwise the loan defaults into bankruptcy
+    async fn settle_due(&self, loan: Loan) -> Result<(), anyhow::Error> {
+        let balance = self.participant_repo.find_by_game_id(loan.game_id).await?
+            .into_iter()
+            .find(|p| p.id == loan.borrower_participant_id)
+            .map(|p| p.balance)
+            .unwrap_or_default();
+
+        if balance >= loan.outstanding {
+            let amount = loan.outstanding.clone();
+            self.pay_down(loan, amount).await?;
+            return Ok(());
+        }
+
+        tracing::info!("Loan {} defaulted: owes {}, has {}", loan.id, loan.outstanding, balance);
+        let (game_id, borrower, lender) = (loan.game_id, loan.borrower_participant_id, loan.lender_participant_id);
+        self.bankruptcy_service.declare_bankruptcy(game_id, borrower, lender).await
+    }
+
+    // Books the repayment on the loan in the same batch as the money, so two concurrent repayments
+    // (or a repayment and an accrual) cannot both start from the same `outstanding`
+    async fn pay_down(&self, loan: Loan, amount: BigDecimal) -> Result<Loan, anyhow::Error> {
+        let mut paid = loan;
+        paid.outstanding -= &amount;
+        if paid.outstanding <= BigDecimal::zero() {
+            paid.outstanding = BigDecimal::zero();
+            paid.status = "REPAID".to_string();
+            paid.settled_at = Some(time::OffsetDateTime::now_utc());
+        }
+        let leg = TransferLeg {
+            from_participant_id: Some(paid.borrower_participant_id),
+            to_participant_id: paid.lender_participant_id,
+            amount,
+            details: TransferDetails::new(TransactionCategory::Loan, "Loan repayment").loan(paid.id),
+        };
+        let game_id = paid.game_id;
+        let change = OwnershipChange::UpdateLoan { loan: paid, expected_status: "ACTIVE".to_string() };
+        let result = self.transaction_service.execute_batch(game_id, vec![leg], vec![change]).await?;
+        let paid = result.loans.into_iter().next()
+            .ok_or_else(|| anyhow::anyhow!("Loan repayment produced no loan"))?;
+
+        let _ = self.tx.send(GameEvent::LoanUpdated(paid.clone()));
+        Ok(paid)
+    }
+
+    async fn find_pending(&self, game_id: Uuid, loan_id: Uuid) -> Result<Loan, anyhow::Error> {
+        let loan = self.loan_repo.find_by_id(loan_id).await?
+            .filter(|l| l.game_id == game_id)
+            .ok_or_else(|| anyhow::anyhow!("Loan not found"))?;
+
+        if loan.status != "PENDING" {
+            return Err(anyhow::anyhow!("Loan is not pending"));
+        }
+        Ok(loan)
+    }
+
+    // Player loans are decided by the lender, Bank loans by the banker
+    async fn ensure_lender(&self, loan: &Loan, user_id: Uuid) -> Result<(), anyhow::Error> {
+        match loan.lender_participant_id {
+            Some(lender_id) => {
+                let is_lender = self.participant_repo.find_by_game_id(loan.game_id).await?
+                    .iter().any(|p| p.id == lender_id && p.user_id == user_id);
+                if !is_lender {
+                    return Err(anyhow::anyhow!("Only the lender can decide on this loan"));
+                }
+            }
+            None => {
+                let game = self.game_repo.find_by_id(loan.game_id).await?
+                    .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+                if game.banker_id() != user_id {
+                    return Err(anyhow::anyhow!("Only the banker can decide on Bank loans"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Simple interest on the principal for one period; returns whether the loan is now due
+fn accrue_interest(loan: &mut Loan) -> bool {
+    let interest = (&loan.principal * &loan.interest_percent / BigDecimal::from(100))
+        .with_scale_round(2, RoundingMode::HalfUp);
+    loan.outstanding += interest;
+    loan.periods_elapsed += 1;
+    loan.due_after_periods.is_some_and(|d| loan.periods_elapsed >= d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::application::standings_service::StandingsService;
+    use crate::domain::entities::GameParticipant;
+    use crate::domain::errors::ConcurrencyConflict;
+    use crate::domain::repositories::{MockBankIouRepository, MockCardRepository, MockGameRepository, MockLoanRepository, MockParticipantRepository, MockPropertyRepository, MockStandingsRepository, MockTransactionRepository};
+
+    fn player_loan(game_id: Uuid, lender: Uuid, borrower: Uuid) -> Loan {
+        Loan {
+            id: Uuid::new_v4(),
+            game_id,
+            lender_participant_id: Some(lender),
+            borrower_participant_id: borrower,
+            principal: BigDecimal::from(300),
+            interest_percent: BigDecimal::from(5),
+            interest_period: InterestPeriod::Turn,
+            due_after_periods: None,
+            periods_elapsed: 0,
+            outstanding: BigDecimal::from(300),
+            status: "ACTIVE".to_string(),
+            version: 3,
+            created_at: None,
+            settled_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repayment_books_loan_in_the_batch() {
+        let (game_id, user_id, lender, borrower) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let loan = player_loan(game_id, lender, borrower);
+
+        let mut loan_repo = MockLoanRepository::new();
+        loan_repo.expect_find_by_id().returning(move |_| Ok(Some(loan.clone())));
+        // Nothing writes the loan outside the batch, so a failed batch leaves nothing to restore
+        loan_repo.expect_update().never();
+
+        let mut participant_repo = MockParticipantRepository::new();
+        participant_repo.expect_find_by_game_id().returning(move |_| Ok(vec![GameParticipant {
+            id: borrower,
+            game_id,
+            user_id,
+            balance: BigDecimal::from(1500),
+            position: 0,
+            joined_at: None,
+            bankrupt_at: None,
+            bills: None,
+        }]));
+
+        // A concurrent accrual got there first: the payment and the loan roll back together
+        let mut transaction_repo = MockTransactionRepository::new();
+        transaction_repo.expect_execute_batch()
+            .withf(|batch| batch.legs.len() == 1 && matches!(
+                batch.ownership.as_slice(),
+                [OwnershipChange::UpdateLoan { loan, expected_status }] if loan.outstanding == 200 && loan.version == 3 && expected_status == "ACTIVE"
+            ))
+            .times(1)
+            .returning(|_| Err(ConcurrencyConflict { entity: "loan" }.into()));
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(10);
+        let standings_service = Arc::new(StandingsService::new(
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockParticipantRepository::new()),
+            Arc::new(MockPropertyRepository::new()),
+            Arc::new(MockStandingsRepository::new()),
+            Arc::new(MockLoanRepository::new()),
+            tx.clone(),
+        ));
+        let transaction_service = Arc::new(TransactionService::new(
+            Arc::new(transaction_repo),
+            Arc::new(MockParticipantRepository::new()),
+            Arc::new(MockCardRepository::new()),
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockBankIouRepository::new()),
+            standings_service.clone(),
+            tx.clone(),
+        ));
+        let bankruptcy_service = Arc::new(BankruptcyService::new(
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockParticipantRepository::new()),
+            Arc::new(MockPropertyRepository::new()),
+            transaction_service.clone(),
+            standings_service,
+            tx.clone(),
+        ));
+        let service = LoanService::new(
+            Arc::new(loan_repo),
+            Arc::new(MockGameRepository::new()),
+            Arc::new(participant_repo),
+            transaction_service,
+            bankruptcy_service,
+            tx,
+        );
+
+        let err = service.repay(game_id, Uuid::new_v4(), user_id, Some(BigDecimal::from(100))).await.unwrap_err();
+
+        assert!(err.downcast_ref::<ConcurrencyConflict>().is_some());
+    }
+
+    #[test]
+    fn test_accrue_interest_until_due() {
+        let mut loan = Loan {
+            id: Uuid::new_v4(),
+            game_id: Uuid::new_v4(),
+            lender_participant_id: None,
+            borrower_participant_id: Uuid::new_v4(),
+            principal: BigDecimal::from(300),
+            interest_percent: BigDecimal::from(5),
+            interest_period: InterestPeriod::Lap,
+            due_after_periods: Some(2),
+            periods_elapsed: 0,
+            outstanding: BigDecimal::from(250), // Partly repaid already
+            status: "ACTIVE".to_string(),
+            version: 0,
+            created_at: None,
+            settled_at: None,
+        };
+
+        assert!(!accrue_interest(&mut loan));
+        assert_eq!(loan.outstanding, BigDecimal::from(265));
+
+        assert!(accrue_interest(&mut loan));
+        assert_eq!(loan.outstanding, BigDecimal::from(280));
+        assert_eq!(loan.periods_elapsed, 2);
+    }
+}
@@ -8,3 +8,14 @@ pub mod card_service;
 pub mod property_service;
 pub mod auction_service;
 pub mod trade_service;
+pub mod standings_service;
+pub mod ledger_service;
+pub mod loan_service;
+pub mod bankruptcy_service;
+pub mod payment_request_service;
+pub mod banker_service;
+pub mod scheduler;
+pub mod timed_game_scheduler;
+pub mod cash_service;
+pub mod idempotency_service;
+pub mod auction_scheduler;
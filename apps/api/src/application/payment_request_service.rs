@@ -0,0 +1,329 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use bigdecimal::{BigDecimal, Zero};
+use crate::domain::{
+    entities::{NewPaymentRequest, OwnershipChange, PaymentRequest, TransferDetails, TransferLeg},
+    repositories::{GameRepository, ParticipantRepository, PaymentRequestRepository},
+    events::GameEvent,
+};
+use crate::application::transaction_service::TransactionService;
+
+// Payee asks, payer agrees: the transfer only runs once the request is approved
+pub struct PaymentRequestService {
+    request_repo: Arc<dyn PaymentRequestRepository + Send + Sync>,
+    game_repo: Arc<dyn GameRepository + Send + Sync>,
+    participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+    transaction_service: Arc<TransactionService>,
+    tx: tokio::sync::broadcast::Sender<GameEvent>,
+}
+
+impl PaymentRequestService {
+    pub fn new(
+        request_repo: Arc<dyn PaymentRequestRepository + Send + Sync>,
+        game_repo: Arc<dyn GameRepository + Send + Sync>,
+        participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+        transaction_service: Arc<TransactionService>,
+        tx: tokio::sync::broadcast::Sender<GameEvent>,
+    ) -> Self {
+        Self { request_repo, game_repo, participant_repo, transaction_service, tx }
+    }
+
+    pub async fn get_requests(&self, game_id: Uuid) -> Result<Vec<PaymentRequest>, anyhow::Error> {
+        self.request_repo.find_by_game(game_id).await
+    }
+
+    pub async fn create_request(&self, game_id: Uuid, user_id: Uuid, request: NewPaymentRequest) -> Result<PaymentRequest, anyhow::Error> {
+        if request.amount <= BigDecimal::zero() {
+            return Err(anyhow::anyhow!("Amount must be positive"));
+        }
+
+        let participants = self.participant_repo.find_by_game_id(game_id).await?;
+        if !participants.iter().any(|p| p.id == request.payer_participant_id) {
+            return Err(anyhow::anyhow!("Payer is not in this game"));
+        }
+
+        let payee_id = if request.to_bank {
+            let game = self.game_repo.find_by_id(game_id).await?
+                .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+            if game.banker_id() != user_id {
+                return Err(anyhow::anyhow!("Only the banker can request payments to the Bank"));
+            }
+            None
+        } else {
+            let payee = participants.iter().find(|p| p.user_id == user_id)
+                .ok_or_else(|| anyhow::anyhow!("Participant not found"))?;
+            if request.payer_participant_id == payee.id {
+                return Err(anyhow::anyhow!("Cannot request a payment from yourself"));
+            }
+            Some(payee.id)
+        };
+
+        let created = self.request_repo.create(PaymentRequest {
+            id: Uuid::new_v4(),
+            game_id,
+            payee_participant_id: payee_id,
+            payer_participant_id: request.payer_participant_id,
+            amount: request.amount,
+            category: request.category,
+            description: request.description,
+            property_id: request.property_id,
+            status: "PENDING".to_string(),
+            requested_by_user_id: user_id,
+            resolved_by_user_id: None,
+            transaction_id: None,
+            created_at: Some(time::OffsetDateTime::now_utc()),
+            resolved_at: None,
+        }).await?;
+
+        let _ = self.tx.send(GameEvent::PaymentRequestUpdated(created.clone()));
+        Ok(created)
+    }
+
+    /// Payer approves (or the host, or the banker for Bank-bound requests, forces it through)
+    /// and the payment is executed
+    pub async fn approve_request(&self, game_id: Uuid, request_id: Uuid, user_id: Uuid) -> Result<PaymentRequest, anyhow::Error> {
+        let request = self.find_pending(game_id, request_id).await?;
+        self.ensure_can_decide(&request, user_id).await?;
+
+        let details = TransferDetails {
+            category: request.category,
+            description: request.description.clone(),
+            property_id: request.property_id,
+            ..Default::default()
+        };
+        let leg = TransferLeg {
+            from_participant_id: Some(request.payer_participant_id),
+            to_participant_id: request.payee_participant_id,
+            amount: request.amount.clone(),
+            details,
+        };
+
+        // Approval and the link to the payment commit with the payment itself: a double click
+        // loses the status check instead of paying twice, and a failed payment leaves it pending
+        let approve = OwnershipChange::ApprovePaymentRequest { request_id: request.id, resolved_by_user_id: user_id };
+        self.transaction_service.execute_batch(game_id, vec![leg], vec![approve]).await?;
+
+        let paid = self.request_repo.find_by_id(request.id).await?
+            .ok_or_else(|| anyhow::anyhow!("Payment request not found"))?;
+
+        let _ = self.tx.send(GameEvent::PaymentRequestUpdated(paid.clone()));
+        Ok(paid)
+    }
+
+    // The payer (or host/banker) declines; the requester withdrawing it counts as cancelled
+    pub async fn reject_request(&self, game_id: Uuid, request_id: Uuid, user_id: Uuid) -> Result<PaymentRequest, anyhow::Error> {
+        let mut request = self.find_pending(game_id, request_id).await?;
+
+        request.status = if request.requested_by_user_id == user_id {
+            "CANCELLED".to_string()
+        } else {
+            self.ensure_can_decide(&request, user_id).await?;
+            "REJECTED".to_string()
+        };
+        request.resolved_by_user_id = Some(user_id);
+        request.resolved_at = Some(time::OffsetDateTime::now_utc());
+
+        let updated = self.request_repo.update(request, "PENDING").await?
+            .ok_or_else(|| anyhow::anyhow!("Payment request was already resolved"))?;
+
+        let _ = self.tx.send(GameEvent::PaymentRequestUpdated(updated.clone()));
+        Ok(updated)
+    }
+
+    async fn find_pending(&self, game_id: Uuid, request_id: Uuid) -> Result<PaymentRequest, anyhow::Error> {
+        let request = self.request_repo.find_by_id(request_id).await?
+            .filter(|r| r.game_id == game_id)
+            .ok_or_else(|| anyhow::anyhow!("Payment request not found"))?;
+
+        if request.status != "PENDING" {
+            return Err(anyhow::anyhow!("Payment request is not pending"));
+        }
+        Ok(request)
+    }
+
+    // Payer always; otherwise the banker for Bank-bound requests and the host for the rest
+    async fn ensure_can_decide(&self, request: &PaymentRequest, user_id: Uuid) -> Result<(), anyhow::Error> {
+        let is_payer = self.participant_repo.find_by_game_id(request.game_id).await?
+            .iter().any(|p| p.id == request.payer_participant_id && p.user_id == user_id);
+        if is_payer {
+            return Ok(());
+        }
+
+        let game = self.game_repo.find_by_id(request.game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        let referee = match request.payee_participant_id {
+            None => game.banker_id(),
+            Some(_) => game.host_user_id,
+        };
+        if referee != user_id {
+            return Err(anyhow::anyhow!("Only the payer, or the host/banker, can decide on this request"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{GameParticipant, GameSession, TransactionCategory};
+    use crate::domain::repositories::{MockBankIouRepository, MockCardRepository, MockGameRepository, MockLoanRepository, MockParticipantRepository, MockPaymentRequestRepository, MockPropertyRepository, MockStandingsRepository, MockTransactionRepository};
+    use crate::application::standings_service::StandingsService;
+
+    #[tokio::test]
+    async fn test_approve_requires_payer_or_host() {
+        let game_id = Uuid::new_v4();
+        let payer = GameParticipant {
+            id: Uuid::new_v4(),
+            game_id,
+            user_id: Uuid::new_v4(),
+            balance: BigDecimal::from(1500),
+            position: 0,
+            joined_at: None,
+            bankrupt_at: None,
+            bills: None,
+        };
+        let request = PaymentRequest {
+            id: Uuid::new_v4(),
+            game_id,
+            payee_participant_id: Some(Uuid::new_v4()),
+            payer_participant_id: payer.id,
+            amount: BigDecimal::from(50),
+            category: TransactionCategory::Rent,
+            description: None,
+            property_id: None,
+            status: "PENDING".to_string(),
+            requested_by_user_id: Uuid::new_v4(),
+            resolved_by_user_id: None,
+            transaction_id: None,
+            created_at: None,
+            resolved_at: None,
+        };
+
+        let mut mock_request_repo = MockPaymentRequestRepository::new();
+        let found = request.clone();
+        mock_request_repo.expect_find_by_id().returning(move |_| Ok(Some(found.clone())));
+        mock_request_repo.expect_update().times(0);
+
+        let mut mock_part_repo = MockParticipantRepository::new();
+        mock_part_repo.expect_find_by_game_id().returning(move |_| Ok(vec![payer.clone()]));
+
+        let mut mock_game_repo = MockGameRepository::new();
+        mock_game_repo.expect_find_by_id().returning(move |id| Ok(Some(GameSession {
+            id,
+            code: "ABCD".to_string(),
+            host_user_id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            status: "ACTIVE".to_string(),
+            jackpot_balance: BigDecimal::zero(),
+            created_at: None,
+            ended_at: None,
+            current_turn_user_id: None,
+            turn_order: None,
+            winner_participant_id: None,
+            win_reason: None,
+            house_rules: Default::default(),
+            deadline_at: None,
+            rematch_game_id: None,
+            banker_user_id: None,
+            bank_balance: None,
+            version: 0,
+        })));
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(10);
+        let tx_service = Arc::new(TransactionService::new(
+            Arc::new(MockTransactionRepository::new()),
+            Arc::new(MockParticipantRepository::new()),
+            Arc::new(MockCardRepository::new()),
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockBankIouRepository::new()),
+            Arc::new(StandingsService::new(
+                Arc::new(MockGameRepository::new()),
+                Arc::new(MockParticipantRepository::new()),
+                Arc::new(MockPropertyRepository::new()),
+                Arc::new(MockStandingsRepository::new()),
+                Arc::new(MockLoanRepository::new()),
+                tx.clone(),
+            )),
+            tx.clone(),
+        ));
+        let service = PaymentRequestService::new(Arc::new(mock_request_repo), Arc::new(mock_game_repo), Arc::new(mock_part_repo), tx_service, tx);
+
+        // Neither the payer nor the host
+        let result = service.approve_request(game_id, request.id, Uuid::new_v4()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_approval_commits_with_the_payment() {
+        let game_id = Uuid::new_v4();
+        let payer = GameParticipant {
+            id: Uuid::new_v4(),
+            game_id,
+            user_id: Uuid::new_v4(),
+            balance: BigDecimal::from(1500),
+            position: 0,
+            joined_at: None,
+            bankrupt_at: None,
+            bills: None,
+        };
+        let request = PaymentRequest {
+            id: Uuid::new_v4(),
+            game_id,
+            payee_participant_id: Some(Uuid::new_v4()),
+            payer_participant_id: payer.id,
+            amount: BigDecimal::from(50),
+            category: TransactionCategory::Rent,
+            description: None,
+            property_id: None,
+            status: "PENDING".to_string(),
+            requested_by_user_id: Uuid::new_v4(),
+            resolved_by_user_id: None,
+            transaction_id: None,
+            created_at: None,
+            resolved_at: None,
+        };
+
+        // Both clicks read the request as pending; nothing but the batch may write it
+        let mut mock_request_repo = MockPaymentRequestRepository::new();
+        let found = request.clone();
+        mock_request_repo.expect_find_by_id().returning(move |_| Ok(Some(found.clone())));
+        mock_request_repo.expect_update().never();
+
+        let mut mock_part_repo = MockParticipantRepository::new();
+        let p = payer.clone();
+        mock_part_repo.expect_find_by_game_id().returning(move |_| Ok(vec![p.clone()]));
+
+        // The other click already approved it, so the status check fails and the batch rolls back
+        let request_id = request.id;
+        let mut mock_tx_repo = MockTransactionRepository::new();
+        mock_tx_repo.expect_execute_batch()
+            .withf(move |batch| batch.legs.len() == 1 && matches!(
+                batch.ownership.as_slice(),
+                [OwnershipChange::ApprovePaymentRequest { request_id: id, .. }] if *id == request_id
+            ))
+            .times(1)
+            .returning(|_| Err(crate::domain::errors::ConcurrencyConflict { entity: "payment request" }.into()));
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(10);
+        let tx_service = Arc::new(TransactionService::new(
+            Arc::new(mock_tx_repo),
+            Arc::new(MockParticipantRepository::new()),
+            Arc::new(MockCardRepository::new()),
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockBankIouRepository::new()),
+            Arc::new(StandingsService::new(
+                Arc::new(MockGameRepository::new()),
+                Arc::new(MockParticipantRepository::new()),
+                Arc::new(MockPropertyRepository::new()),
+                Arc::new(MockStandingsRepository::new()),
+                Arc::new(MockLoanRepository::new()),
+                tx.clone(),
+            )),
+            tx.clone(),
+        ));
+        let service = PaymentRequestService::new(Arc::new(mock_request_repo), Arc::new(MockGameRepository::new()), Arc::new(mock_part_repo), tx_service, tx);
+
+        let err = service.approve_request(game_id, request.id, payer.user_id).await.unwrap_err();
+        assert!(err.downcast_ref::<crate::domain::errors::ConcurrencyConflict>().is_some());
+    }
+}
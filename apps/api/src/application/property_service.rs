@@ -1,7 +1,7 @@
 use std::sync::Arc;
 use uuid::Uuid;
 use crate::domain::{
-    entities::{Property, ParticipantProperty},
+    entities::{AuctionLotType, BatchResult, Property, ParticipantProperty, OwnershipChange, TransactionCategory, TransferDetails, TransferLeg},
     repositories::{PropertyRepository, ParticipantRepository},
     events::GameEvent,
 };
@@ -48,29 +48,22 @@ impl PropertyService {
              return Err(anyhow::anyhow!("Property is already owned"));
         }
 
-        // 4. Deduct Balance (Pay Bank)
-        self.transaction_service.transfer(
+        // 4. Pay the Bank and take the deed together
+        let result = self.transaction_service.execute_batch(
             game_id,
-            Some(participant.id),
-            None, // Bank
-            property.price.clone(),
-            Some(format!("Bought {}", property.name))
+            vec![TransferLeg {
+                from_participant_id: Some(participant.id),
+                to_participant_id: None, // Bank
+                amount: property.price.clone(),
+                details: TransferDetails::new(TransactionCategory::Purchase, format!("Bought {}", property.name)).property(property.id),
+            }],
+            vec![OwnershipChange::Assign { property_id, participant_id: participant.id }],
         ).await?;
 
-        // 5. Assign Property
-        let pp = ParticipantProperty {
-            id: Uuid::new_v4(),
-            game_id,
-            participant_id: participant.id,
-            property_id,
-            is_mortgaged: false,
-            house_count: 0,
-            hotel_count: 0,
-            property_name: Some(property.name.clone()),
-            group_color: Some(property.group_color.clone()),
-        };
-
-        let stored = self.property_repo.assign_property(pp).await?;
+        let mut stored = result.ownership.into_iter().next()
+            .ok_or_else(|| anyhow::anyhow!("Property was not assigned"))?;
+        stored.property_name = Some(property.name.clone());
+        stored.group_color = Some(property.group_color.clone());
 
         let _ = self.tx.send(GameEvent::PropertyUpdated(stored.clone()));
 
@@ -100,22 +93,20 @@ impl PropertyService {
              return Err(anyhow::anyhow!("Must sell buildings first"));
         }
 
-        // Mortgage it
+        // Mortgage it and give cash together
         owned.is_mortgaged = true;
-        let updated = self.property_repo.update_property_ownership(owned.clone()).await?;
-
-        let _ = self.tx.send(GameEvent::PropertyUpdated(updated.clone()));
-
-        // Give Cash
-        self.transaction_service.transfer(
+        let result = self.transaction_service.execute_batch(
             game_id,
-            None, // Bank
-            Some(participant.id),
-            property.mortgage_value.clone(),
-            Some(format!("Mortgaged {}", property.name))
+            vec![TransferLeg {
+                from_participant_id: None, // Bank
+                to_participant_id: Some(participant.id),
+                amount: property.mortgage_value.clone(),
+                details: TransferDetails::new(TransactionCategory::Mortgage, format!("Mortgaged {}", property.name)).property(property.id),
+            }],
+            vec![OwnershipChange::Update(owned)],
         ).await?;
 
-        Ok(owned)
+        self.announce_update(result)
     }
 
     pub async fn unmortgage_property(&self, game_id: Uuid, user_id: Uuid, property_id: Uuid) -> Result<ParticipantProperty, anyhow::Error> {
@@ -135,21 +126,20 @@ impl PropertyService {
             return Err(anyhow::anyhow!("Property is not mortgaged"));
         }
 
-        // Pay Bank
-        self.transaction_service.transfer(
+        // Pay Bank and lift the mortgage together
+        owned.is_mortgaged = false;
+        let result = self.transaction_service.execute_batch(
             game_id,
-            Some(participant.id),
-            None,
-            property.unmortgage_cost.clone(),
-            Some(format!("Unmortgaged {}", property.name))
+            vec![TransferLeg {
+                from_participant_id: Some(participant.id),
+                to_participant_id: None, // Bank
+                amount: property.unmortgage_cost.clone(),
+                details: TransferDetails::new(TransactionCategory::Mortgage, format!("Unmortgaged {}", property.name)).property(property.id),
+            }],
+            vec![OwnershipChange::Update(owned)],
         ).await?;
 
-        owned.is_mortgaged = false;
-        let updated = self.property_repo.update_property_ownership(owned.clone()).await?;
-
-        let _ = self.tx.send(GameEvent::PropertyUpdated(updated.clone()));
-
-        Ok(updated)
+        self.announce_update(result)
     }
 
     pub async fn buy_building(&self, game_id: Uuid, user_id: Uuid, property_id: Uuid) -> Result<ParticipantProperty, anyhow::Error> {
@@ -222,16 +212,7 @@ impl PropertyService {
             cost = hotel_cost; // Use hotel cost
         }
 
-        // 8. Pay
-        self.transaction_service.transfer(
-            game_id,
-            Some(participant.id),
-            None,
-            cost.clone(),
-            Some(format!("Bought Building for {}", property.name))
-        ).await?;
-
-        // 9. Update State
+        // 8. Update State
         if is_hotel_upgrade {
             target_own.house_count = 0;
             target_own.hotel_count = 1;
@@ -239,9 +220,28 @@ impl PropertyService {
             target_own.house_count += 1;
         }
 
-        let updated = self.property_repo.update_property_ownership(target_own).await?;
-        let _ = self.tx.send(GameEvent::PropertyUpdated(updated.clone()));
-        Ok(updated)
+        // 9. Pay, unless a house/hotel won at auction already covers it.
+        // Payment and building commit together, so a stale read fails before any money moves.
+        let kind = if is_hotel_upgrade { AuctionLotType::Hotel } else { AuctionLotType::House };
+        let right = self.property_repo.find_building_rights(game_id, participant.id).await?
+            .into_iter()
+            .find(|r| r.kind == kind);
+        let (legs, mut changes) = match right {
+            Some(right) => (Vec::new(), vec![OwnershipChange::UseBuildingRight { right_id: right.id }]),
+            None => (
+                vec![TransferLeg {
+                    from_participant_id: Some(participant.id),
+                    to_participant_id: None, // Bank
+                    amount: cost.clone(),
+                    details: TransferDetails::new(TransactionCategory::Building, format!("Bought Building for {}", property.name)).property(property.id),
+                }],
+                Vec::new(),
+            ),
+        };
+        changes.push(OwnershipChange::Update(target_own));
+
+        let result = self.transaction_service.execute_batch(game_id, legs, changes).await?;
+        self.announce_update(result)
     }
 
     pub async fn sell_building(&self, game_id: Uuid, user_id: Uuid, property_id: Uuid) -> Result<ParticipantProperty, anyhow::Error> {
@@ -280,40 +280,178 @@ impl PropertyService {
              return Err(anyhow::anyhow!("You must sell evenly! Sell buildings from more developed properties in this group first."));
         }
 
-        if target_own.hotel_count > 0 {
+        let (amount, description) = if target_own.hotel_count > 0 {
             // Sell Hotel -> 4 Houses
             // Actually, usually you sell 1 building at a time. Hotel -> 4 houses adds value?
             // "Selling a hotel gives you half price of hotel cost, and returns 4 houses."
             // Detailed rules: "Hotels can be sold back... for 4 houses."
             // Simplified V1: DOWNGRADE. Hotel -> 4 Houses. Refund (HotelCost / 2).
             let h_refund = property.hotel_cost.as_ref().unwrap().div(bigdecimal::BigDecimal::from(2));
-             
-            self.transaction_service.transfer(
-                game_id,
-                None,
-                Some(participant.id),
-                h_refund,
-                Some(format!("Sold Hotel on {}", property.name))
-            ).await?;
 
             target_own.hotel_count = 0;
             target_own.house_count = 4;
+            (h_refund, format!("Sold Hotel on {}", property.name))
         } else if target_own.house_count > 0 {
             // Sell House
-             self.transaction_service.transfer(
-                game_id,
-                None,
-                Some(participant.id),
-                refund,
-                Some(format!("Sold House on {}", property.name))
-            ).await?;
             target_own.house_count -= 1;
+            (refund, format!("Sold House on {}", property.name))
         } else {
              return Err(anyhow::anyhow!("No buildings to sell"));
-        }
+        };
 
-        let updated = self.property_repo.update_property_ownership(target_own).await?;
+        // Refund and removal commit together
+        let result = self.transaction_service.execute_batch(
+            game_id,
+            vec![TransferLeg {
+                from_participant_id: None, // Bank
+                to_participant_id: Some(participant.id),
+                amount,
+                details: TransferDetails::new(TransactionCategory::Building, description).property(property.id),
+            }],
+            vec![OwnershipChange::Update(target_own)],
+        ).await?;
+        self.announce_update(result)
+    }
+
+    // The property row a single-property batch changed
+    fn announce_update(&self, result: BatchResult) -> Result<ParticipantProperty, anyhow::Error> {
+        let updated = result.ownership.into_iter().next()
+            .ok_or_else(|| anyhow::anyhow!("Property was not updated"))?;
         let _ = self.tx.send(GameEvent::PropertyUpdated(updated.clone()));
         Ok(updated)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use bigdecimal::BigDecimal;
+    use crate::application::standings_service::StandingsService;
+    use crate::domain::entities::{GameParticipant, GameSession};
+    use crate::domain::errors::ConcurrencyConflict;
+    use crate::domain::repositories::{MockBankIouRepository, MockCardRepository, MockGameRepository, MockLoanRepository, MockParticipantRepository, MockPropertyRepository, MockStandingsRepository, MockTransactionRepository};
+
+    #[tokio::test]
+    async fn test_stale_building_purchase_leaves_balances_unchanged() {
+        let (game_id, user_id, participant_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let property = Property {
+            id: Uuid::new_v4(),
+            name: "Mediterranean Avenue".to_string(),
+            group_color: "brown".to_string(),
+            price: BigDecimal::from(60),
+            rent_base: BigDecimal::from(2),
+            rent_house_1: None,
+            rent_house_2: None,
+            rent_house_3: None,
+            rent_house_4: None,
+            rent_hotel: None,
+            mortgage_value: BigDecimal::from(30),
+            unmortgage_cost: BigDecimal::from(33),
+            house_cost: Some(BigDecimal::from(50)),
+            hotel_cost: Some(BigDecimal::from(50)),
+            board_position: Some(1),
+        };
+        // Read at version 1, while a concurrent build already moved the row to version 2
+        let owned = ParticipantProperty {
+            id: Uuid::new_v4(),
+            game_id,
+            participant_id,
+            property_id: property.id,
+            is_mortgaged: false,
+            house_count: 0,
+            hotel_count: 0,
+            version: 1,
+            property_name: None,
+            group_color: None,
+        };
+
+        let mut property_repo = MockPropertyRepository::new();
+        let p = property.clone();
+        property_repo.expect_find_property_by_id().returning(move |_| Ok(Some(p.clone())));
+        let p = property.clone();
+        property_repo.expect_find_all_properties().returning(move || Ok(vec![p.clone()]));
+        property_repo.expect_find_participant_properties().returning(move |_, _| Ok(vec![owned.clone()]));
+        property_repo.expect_find_building_rights().returning(|_, _| Ok(vec![]));
+
+        let mut participant_repo = MockParticipantRepository::new();
+        participant_repo.expect_find_by_game_id().returning(move |_| Ok(vec![GameParticipant {
+            id: participant_id,
+            game_id,
+            user_id,
+            balance: BigDecimal::from(1500),
+            position: 1,
+            joined_at: None,
+            bankrupt_at: None,
+            bills: None,
+        }]));
+
+        // Stands in for the database: the batch only applies its legs if every version check passes
+        let balances = Arc::new(Mutex::new(HashMap::from([(participant_id, BigDecimal::from(1500))])));
+        let ledger = balances.clone();
+        let mut transaction_repo = MockTransactionRepository::new();
+        transaction_repo.expect_execute_batch().returning(move |batch| {
+            let stale = batch.ownership.iter().any(|c| matches!(c, OwnershipChange::Update(pp) if pp.version != 2));
+            if stale {
+                return Err(ConcurrencyConflict { entity: "property" }.into());
+            }
+            let mut ledger = ledger.lock().unwrap();
+            for leg in &batch.legs {
+                if let Some(from) = leg.from_participant_id {
+                    *ledger.get_mut(&from).unwrap() -= &leg.amount;
+                }
+            }
+            Ok(BatchResult { transactions: batch.legs, ..Default::default() })
+        });
+
+        let mut card_repo = MockCardRepository::new();
+        card_repo.expect_find_owner_of_card_title().returning(|_, _| Ok(None));
+        let mut game_repo = MockGameRepository::new();
+        game_repo.expect_find_by_id().returning(move |id| Ok(Some(GameSession {
+            id,
+            code: "ABCD".to_string(),
+            host_user_id: user_id,
+            name: "Game".to_string(),
+            status: "ACTIVE".to_string(),
+            jackpot_balance: BigDecimal::from(0),
+            created_at: None,
+            ended_at: None,
+            current_turn_user_id: None,
+            turn_order: None,
+            winner_participant_id: None,
+            win_reason: None,
+            house_rules: Default::default(),
+            deadline_at: None,
+            rematch_game_id: None,
+            banker_user_id: None,
+            bank_balance: None,
+            version: 0,
+        })));
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(10);
+        let standings_service = Arc::new(StandingsService::new(
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockParticipantRepository::new()),
+            Arc::new(MockPropertyRepository::new()),
+            Arc::new(MockStandingsRepository::new()),
+            Arc::new(MockLoanRepository::new()),
+            tx.clone(),
+        ));
+        let transaction_service = Arc::new(TransactionService::new(
+            Arc::new(transaction_repo),
+            Arc::new(MockParticipantRepository::new()),
+            Arc::new(card_repo),
+            Arc::new(game_repo),
+            Arc::new(MockBankIouRepository::new()),
+            standings_service,
+            tx.clone(),
+        ));
+        let service = PropertyService::new(Arc::new(property_repo), Arc::new(participant_repo), transaction_service, tx);
+
+        let err = service.buy_building(game_id, user_id, property.id).await.unwrap_err();
+
+        assert!(err.downcast_ref::<ConcurrencyConflict>().is_some());
+        assert_eq!(balances.lock().unwrap()[&participant_id], BigDecimal::from(1500));
+    }
+}
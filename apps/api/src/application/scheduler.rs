@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+// A background job that runs on a fixed interval; a failed tick is logged and the next one runs as usual
+#[async_trait]
+pub trait Ticker: Send + Sync + 'static {
+    // For the logs
+    const NAME: &'static str;
+
+    async fn tick(&self) -> Result<(), anyhow::Error>;
+}
+
+pub fn spawn<T: Ticker>(ticker: Arc<T>, every: Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            if let Err(e) = ticker.tick().await {
+                tracing::error!("{} tick failed: {}", T::NAME, e);
+            }
+        }
+    });
+}
+
+/// Countdown announcements for a set of running clocks, one event per step rather than per tick.
+/// Steps are (above, step) pairs checked in order: while more than `above` seconds remain the clock
+/// is announced every `step` seconds, rounding up so "4:59" still reads as the 5 minute mark.
+pub struct Countdown {
+    steps: &'static [(i64, i64)],
+    // Last bucket announced per clock
+    announced: Mutex<HashMap<Uuid, i64>>,
+}
+
+impl Countdown {
+    pub fn new(steps: &'static [(i64, i64)]) -> Self {
+        Self { steps, announced: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn bucket(&self, remaining_seconds: i64) -> i64 {
+        if remaining_seconds <= 0 {
+            return 0;
+        }
+        let step = self.steps.iter()
+            .find(|(above, _)| remaining_seconds > *above)
+            .map_or(1, |(_, step)| *step);
+        (remaining_seconds + step - 1) / step * step
+    }
+
+    /// True when the clock entered a new bucket since it was last announced
+    pub fn should_announce(&self, id: Uuid, remaining_seconds: i64) -> bool {
+        let bucket = self.bucket(remaining_seconds);
+        self.announced.lock().unwrap().insert(id, bucket) != Some(bucket)
+    }
+
+    // Forget clocks that are no longer running
+    pub fn retain(&self, running: impl Fn(&Uuid) -> bool) {
+        self.announced.lock().unwrap().retain(|id, _| running(id));
+    }
+
+    pub fn forget(&self, id: Uuid) {
+        self.announced.lock().unwrap().remove(&id);
+    }
+}
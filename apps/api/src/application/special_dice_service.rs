@@ -15,6 +15,7 @@ impl SpecialDiceService {
         Self { repo, tx }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn record_roll(
         &self, 
         game_id: Uuid, 
@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+use bigdecimal::{BigDecimal, Zero};
+use crate::domain::{
+    entities::{GameStatus, GameResult, ParticipantProperty, Property, Standing, WinReason},
+    repositories::{GameRepository, LoanRepository, ParticipantRepository, PropertyRepository, StandingsRepository},
+    events::GameEvent,
+};
+
+pub struct StandingsService {
+    game_repo: Arc<dyn GameRepository + Send + Sync>,
+    participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+    property_repo: Arc<dyn PropertyRepository + Send + Sync>,
+    standings_repo: Arc<dyn StandingsRepository + Send + Sync>,
+    loan_repo: Arc<dyn LoanRepository + Send + Sync>,
+    tx: tokio::sync::broadcast::Sender<GameEvent>,
+}
+
+impl StandingsService {
+    pub fn new(
+        game_repo: Arc<dyn GameRepository + Send + Sync>,
+        participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+        property_repo: Arc<dyn PropertyRepository + Send + Sync>,
+        standings_repo: Arc<dyn StandingsRepository + Send + Sync>,
+        loan_repo: Arc<dyn LoanRepository + Send + Sync>,
+        tx: tokio::sync::broadcast::Sender<GameEvent>,
+    ) -> Self {
+        Self { game_repo, participant_repo, property_repo, standings_repo, loan_repo, tx }
+    }
+
+    // Live ranking by net worth (cash + property value + buildings at cost + loans owed to minus owed by)
+    pub async fn compute_standings(&self, game_id: Uuid) -> Result<Vec<Standing>, anyhow::Error> {
+        let participants = self.participant_repo.find_by_game_id(game_id).await?;
+        let ownership = self.property_repo.find_ownership_by_game(game_id).await?;
+        let properties: HashMap<Uuid, Property> = self.property_repo.find_all_properties().await?
+            .into_iter()
+            .map(|p| (p.id, p))
+            .collect();
+        let loans: Vec<_> = self.loan_repo.find_by_game(game_id).await?
+            .into_iter()
+            .filter(|l| l.status == "ACTIVE")
+            .collect();
+
+        let mut standings: Vec<Standing> = participants.into_iter().map(|p| {
+            let owned: Vec<&ParticipantProperty> = ownership.iter().filter(|o| o.participant_id == p.id).collect();
+            let (property_value, building_value) = asset_values(&owned, &properties);
+            let loans_receivable: BigDecimal = loans.iter()
+                .filter(|l| l.lender_participant_id == Some(p.id))
+                .map(|l| &l.outstanding)
+                .sum();
+            let loans_payable: BigDecimal = loans.iter()
+                .filter(|l| l.borrower_participant_id == p.id)
+                .map(|l| &l.outstanding)
+                .sum();
+            let net_worth = &p.balance + &property_value + &building_value + &loans_receivable - &loans_payable;
+            Standing {
+                game_id,
+                participant_id: p.id,
+                user_id: p.user_id,
+                rank: 0,
+                cash: p.balance,
+                property_value,
+                building_value,
+                loans_receivable,
+                loans_payable,
+                net_worth,
+            }
+        }).collect();
+
+        rank_standings(&mut standings);
+        Ok(standings)
+    }
+
+    // Stored result for a finished game, otherwise the live ranking
+    pub async fn get_standings(&self, game_id: Uuid) -> Result<Vec<Standing>, anyhow::Error> {
+        let stored = self.standings_repo.find_by_game(game_id).await?;
+        if !stored.is_empty() {
+            return Ok(stored);
+        }
+        self.compute_standings(game_id).await
+    }
+
+    /// Finish the game, freeze the final standings and announce the winner.
+    /// Without an explicit winner the participant with the highest net worth wins.
+    pub async fn end_game(&self, game_id: Uuid, reason: WinReason, winner_participant_id: Option<Uuid>) -> Result<GameResult, anyhow::Error> {
+        let mut game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        if game.status == GameStatus::FINISHED.to_string() {
+            return Err(anyhow::anyhow!("Game is already finished"));
+        }
+
+        let standings = self.compute_standings(game_id).await?;
+        let winner = winner_participant_id.or_else(|| standings.first().map(|s| s.participant_id));
+
+        game.status = GameStatus::FINISHED.to_string();
+        game.ended_at = Some(time::OffsetDateTime::now_utc());
+        game.winner_participant_id = winner;
+        game.win_reason = Some(reason.to_string());
+
+        let (game, standings) = self.standings_repo.finish_game(game, standings).await?;
+
+        tracing::info!("Game {} finished ({}), winner participant {:?}", game_id, reason, winner);
+
+        let _ = self.tx.send(GameEvent::GameUpdated { id: game_id, status: game.status.clone() });
+        let _ = self.tx.send(GameEvent::GameEnded {
+            game_id,
+            winner_participant_id: winner,
+            reason: reason.to_string(),
+            standings: standings.clone(),
+        });
+
+        Ok(GameResult { game, standings })
+    }
+}
+
+// Unmortgaged properties count at price, mortgaged ones at mortgage value.
+// A hotel is valued at its cost plus the four houses it replaced.
+fn asset_values(owned: &[&ParticipantProperty], properties: &HashMap<Uuid, Property>) -> (BigDecimal, BigDecimal) {
+    let mut property_value = BigDecimal::zero();
+    let mut building_value = BigDecimal::zero();
+
+    for o in owned {
+        let Some(prop) = properties.get(&o.property_id) else { continue };
+
+        property_value += if o.is_mortgaged { &prop.mortgage_value } else { &prop.price };
+
+        let house_cost = prop.house_cost.clone().unwrap_or_default();
+        let hotel_cost = prop.hotel_cost.clone().unwrap_or_default();
+        building_value += &house_cost * BigDecimal::from(o.house_count);
+        building_value += (hotel_cost + &house_cost * BigDecimal::from(4)) * BigDecimal::from(o.hotel_count);
+    }
+
+    (property_value, building_value)
+}
+
+// Highest net worth first, cash breaks ties
+fn rank_standings(standings: &mut [Standing]) {
+    standings.sort_by(|a, b| b.net_worth.cmp(&a.net_worth).then_with(|| b.cash.cmp(&a.cash)));
+    for (i, s) in standings.iter_mut().enumerate() {
+        s.rank = i as i32 + 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn property(price: i32, mortgage: i32, house_cost: i32, hotel_cost: i32) -> Property {
+        Property {
+            id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            group_color: "brown".to_string(),
+            price: BigDecimal::from(price),
+            rent_base: BigDecimal::from(2),
+            rent_house_1: None,
+            rent_house_2: None,
+            rent_house_3: None,
+            rent_house_4: None,
+            rent_hotel: None,
+            mortgage_value: BigDecimal::from(mortgage),
+            unmortgage_cost: BigDecimal::from(mortgage),
+            house_cost: Some(BigDecimal::from(house_cost)),
+            hotel_cost: Some(BigDecimal::from(hotel_cost)),
+            board_position: None,
+        }
+    }
+
+    fn owned(property_id: Uuid, is_mortgaged: bool, house_count: i32, hotel_count: i32) -> ParticipantProperty {
+        ParticipantProperty {
+            id: Uuid::new_v4(),
+            game_id: Uuid::new_v4(),
+            participant_id: Uuid::new_v4(),
+            property_id,
+            is_mortgaged,
+            house_count,
+            hotel_count,
+            version: 0,
+            property_name: None,
+            group_color: None,
+        }
+    }
+
+    #[test]
+    fn test_asset_values() {
+        let plain = property(60, 30, 50, 50);
+        let mortgaged = property(100, 50, 50, 50);
+        let hotel = property(200, 100, 100, 100);
+
+        let o1 = owned(plain.id, false, 2, 0);
+        let o2 = owned(mortgaged.id, true, 0, 0);
+        let o3 = owned(hotel.id, false, 0, 1);
+
+        let props: HashMap<Uuid, Property> = [plain, mortgaged, hotel].into_iter().map(|p| (p.id, p)).collect();
+        let (property_value, building_value) = asset_values(&[&o1, &o2, &o3], &props);
+
+        assert_eq!(property_value, BigDecimal::from(60 + 50 + 200));
+        // 2 houses at 50, hotel at 100 + 4 houses at 100
+        assert_eq!(building_value, BigDecimal::from(100 + 500));
+    }
+
+    #[test]
+    fn test_rank_standings_breaks_ties_on_cash() {
+        let standing = |cash: i32, net_worth: i32| Standing {
+            game_id: Uuid::nil(),
+            participant_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            rank: 0,
+            cash: BigDecimal::from(cash),
+            property_value: BigDecimal::zero(),
+            building_value: BigDecimal::zero(),
+            loans_receivable: BigDecimal::zero(),
+            loans_payable: BigDecimal::zero(),
+            net_worth: BigDecimal::from(net_worth),
+        };
+
+        let mut standings = vec![standing(100, 500), standing(300, 900), standing(400, 500)];
+        rank_standings(&mut standings);
+
+        let ranked: Vec<(i32, BigDecimal)> = standings.iter().map(|s| (s.rank, s.cash.clone())).collect();
+        assert_eq!(ranked, vec![
+            (1, BigDecimal::from(300)),
+            (2, BigDecimal::from(400)),
+            (3, BigDecimal::from(100)),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_end_game_announces_nothing_when_another_end_won() {
+        use crate::domain::errors::ConcurrencyConflict;
+        use crate::domain::repositories::{MockGameRepository, MockLoanRepository, MockParticipantRepository, MockPropertyRepository, MockStandingsRepository};
+
+        let game_id = Uuid::new_v4();
+        let mut game_repo = MockGameRepository::new();
+        game_repo.expect_find_by_id().returning(move |_| Ok(Some(crate::domain::entities::GameSession {
+            id: game_id,
+            code: "ABCD".to_string(),
+            host_user_id: Uuid::new_v4(),
+            name: "Game".to_string(),
+            status: "ACTIVE".to_string(),
+            jackpot_balance: BigDecimal::zero(),
+            created_at: None,
+            ended_at: None,
+            current_turn_user_id: None,
+            turn_order: None,
+            winner_participant_id: None,
+            win_reason: None,
+            house_rules: Default::default(),
+            deadline_at: None,
+            rematch_game_id: None,
+            banker_user_id: None,
+            bank_balance: None,
+            version: 4,
+        })));
+        // The status only changes together with the standings
+        game_repo.expect_update().never();
+
+        let mut participant_repo = MockParticipantRepository::new();
+        participant_repo.expect_find_by_game_id().returning(|_| Ok(vec![]));
+        let mut property_repo = MockPropertyRepository::new();
+        property_repo.expect_find_ownership_by_game().returning(|_| Ok(vec![]));
+        property_repo.expect_find_all_properties().returning(|| Ok(vec![]));
+        let mut loan_repo = MockLoanRepository::new();
+        loan_repo.expect_find_by_game().returning(|_| Ok(vec![]));
+
+        let mut standings_repo = MockStandingsRepository::new();
+        standings_repo.expect_finish_game()
+            .withf(|game, _| game.status == "FINISHED" && game.version == 4)
+            .times(1)
+            .returning(|_, _| Err(ConcurrencyConflict { entity: "game" }.into()));
+
+        let (tx, mut rx) = tokio::sync::broadcast::channel(10);
+        let service = StandingsService::new(
+            Arc::new(game_repo),
+            Arc::new(participant_repo),
+            Arc::new(property_repo),
+            Arc::new(standings_repo),
+            Arc::new(loan_repo),
+            tx,
+        );
+
+        assert!(service.end_game(game_id, WinReason::HostDecision, None).await.is_err());
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use crate::application::{scheduler::{self, Countdown, Ticker}, standings_service::StandingsService};
+use crate::domain::{
+    entities::{GameSession, WinReason},
+    repositories::GameRepository,
+    events::GameEvent,
+};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+
+// Countdown granularity: once per minute, then every 10 seconds during the last minute
+const COUNTDOWN_STEPS: &[(i64, i64)] = &[(60, 60), (0, 10)];
+
+// Watches active timed games, broadcasts the countdown and ends them at the deadline
+pub struct TimedGameScheduler {
+    game_repo: Arc<dyn GameRepository + Send + Sync>,
+    standings_service: Arc<StandingsService>,
+    tx: tokio::sync::broadcast::Sender<GameEvent>,
+    countdown: Countdown,
+}
+
+impl TimedGameScheduler {
+    pub fn new(
+        game_repo: Arc<dyn GameRepository + Send + Sync>,
+        standings_service: Arc<StandingsService>,
+        tx: tokio::sync::broadcast::Sender<GameEvent>,
+    ) -> Self {
+        Self { game_repo, standings_service, tx, countdown: Countdown::new(COUNTDOWN_STEPS) }
+    }
+
+    pub fn spawn(self: Arc<Self>) {
+        scheduler::spawn(self, TICK_INTERVAL);
+    }
+
+    fn announce(&self, game: &GameSession, remaining_seconds: i64, finishing_round: bool) {
+        if !self.countdown.should_announce(game.id, remaining_seconds) {
+            return;
+        }
+
+        let _ = self.tx.send(GameEvent::GameCountdown {
+            game_id: game.id,
+            remaining_seconds,
+            finishing_round,
+        });
+    }
+}
+
+#[async_trait]
+impl Ticker for TimedGameScheduler {
+    const NAME: &'static str = "Timed game scheduler";
+
+    async fn tick(&self) -> Result<(), anyhow::Error> {
+        let games = self.game_repo.find_active_with_deadline().await?;
+        let now = time::OffsetDateTime::now_utc();
+
+        // Forget games that are no longer running
+        self.countdown.retain(|id| games.iter().any(|g| g.id == *id));
+
+        for game in games {
+            let Some(deadline) = game.deadline_at else { continue };
+            let remaining = (deadline - now).whole_seconds();
+
+            if remaining > 0 {
+                self.announce(&game, remaining, false);
+                continue;
+            }
+
+            if game.house_rules.finish_round_at_deadline {
+                // The last turn of the round ends the game (see GameService::end_turn)
+                self.announce(&game, 0, true);
+                continue;
+            }
+
+            self.countdown.forget(game.id);
+            if let Err(e) = self.standings_service.end_game(game.id, WinReason::TimeLimit, None).await {
+                tracing::error!("Failed to end timed game {}: {}", game.id, e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_countdown_bucket() {
+        let countdown = Countdown::new(COUNTDOWN_STEPS);
+        assert_eq!(countdown.bucket(299), 300);
+        assert_eq!(countdown.bucket(241), 300);
+        assert_eq!(countdown.bucket(240), 240);
+        assert_eq!(countdown.bucket(61), 120);
+        assert_eq!(countdown.bucket(60), 60);
+        assert_eq!(countdown.bucket(55), 60);
+        assert_eq!(countdown.bucket(50), 50);
+        assert_eq!(countdown.bucket(1), 10);
+        assert_eq!(countdown.bucket(0), 0);
+        assert_eq!(countdown.bucket(-5), 0);
+    }
+}
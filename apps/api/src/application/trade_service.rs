@@ -1,7 +1,8 @@
 use std::sync::Arc;
+use bigdecimal::Zero;
 use uuid::Uuid;
 use crate::domain::{
-    entities::Trade,
+    entities::{ParticipantCard, ParticipantProperty, Trade, OwnershipChange, TransactionCategory, TransferDetails, TransferLeg},
     repositories::{TradeRepository, PropertyRepository, CardRepository, ParticipantRepository},
     events::GameEvent,
 };
@@ -10,7 +11,7 @@ use crate::application::transaction_service::TransactionService;
 pub struct TradeService {
     trade_repo: Arc<dyn TradeRepository + Send + Sync>,
     property_repo: Arc<dyn PropertyRepository + Send + Sync>,
-    _card_repo: Arc<dyn CardRepository + Send + Sync>, 
+    card_repo: Arc<dyn CardRepository + Send + Sync>,
     participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
     transaction_service: Arc<TransactionService>,
     tx: tokio::sync::broadcast::Sender<GameEvent>,
@@ -20,15 +21,16 @@ impl TradeService {
     pub fn new(
         trade_repo: Arc<dyn TradeRepository + Send + Sync>,
         property_repo: Arc<dyn PropertyRepository + Send + Sync>,
-        _card_repo: Arc<dyn CardRepository + Send + Sync>,
+        card_repo: Arc<dyn CardRepository + Send + Sync>,
         participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
         transaction_service: Arc<TransactionService>,
         tx: tokio::sync::broadcast::Sender<GameEvent>,
     ) -> Self {
-        Self { trade_repo, property_repo, _card_repo, participant_repo, transaction_service, tx }
+        Self { trade_repo, property_repo, card_repo, participant_repo, transaction_service, tx }
     }
 
     pub async fn create_trade(&self, trade: Trade) -> Result<Trade, anyhow::Error> {
+        self.check_trade(&trade).await?;
         let created = self.trade_repo.create(trade).await?;
         let _ = self.tx.send(GameEvent::TradeUpdated(created.clone()));
         Ok(created)
@@ -51,55 +53,338 @@ impl TradeService {
              return Err(anyhow::anyhow!("You are not the target of this trade"));
         }
 
-        // Execute Transfers
+        // Things may have changed hands since the offer was made: such a trade is off
+        if let Err(e) = self.check_trade(&trade).await {
+            return Err(self.call_off(trade, e).await);
+        }
+
+        // Execute Transfers, all in one batch
+        let mut legs = Vec::new();
         // 1. Cash (Initiator pays Offer Cash to Target)
-        if trade.offer_cash > bigdecimal::BigDecimal::from(0) {
-            self.transaction_service.transfer(
-                trade.game_id, Some(trade.initiator_id), Some(trade.target_id), trade.offer_cash.clone(), Some("Trade Cash".to_string())
-            ).await?;
+        if trade.offer_cash > bigdecimal::BigDecimal::zero() {
+            legs.push(TransferLeg {
+                from_participant_id: Some(trade.initiator_id),
+                to_participant_id: Some(trade.target_id),
+                amount: trade.offer_cash.clone(),
+                details: TransferDetails::new(TransactionCategory::Trade, "Trade Cash").trade(trade.id),
+            });
         }
         // 2. Request Cash (Target pays Request Cash to Initiator)
-         if trade.request_cash > bigdecimal::BigDecimal::from(0) {
-            self.transaction_service.transfer(
-                trade.game_id, Some(trade.target_id), Some(trade.initiator_id), trade.request_cash.clone(), Some("Trade Cash".to_string())
-            ).await?;
+        if trade.request_cash > bigdecimal::BigDecimal::zero() {
+            legs.push(TransferLeg {
+                from_participant_id: Some(trade.target_id),
+                to_participant_id: Some(trade.initiator_id),
+                amount: trade.request_cash.clone(),
+                details: TransferDetails::new(TransactionCategory::Trade, "Trade Cash").trade(trade.id),
+            });
         }
 
+        let mut ownership = Vec::new();
         // 3. Properties (Offer Properties -> Target)
         if let Some(props) = &trade.offer_properties {
              for prop_id in props.0.iter() {
-                 self.property_repo.transfer_property(trade.game_id, *prop_id, trade.target_id).await?;
+                 ownership.push(OwnershipChange::TradeProperty {
+                     property_id: *prop_id,
+                     from_participant_id: trade.initiator_id,
+                     to_participant_id: trade.target_id,
+                 });
              }
         }
         // 4. Request Properties (Target Properties -> Initiator)
         if let Some(props) = &trade.request_properties {
              for prop_id in props.0.iter() {
-                 self.property_repo.transfer_property(trade.game_id, *prop_id, trade.initiator_id).await?;
+                 ownership.push(OwnershipChange::TradeProperty {
+                     property_id: *prop_id,
+                     from_participant_id: trade.target_id,
+                     to_participant_id: trade.initiator_id,
+                 });
              }
         }
-        
+
+        // 5. Cards, both ways
+        for (cards, from, to) in [
+            (&trade.offer_cards, trade.initiator_id, trade.target_id),
+            (&trade.request_cards, trade.target_id, trade.initiator_id),
+        ] {
+            for inventory_id in cards.iter().flat_map(|c| c.0.iter()) {
+                ownership.push(OwnershipChange::TransferCard {
+                    inventory_id: *inventory_id,
+                    from_participant_id: from,
+                    to_participant_id: to,
+                });
+            }
+        }
+
+        // 6. The trade itself: the batch only commits while it is still pending, and it re-checks
+        // ownership, buildings and cards against the rows it locks
+        ownership.push(OwnershipChange::AcceptTrade { trade_id: trade.id });
+
+        let result = match self.transaction_service.execute_batch(trade.game_id, legs, ownership).await {
+            Ok(result) => result,
+            Err(e) => {
+                // Nothing moved. Lost to another accept or a reject: say so; assets changed hands: the trade is off
+                let current = self.trade_repo.find_by_id(trade_id).await?
+                    .ok_or_else(|| anyhow::anyhow!("Trade not found"))?;
+                if current.status != "PENDING" {
+                    return Err(anyhow::anyhow!("Trade was already resolved"));
+                }
+                if let Err(invalid) = self.check_trade(&current).await {
+                    return Err(self.call_off(current, invalid).await);
+                }
+                return Err(e);
+            }
+        };
+        for pp in result.ownership {
+            let _ = self.tx.send(GameEvent::PropertyUpdated(pp));
+        }
+
         trade.status = "ACCEPTED".to_string();
-        let updated = self.trade_repo.update(trade).await?;
-        let _ = self.tx.send(GameEvent::TradeUpdated(updated.clone()));
-        Ok(updated)
+        let _ = self.tx.send(GameEvent::TradeUpdated(trade.clone()));
+        Ok(trade)
+    }
+
+    // Reject a trade whose assets are no longer what it promises; the error to hand back
+    async fn call_off(&self, mut trade: Trade, reason: anyhow::Error) -> anyhow::Error {
+        trade.status = "REJECTED".to_string();
+        match self.trade_repo.update(trade, "PENDING").await {
+            Ok(Some(rejected)) => {
+                let _ = self.tx.send(GameEvent::TradeUpdated(rejected));
+                anyhow::anyhow!("Trade is no longer valid: {}", reason)
+            }
+            Ok(None) => anyhow::anyhow!("Trade was already resolved"),
+            Err(e) => e,
+        }
     }
 
-    pub async fn reject_trade(&self, trade_id: Uuid, _user_id: Uuid) -> Result<Trade, anyhow::Error> {
+    pub async fn reject_trade(&self, trade_id: Uuid, user_id: Uuid) -> Result<Trade, anyhow::Error> {
         let mut trade = self.trade_repo.find_by_id(trade_id).await?
             .ok_or_else(|| anyhow::anyhow!("Trade not found"))?;
 
+        if trade.status != "PENDING" {
+            return Err(anyhow::anyhow!("Trade is not pending"));
+        }
+
         // Verify user is target OR initiator (initiator can cancel)
-        // ... Logic checks ...
+        let participants = self.participant_repo.find_by_game_id(trade.game_id).await?;
+        let participant = participants.iter().find(|p| p.user_id == user_id)
+            .ok_or_else(|| anyhow::anyhow!("Participant not found"))?;
+
+        if participant.id != trade.target_id && participant.id != trade.initiator_id {
+            return Err(anyhow::anyhow!("You are not part of this trade"));
+        }
 
         trade.status = "REJECTED".to_string();
-        let updated = self.trade_repo.update(trade).await?;
+        let updated = self.trade_repo.update(trade, "PENDING").await?
+            .ok_or_else(|| anyhow::anyhow!("Trade was already resolved"))?;
         let _ = self.tx.send(GameEvent::TradeUpdated(updated.clone()));
         Ok(updated)
     }
 
+    // Current state of everything the trade moves, checked against what it promises
+    async fn check_trade(&self, trade: &Trade) -> Result<(), anyhow::Error> {
+        let ownership = self.property_repo.find_ownership_by_game(trade.game_id).await?;
+        let initiator_cards = self.card_repo.get_inventory(trade.initiator_id).await?;
+        let target_cards = self.card_repo.get_inventory(trade.target_id).await?;
+        validate_trade(trade, &ownership, &initiator_cards, &target_cards)
+    }
+
     pub async fn get_active_trades(&self, game_id: Uuid) -> Result<Vec<Trade>, anyhow::Error> {
         let trades = self.trade_repo.find_by_game(game_id).await?;
         let pending: Vec<Trade> = trades.into_iter().filter(|t| t.status == "PENDING").collect();
         Ok(pending)
     }
 }
+
+/// Each side still owns what it gives, no traded property sits in a color group with buildings,
+/// and every traded card is still in the giver's inventory
+fn validate_trade(trade: &Trade, ownership: &[ParticipantProperty], initiator_cards: &[ParticipantCard], target_cards: &[ParticipantCard]) -> Result<(), anyhow::Error> {
+    let sides = [
+        (trade.initiator_id, &trade.offer_properties, &trade.offer_cards, initiator_cards),
+        (trade.target_id, &trade.request_properties, &trade.request_cards, target_cards),
+    ];
+
+    for (giver, properties, cards, inventory) in sides {
+        for property_id in properties.iter().flat_map(|p| p.0.iter()) {
+            let owned = ownership.iter()
+                .find(|pp| pp.property_id == *property_id && pp.participant_id == giver)
+                .ok_or_else(|| anyhow::anyhow!("Property {} is not owned by the participant giving it", property_id))?;
+
+            // Buildings have to be sold off the whole color group before any of it can be traded
+            let built = ownership.iter().any(|pp| {
+                pp.participant_id == giver
+                    && pp.group_color.is_some()
+                    && pp.group_color == owned.group_color
+                    && (pp.house_count > 0 || pp.hotel_count > 0)
+            });
+            if built {
+                return Err(anyhow::anyhow!(
+                    "{} is in a color group with buildings; sell them first",
+                    owned.property_name.as_deref().unwrap_or("The property")
+                ));
+            }
+        }
+
+        for inventory_id in cards.iter().flat_map(|c| c.0.iter()) {
+            if !inventory.iter().any(|c| c.id == *inventory_id) {
+                return Err(anyhow::anyhow!("Card {} is not held by the participant giving it", inventory_id));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigdecimal::BigDecimal;
+    use crate::application::standings_service::StandingsService;
+    use crate::domain::entities::GameParticipant;
+    use crate::domain::errors::ConcurrencyConflict;
+    use crate::domain::repositories::{MockBankIouRepository, MockCardRepository, MockGameRepository, MockLoanRepository, MockParticipantRepository, MockPropertyRepository, MockStandingsRepository, MockTradeRepository, MockTransactionRepository};
+
+    fn owned(participant_id: Uuid, color: &str, house_count: i32) -> ParticipantProperty {
+        ParticipantProperty {
+            id: Uuid::new_v4(),
+            game_id: Uuid::nil(),
+            participant_id,
+            property_id: Uuid::new_v4(),
+            is_mortgaged: false,
+            house_count,
+            hotel_count: 0,
+            version: 0,
+            property_name: None,
+            group_color: Some(color.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_validate_trade() {
+        let (ana, beto) = (Uuid::new_v4(), Uuid::new_v4());
+        let ana_red = owned(ana, "red", 0);
+        let ana_blue = owned(ana, "blue", 0);
+        let ana_blue_built = owned(ana, "blue", 2);
+        let beto_green = owned(beto, "green", 0);
+        let ownership = vec![ana_red.clone(), ana_blue.clone(), ana_blue_built, beto_green.clone()];
+        let ana_card = ParticipantCard {
+            id: Uuid::new_v4(),
+            participant_id: ana,
+            card_id: Uuid::new_v4(),
+            is_active: true,
+            acquired_at: None,
+            title: None,
+            description: None,
+            type_: None,
+            color: None,
+            action_type: None,
+            action_value: None,
+        };
+
+        let trade = |offer: Vec<Uuid>, request: Vec<Uuid>, cards: Vec<Uuid>| Trade {
+            id: Uuid::new_v4(),
+            game_id: Uuid::nil(),
+            initiator_id: ana,
+            target_id: beto,
+            offer_cash: BigDecimal::from(0),
+            offer_properties: Some(sqlx::types::Json(offer)),
+            offer_cards: Some(sqlx::types::Json(cards)),
+            request_cash: BigDecimal::from(0),
+            request_properties: Some(sqlx::types::Json(request)),
+            request_cards: None,
+            status: "PENDING".to_string(),
+            created_at: None,
+        };
+        let cards = [ana_card.clone()];
+
+        // Red for green plus a card: fine
+        assert!(validate_trade(&trade(vec![ana_red.property_id], vec![beto_green.property_id], vec![ana_card.id]), &ownership, &cards, &[]).is_ok());
+        // Ana asks for something Beto does not own
+        assert!(validate_trade(&trade(vec![], vec![ana_red.property_id], vec![]), &ownership, &cards, &[]).is_err());
+        // Blue has houses on the other lot of the group
+        assert!(validate_trade(&trade(vec![ana_blue.property_id], vec![], vec![]), &ownership, &cards, &[]).is_err());
+        // The card was used meanwhile
+        assert!(validate_trade(&trade(vec![], vec![], vec![ana_card.id]), &ownership, &[], &[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_second_accept_does_not_execute_trade() {
+        let (game_id, user_id, initiator_id, target_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let pending = Trade {
+            id: Uuid::new_v4(),
+            game_id,
+            initiator_id,
+            target_id,
+            offer_cash: BigDecimal::from(100),
+            offer_properties: None,
+            offer_cards: None,
+            request_cash: BigDecimal::from(0),
+            request_properties: None,
+            request_cards: None,
+            status: "PENDING".to_string(),
+            created_at: None,
+        };
+
+        // Both accepts read the trade as pending, but the other one commits first
+        let mut accepted = pending.clone();
+        accepted.status = "ACCEPTED".to_string();
+        let mut trade_repo = MockTradeRepository::new();
+        let mut seq = mockall::Sequence::new();
+        trade_repo.expect_find_by_id().times(1).in_sequence(&mut seq).returning(move |_| Ok(Some(pending.clone())));
+        trade_repo.expect_find_by_id().times(1).in_sequence(&mut seq).returning(move |_| Ok(Some(accepted.clone())));
+        trade_repo.expect_update().never();
+
+        let mut property_repo = MockPropertyRepository::new();
+        property_repo.expect_find_ownership_by_game().returning(|_| Ok(vec![]));
+        let mut card_repo = MockCardRepository::new();
+        card_repo.expect_get_inventory().returning(|_| Ok(vec![]));
+        let mut participant_repo = MockParticipantRepository::new();
+        participant_repo.expect_find_by_game_id().returning(move |_| Ok(vec![GameParticipant {
+            id: target_id,
+            game_id,
+            user_id,
+            balance: BigDecimal::from(1500),
+            position: 1,
+            joined_at: None,
+            bankrupt_at: None,
+            bills: None,
+        }]));
+
+        // The batch finds the trade no longer pending and rolls back
+        let mut transaction_repo = MockTransactionRepository::new();
+        transaction_repo.expect_execute_batch()
+            .withf(|batch| batch.ownership.iter().any(|c| matches!(c, OwnershipChange::AcceptTrade { .. })))
+            .times(1)
+            .returning(|_| Err(ConcurrencyConflict { entity: "trade" }.into()));
+
+        let (tx, _rx) = tokio::sync::broadcast::channel(10);
+        let standings_service = Arc::new(StandingsService::new(
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockParticipantRepository::new()),
+            Arc::new(MockPropertyRepository::new()),
+            Arc::new(MockStandingsRepository::new()),
+            Arc::new(MockLoanRepository::new()),
+            tx.clone(),
+        ));
+        let transaction_service = Arc::new(TransactionService::new(
+            Arc::new(transaction_repo),
+            Arc::new(MockParticipantRepository::new()),
+            Arc::new(MockCardRepository::new()),
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockBankIouRepository::new()),
+            standings_service,
+            tx.clone(),
+        ));
+        let service = TradeService::new(
+            Arc::new(trade_repo),
+            Arc::new(property_repo),
+            Arc::new(card_repo),
+            Arc::new(participant_repo),
+            transaction_service,
+            tx,
+        );
+
+        let err = service.accept_trade(Uuid::new_v4(), user_id).await.unwrap_err();
+
+        assert_eq!(err.to_string(), "Trade was already resolved");
+    }
+}
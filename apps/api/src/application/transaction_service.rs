@@ -1,17 +1,21 @@
 use std::sync::Arc;
 use uuid::Uuid;
-use bigdecimal::BigDecimal;
+use bigdecimal::{BigDecimal, Zero};
 use crate::domain::{
-    entities::Transaction,
-    repositories::{TransactionRepository, ParticipantRepository, CardRepository},
+    entities::{BankEmptyPolicy, BankIou, BatchResult, GameParticipant, GameSession, GameStatus, WinReason, JackpotHistoryEntry, JackpotRules, MultiTransferDirection, OwnershipChange, Transaction, TransactionCategory, TransactionFilter, TransferBatch, TransferDetails, TransferLeg},
+    repositories::{TransactionRepository, ParticipantRepository, CardRepository, GameRepository, BankIouRepository},
     events::GameEvent,
 };
+use crate::application::standings_service::StandingsService;
 use tokio::sync::broadcast;
 
 pub struct TransactionService {
     transaction_repo: Arc<dyn TransactionRepository + Send + Sync>,
-    _participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
+    participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
     card_repo: Arc<dyn CardRepository + Send + Sync>,
+    game_repo: Arc<dyn GameRepository + Send + Sync>,
+    iou_repo: Arc<dyn BankIouRepository + Send + Sync>,
+    standings_service: Arc<StandingsService>,
     tx: broadcast::Sender<GameEvent>,
 }
 
@@ -20,107 +24,631 @@ impl TransactionService {
         transaction_repo: Arc<dyn TransactionRepository + Send + Sync>,
         participant_repo: Arc<dyn ParticipantRepository + Send + Sync>,
         card_repo: Arc<dyn CardRepository + Send + Sync>,
+        game_repo: Arc<dyn GameRepository + Send + Sync>,
+        iou_repo: Arc<dyn BankIouRepository + Send + Sync>,
+        standings_service: Arc<StandingsService>,
         tx: broadcast::Sender<GameEvent>,
     ) -> Self {
-        Self { transaction_repo, _participant_repo: participant_repo, card_repo, tx }
+        Self { transaction_repo, participant_repo, card_repo, game_repo, iou_repo, standings_service, tx }
     }
 
-    pub async fn transfer(&self, game_id: Uuid, from_pid: Option<Uuid>, to_pid: Option<Uuid>, amount: BigDecimal, description: Option<String>) -> Result<Transaction, anyhow::Error> {
+    /// Transfer requested by a player through the API. Game rules (salary, rent engines, cards)
+    /// call `transfer` directly; players go through the permission checks here.
+    pub async fn user_transfer(&self, game_id: Uuid, user_id: Uuid, from_pid: Option<Uuid>, to_pid: Option<Uuid>, amount: BigDecimal, details: TransferDetails) -> Result<Transaction, anyhow::Error> {
+        let game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        let participants = self.participant_repo.find_by_game_id(game_id).await?;
+
+        authorize_transfer(game.banker_id(), &participants, user_id, from_pid, to_pid, &amount)?;
+        self.transfer(game_id, from_pid, to_pid, amount, details).await
+    }
+
+    pub async fn transfer(&self, game_id: Uuid, from_pid: Option<Uuid>, to_pid: Option<Uuid>, amount: BigDecimal, details: TransferDetails) -> Result<Transaction, anyhow::Error> {
+        let leg = TransferLeg { from_participant_id: from_pid, to_participant_id: to_pid, amount, details };
+        let result = self.execute_batch(game_id, vec![leg], Vec::new()).await?;
+
+        // The payment itself comes first, El Banco jackpot legs after it
+        result.transactions.into_iter().next()
+            .ok_or_else(|| anyhow::anyhow!("Transfer produced no transaction"))
+    }
+
+    /// Execute several legs plus the ownership changes they pay for in a single database transaction.
+    /// Either everything commits or nothing does; events go out only after the commit.
+    pub async fn execute_batch(&self, game_id: Uuid, legs: Vec<TransferLeg>, ownership: Vec<OwnershipChange>) -> Result<BatchResult, anyhow::Error> {
+        let result = self.book_batch(game_id, legs, ownership).await?;
+
+        // Broadcast event. We ignore errors if nobody is listening.
+        for transaction in &result.transactions {
+            let _ = self.tx.send(GameEvent::TransactionCreated(transaction.clone()));
+        }
+
+        Ok(result)
+    }
+
+    /// One participant pays each counterparty, or collects from each, as a single grouped operation.
+    /// Without explicit counterparties everybody else in the game takes part.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn transfer_multi(
+        &self,
+        game_id: Uuid,
+        user_id: Uuid,
+        participant_id: Uuid,
+        direction: MultiTransferDirection,
+        counterparty_ids: Vec<Uuid>,
+        amount_each: BigDecimal,
+        mut details: TransferDetails,
+    ) -> Result<Vec<Transaction>, anyhow::Error> {
+        if amount_each <= BigDecimal::zero() {
+            return Err(anyhow::anyhow!("Amount must be positive"));
+        }
+
+        let participants = self.participant_repo.find_by_game_id(game_id).await?;
+        if !participants.iter().any(|p| p.id == participant_id) {
+            return Err(anyhow::anyhow!("Participant not found"));
+        }
+
+        let counterparties: Vec<Uuid> = if counterparty_ids.is_empty() {
+            participants.iter().map(|p| p.id).filter(|id| *id != participant_id).collect()
+        } else {
+            if let Some(unknown) = counterparty_ids.iter().find(|id| !participants.iter().any(|p| p.id == **id)) {
+                return Err(anyhow::anyhow!("Participant {} is not in this game", unknown));
+            }
+            if counterparty_ids.contains(&participant_id) {
+                return Err(anyhow::anyhow!("A participant cannot pay themselves"));
+            }
+            // Each counterparty takes part once; a repeated id would be charged or paid twice
+            let mut seen = std::collections::HashSet::new();
+            if let Some(repeated) = counterparty_ids.iter().find(|id| !seen.insert(**id)) {
+                return Err(anyhow::anyhow!("Participant {} is listed more than once", repeated));
+            }
+            counterparty_ids
+        };
+
+        if counterparties.is_empty() {
+            return Err(anyhow::anyhow!("Nobody to transfer with"));
+        }
+
+        let game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        let group_id = Uuid::new_v4();
+        details.group_id = Some(group_id);
+
+        let mut legs = Vec::new();
+        for other in counterparties {
+            let (from, to) = match direction {
+                MultiTransferDirection::PayEach => (participant_id, other),
+                MultiTransferDirection::CollectFromEach => (other, participant_id),
+            };
+            authorize_transfer(game.banker_id(), &participants, user_id, Some(from), Some(to), &amount_each)?;
+            legs.push(TransferLeg {
+                from_participant_id: Some(from),
+                to_participant_id: Some(to),
+                amount: amount_each.clone(),
+                details: details.clone(),
+            });
+        }
+
+        let result = self.book_batch(game_id, legs, Vec::new()).await?;
+
+        let _ = self.tx.send(GameEvent::TransactionsGrouped {
+            game_id,
+            group_id,
+            transactions: result.transactions.clone(),
+        });
+
+        Ok(result.transactions)
+    }
+
+    async fn book_batch(&self, game_id: Uuid, legs: Vec<TransferLeg>, ownership: Vec<OwnershipChange>) -> Result<BatchResult, anyhow::Error> {
+        // Only legs with the Bank on one side depend on the jackpot and reserve rules
+        let game = if legs.iter().any(|l| l.from_participant_id.is_none() || l.to_participant_id.is_none()) {
+            Some(self.game_repo.find_by_id(game_id).await?
+                .ok_or_else(|| anyhow::anyhow!("Game not found"))?)
+        } else {
+            None
+        };
+        let rules = game.as_ref().map(|g| g.house_rules.jackpot.clone());
+
+        let mut rows = Vec::new();
+        let mut bills = std::collections::HashMap::new();
+        for leg in legs {
+            let explicit = leg.details.bills.clone();
+            let resolved = self.resolve_leg(game_id, leg, rules.as_ref()).await?;
+            // Explicit bills belong to the payment itself, the first row
+            if let (Some(explicit), Some(row)) = (explicit, resolved.first()) {
+                bills.insert(row.id, explicit);
+            }
+            rows.extend(resolved);
+        }
+
+        // A Bank that has run dry pays what it has and writes IOUs for the rest; the repository
+        // cuts the payouts against the reserve it locks, so concurrent payouts cannot spend it twice
+        let bank_ious = matches!(&game, Some(g) if g.house_rules.bank.when_empty == BankEmptyPolicy::Iou && g.bank_balance.is_some());
+
+        let result = self.transaction_repo.execute_batch(TransferBatch { game_id, legs: rows, ownership, bills, bank_ious }).await?;
+        self.announce_jackpot(game_id, result.transactions.iter().map(|t| &t.jackpot_delta).sum()).await?;
+
+        for repayment in &result.repayments {
+            let _ = self.tx.send(GameEvent::TransactionCreated(repayment.clone()));
+        }
+        for iou in &result.ious {
+            let _ = self.tx.send(GameEvent::BankIouUpdated(iou.clone()));
+        }
+
+        if let Some(game) = game.filter(|g| g.bank_balance.is_some()) {
+            let delta: BigDecimal = result.transactions.iter().chain(&result.repayments).map(|t| t.bank_delta()).sum();
+            self.after_bank_moved(game, delta).await?;
+        }
+        Ok(result)
+    }
+
+    // Announce the new reserve, and end the game if the Bank broke under the end-game policy
+    async fn after_bank_moved(&self, game: GameSession, delta: BigDecimal) -> Result<(), anyhow::Error> {
+        if delta.is_zero() {
+            return Ok(());
+        }
+        let balance = self.game_repo.find_by_id(game.id).await?
+            .and_then(|g| g.bank_balance)
+            .unwrap_or_default();
+        let _ = self.tx.send(GameEvent::BankUpdated { game_id: game.id, balance: balance.clone(), delta });
+
+        if game.house_rules.bank.when_empty == BankEmptyPolicy::EndGame
+            && balance < BigDecimal::zero()
+            && game.status == GameStatus::ACTIVE.to_string()
+        {
+            tracing::info!("Bank of game {} ran dry ({}), ending the game", game.id, balance);
+            self.standings_service.end_game(game.id, WinReason::BankExhausted, None).await?;
+        }
+        Ok(())
+    }
+
+    /// Start of the game: whatever the reserve rule leaves after the players' opening funds stays in the Bank
+    pub async fn open_bank(&self, game_id: Uuid) -> Result<(), anyhow::Error> {
+        let game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        let Some(reserve) = game.house_rules.bank.reserve.clone() else {
+            return Ok(());
+        };
+
+        let handed_out: BigDecimal = self.participant_repo.find_by_game_id(game_id).await?
+            .iter()
+            .map(|p| &p.balance)
+            .sum();
+        let balance = reserve - handed_out - &game.jackpot_balance;
+        self.game_repo.set_bank_balance(game_id, Some(balance.clone())).await?;
+
+        let _ = self.tx.send(GameEvent::BankUpdated { game_id, balance, delta: BigDecimal::zero() });
+        Ok(())
+    }
+
+    pub async fn get_bank_ious(&self, game_id: Uuid) -> Result<Vec<BankIou>, anyhow::Error> {
+        self.iou_repo.find_by_game(game_id).await
+    }
+
+    // Turn a requested leg into the rows to book, applying the jackpot and El Banco rules
+    async fn resolve_leg(&self, game_id: Uuid, leg: TransferLeg, rules: Option<&JackpotRules>) -> Result<Vec<Transaction>, anyhow::Error> {
         // Balance validation removed to allow negative balances (debt)
-        
-        let final_from = from_pid;
+
+        if leg.details.category == TransactionCategory::Reversal {
+            return Err(anyhow::anyhow!("Reversals are created by reversing a transaction"));
+        }
+
+        let TransferLeg { from_participant_id: from_pid, to_participant_id: to_pid, amount, details } = leg;
         let mut final_to = to_pid;
         let mut final_amount = amount.clone();
-        
+        let mut jackpot_leg = None;
+
+        // Payments TO the Bank (from_pid = Some, to_pid = None) feed the jackpot as the game's rules say
+        let contribution = match rules {
+            Some(rules) if from_pid.is_some() && to_pid.is_none() => rules.contribution(details.category, &amount),
+            _ => BigDecimal::zero(),
+        };
+        let mut jackpot_delta = contribution.clone();
+
         // --- El Banco Check ---
-        // We need to resolve "El Banco" owner.
-        // If from_pid or to_pid involves Bank or Owner, we apply rules.
-        
-        if let Ok(Some(bank_owner_pid)) = self.card_repo.find_owner_of_card_title(game_id, "El Banco").await {
-            
-            // Case 1: Payment TO Bank (from_pid = Some, to_pid = None)
-            if from_pid.is_some() && to_pid.is_none() {
+        // "los pagos... van ademas del jackpot, a la cuenta del jugador"
+        if from_pid.is_some() && to_pid.is_none() {
+            if let Some(bank_owner_pid) = self.card_repo.find_owner_of_card_title(game_id, "El Banco").await? {
+                // The jackpot share moves to its own row since the payment no longer reaches the Bank
+                jackpot_delta = BigDecimal::zero();
                 if from_pid != Some(bank_owner_pid) {
-                    // Rule: Other player pays Bank -> Redirect to Owner.
+                    // Rule: Other player pays Bank -> Redirect to Owner, plus the same amount into the Jackpot
                     final_to = Some(bank_owner_pid);
-                    
-                    // Side-effect: Also add to Jackpot?
-                    // "los pagos... van ademas del jackpot, a la cuenta del jugador"
-                    // We need a secondary transaction for Jackpot injection.
-                    // Doing it async/fire-and-forget.
-                    let tx_repo = self.transaction_repo.clone();
-                    let amt = amount.clone();
-                    tokio::spawn(async move {
-                         let _ = tx_repo.execute_transfer(Transaction {
-                             id: Uuid::new_v4(),
-                             game_id,
-                             from_participant_id: None, 
-                             to_participant_id: None, // To Jackpot
-                             amount: amt,
-                             description: Some("El Banco Bonus (Inflation)".to_string()),
-                             created_at: Some(time::OffsetDateTime::now_utc())
-                         }).await;
-                    });
-
+                    jackpot_leg = Some("El Banco Bonus (Inflation)");
                 } else {
-                    // Rule: Owner pays Bank -> Immune (Cost 0) BUT add to Jackpot.
+                    // Rule: Owner pays Bank -> Immune (Cost 0) BUT add to Jackpot
                     final_amount = BigDecimal::from(0);
-                    
-                    // Add original amount to Jackpot
-                    let tx_repo = self.transaction_repo.clone();
-                    let amt = amount.clone();
-                    tokio::spawn(async move {
-                         let _ = tx_repo.execute_transfer(Transaction {
-                             id: Uuid::new_v4(),
-                             game_id,
-                             from_participant_id: None,
-                             to_participant_id: None, // To Jackpot
-                             amount: amt,
-                             description: Some("El Banco Owner Payment (Inflation)".to_string()),
-                             created_at: Some(time::OffsetDateTime::now_utc())
-                         }).await;
-                    });
+                    jackpot_leg = Some("El Banco Owner Payment (Inflation)");
                 }
             }
         }
 
-        let tx = Transaction {
+        let mut rows = vec![Transaction {
             id: Uuid::new_v4(),
             game_id,
-            from_participant_id: final_from,
+            from_participant_id: from_pid,
             to_participant_id: final_to,
             amount: final_amount,
-            description,
+            description: details.description,
             created_at: Some(time::OffsetDateTime::now_utc()),
-        };
+            category: details.category,
+            property_id: details.property_id,
+            card_id: details.card_id,
+            trade_id: details.trade_id,
+            auction_id: details.auction_id,
+            loan_id: details.loan_id,
+            group_id: details.group_id,
+            jackpot_delta,
+            reverses_transaction_id: None,
+            reversed_by_transaction_id: None,
+        }];
 
-        let result = self.transaction_repo.execute_transfer(tx).await;
-        
-        if let Ok(transaction) = &result {
-            // Broadcast event. We ignore errors if nobody is listening.
-            let _ = self.tx.send(GameEvent::TransactionCreated(transaction.clone()));
+        if let Some(description) = jackpot_leg.filter(|_| !contribution.is_zero()) {
+            rows.push(Transaction {
+                id: Uuid::new_v4(),
+                game_id,
+                from_participant_id: None,
+                to_participant_id: None, // To Jackpot
+                amount: contribution.clone(),
+                description: Some(description.to_string()),
+                created_at: Some(time::OffsetDateTime::now_utc()),
+                category: TransactionCategory::Jackpot,
+                property_id: details.property_id,
+                card_id: details.card_id,
+                trade_id: details.trade_id,
+                auction_id: details.auction_id,
+                loan_id: details.loan_id,
+                group_id: details.group_id,
+                jackpot_delta: contribution, // Feeds the jackpot directly
+                reverses_transaction_id: None,
+                reversed_by_transaction_id: None,
+            });
         }
 
-        result
+        Ok(rows)
     }
 
-    pub async fn delete_transaction(&self, tx_id: Uuid) -> Result<(), anyhow::Error> {
-        // Just delete for now.
-        // TODO: Broadcast undo? For now just delete.
-        self.transaction_repo.delete(tx_id).await
+    /// Undo a transaction by booking a compensating entry; the original stays in the ledger, marked as reversed.
+    /// Only the host may reverse.
+    pub async fn reverse_transaction(&self, game_id: Uuid, tx_id: Uuid, user_id: Uuid) -> Result<Transaction, anyhow::Error> {
+        let game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+        if game.host_user_id != user_id {
+            return Err(anyhow::anyhow!("Only host can reverse transactions"));
+        }
+
+        let original = self.transaction_repo.find_by_id(tx_id).await?
+            .filter(|t| t.game_id == game_id)
+            .ok_or_else(|| anyhow::anyhow!("Transaction not found"))?;
+
+        let description = Some(format!("Reversal: {}", original.description.as_deref().unwrap_or("transaction")));
+        let (original, reversal) = self.transaction_repo.reverse(original.id, description).await?;
+        self.announce_jackpot(game_id, reversal.jackpot_delta.clone()).await?;
+
+        let _ = self.tx.send(GameEvent::TransactionReversed {
+            game_id,
+            original: Box::new(original),
+            reversal: Box::new(reversal.clone()),
+        });
+
+        Ok(reversal)
     }
-    pub async fn get_transactions(&self, game_id: Uuid) -> Result<Vec<Transaction>, anyhow::Error> {
-        self.transaction_repo.find_by_game(game_id).await
+
+    pub async fn get_transactions(&self, game_id: Uuid, filter: TransactionFilter) -> Result<Vec<Transaction>, anyhow::Error> {
+        self.transaction_repo.find_by_game(game_id, filter).await
     }
 
+    /// Claim the whole jackpot; requires standing on Parada Libre unless the house rules waive it.
+    /// The seed amount, if any, is put back in right after.
     pub async fn claim_jackpot(&self, game_id: Uuid, user_id: Uuid) -> Result<Transaction, anyhow::Error> {
-        let result = self.transaction_repo.claim_jackpot(game_id, user_id).await;
+        let game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        let rules = game.house_rules.0.jackpot;
+
+        let participant = self.participant_repo.find_by_game_id(game_id).await?
+            .into_iter()
+            .find(|p| p.user_id == user_id)
+            .ok_or_else(|| anyhow::anyhow!("User is not a participant"))?;
+        if !rules.claim_anywhere && participant.position != FREE_PARKING_POSITION {
+            return Err(anyhow::anyhow!("Land on Parada Libre (space {}) to claim the jackpot", FREE_PARKING_POSITION));
+        }
+
+        let transaction = self.transaction_repo.claim_jackpot(game_id, user_id).await?;
+        let _ = self.tx.send(GameEvent::TransactionCreated(transaction.clone()));
+        self.announce_jackpot(game_id, transaction.jackpot_delta.clone()).await?;
+
+        if rules.seed_amount > BigDecimal::zero() {
+            self.seed_jackpot(game_id, rules.seed_amount).await?;
+        }
+
+        Ok(transaction)
+    }
+
+    /// The Bank puts a fixed amount into the jackpot (game start, after each claim)
+    pub async fn seed_jackpot(&self, game_id: Uuid, amount: BigDecimal) -> Result<Transaction, anyhow::Error> {
+        let mut seed = Transaction::from_bank(game_id, None, amount.clone(), "Jackpot seed");
+        seed.category = TransactionCategory::Jackpot;
+        seed.jackpot_delta = amount;
+
+        let result = self.transaction_repo.execute_batch(TransferBatch { game_id, legs: vec![seed], ownership: Vec::new(), bills: Default::default(), bank_ious: false }).await?;
+        let seeded = result.transactions.into_iter().next()
+            .ok_or_else(|| anyhow::anyhow!("Seeding produced no transaction"))?;
+
+        let _ = self.tx.send(GameEvent::TransactionCreated(seeded.clone()));
+        self.announce_jackpot(game_id, seeded.jackpot_delta.clone()).await?;
+        Ok(seeded)
+    }
+
+    pub async fn get_jackpot_history(&self, game_id: Uuid) -> Result<Vec<JackpotHistoryEntry>, anyhow::Error> {
+        let transactions = self.transaction_repo.find_by_game(game_id, TransactionFilter::default()).await?;
+        Ok(jackpot_history(transactions))
+    }
+
+    async fn announce_jackpot(&self, game_id: Uuid, delta: BigDecimal) -> Result<(), anyhow::Error> {
+        if delta.is_zero() {
+            return Ok(());
+        }
+        let game = self.game_repo.find_by_id(game_id).await?
+            .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+        let _ = self.tx.send(GameEvent::JackpotUpdated { game_id, balance: game.jackpot_balance, delta });
+        Ok(())
+    }
+}
+
+// Parada Libre
+const FREE_PARKING_POSITION: i32 = 20;
+
+// Oldest first, with the running balance after each change
+fn jackpot_history(mut transactions: Vec<Transaction>) -> Vec<JackpotHistoryEntry> {
+    transactions.retain(|t| !t.jackpot_delta.is_zero());
+    transactions.sort_by_key(|t| t.created_at);
+
+    let mut balance = BigDecimal::zero();
+    transactions.into_iter().map(|t| {
+        balance += &t.jackpot_delta;
+        JackpotHistoryEntry {
+            transaction_id: t.id,
+            category: t.category,
+            description: t.description,
+            participant_id: t.from_participant_id.or(t.to_participant_id),
+            delta: t.jackpot_delta,
+            balance_after: balance.clone(),
+            created_at: t.created_at,
+        }
+    }).collect()
+}
+
+// Who may move money through the API: players debit only their own account. Bank payouts are reserved
+// for the banker (the host unless reassigned), who may also charge a player, but only to the Bank:
+// moving money between players takes the payer's own consent (a payment request).
+fn authorize_transfer(
+    banker_user_id: Uuid,
+    participants: &[GameParticipant],
+    user_id: Uuid,
+    from_pid: Option<Uuid>,
+    to_pid: Option<Uuid>,
+    amount: &BigDecimal,
+) -> Result<(), anyhow::Error> {
+    if *amount <= BigDecimal::zero() {
+        return Err(anyhow::anyhow!("Amount must be positive"));
+    }
+    if from_pid == to_pid {
+        return Err(anyhow::anyhow!("Source and destination must differ"));
+    }
+
+    let owner_of = |pid: Uuid| participants.iter()
+        .find(|p| p.id == pid)
+        .map(|p| p.user_id)
+        .ok_or_else(|| anyhow::anyhow!("Participant {} is not in this game", pid));
 
-        if let Ok(transaction) = &result {
-             // Broadcast event
-             let _ = self.tx.send(GameEvent::TransactionCreated(transaction.clone()));
+    if let Some(to) = to_pid {
+        owner_of(to)?;
+    }
+
+    let is_banker = banker_user_id == user_id;
+    match from_pid {
+        Some(from) => {
+            if owner_of(from)? != user_id && !(is_banker && to_pid.is_none()) {
+                return Err(anyhow::anyhow!("You can only pay from your own account"));
+            }
+        }
+        None => {
+            if !is_banker {
+                return Err(anyhow::anyhow!("Only the banker can pay out from the Bank"));
+            }
         }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use crate::domain::entities::{short_pay, JackpotSource};
+    use crate::domain::repositories::{MockBankIouRepository, MockCardRepository, MockGameRepository, MockLoanRepository, MockParticipantRepository, MockStandingsRepository, MockTransactionRepository};
+
+    fn participant(user_id: Uuid) -> GameParticipant {
+        GameParticipant {
+            id: Uuid::new_v4(),
+            game_id: Uuid::nil(),
+            user_id,
+            balance: BigDecimal::from(1500),
+            position: 0,
+            joined_at: None,
+            bankrupt_at: None,
+            bills: None,
+        }
+    }
+
+    #[test]
+    fn test_authorize_transfer() {
+        let (banker, alice, bob) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let participants = vec![participant(banker), participant(alice), participant(bob)];
+        let (banker_p, alice_p, bob_p) = (participants[0].id, participants[1].id, participants[2].id);
+        let amount = BigDecimal::from(100);
+        let check = |user, from, to, amount: &BigDecimal| authorize_transfer(banker, &participants, user, from, to, amount).is_ok();
+
+        // Own account, to a player or to the Bank
+        assert!(check(alice, Some(alice_p), Some(bob_p), &amount));
+        assert!(check(alice, Some(alice_p), None, &amount));
+        // Someone else's account or a Bank payout
+        assert!(!check(alice, Some(bob_p), Some(alice_p), &amount));
+        assert!(!check(alice, None, Some(alice_p), &amount));
+        // The banker pays out of the Bank and charges players to the Bank, but cannot move money between them
+        assert!(check(banker, None, Some(banker_p), &amount));
+        assert!(check(banker, Some(bob_p), None, &amount));
+        assert!(!check(banker, Some(bob_p), Some(alice_p), &amount));
+        assert!(!check(banker, Some(bob_p), Some(banker_p), &amount));
+        // Outsiders, non-positive amounts and self transfers
+        assert!(!check(alice, Some(alice_p), Some(Uuid::new_v4()), &amount));
+        assert!(!check(alice, Some(alice_p), Some(bob_p), &BigDecimal::zero()));
+        assert!(!check(alice, Some(alice_p), Some(alice_p), &amount));
+    }
+
+    // A service over a game with the given participants, recording every batch it books
+    fn multi_service(host: Uuid, participants: Vec<GameParticipant>, booked: Arc<Mutex<Vec<TransferBatch>>>) -> TransactionService {
+        let mut participant_repo = MockParticipantRepository::new();
+        participant_repo.expect_find_by_game_id().returning(move |_| Ok(participants.clone()));
+
+        let mut game_repo = MockGameRepository::new();
+        game_repo.expect_find_by_id().returning(move |id| Ok(Some(GameSession {
+            id,
+            code: "ABCD".to_string(),
+            host_user_id: host,
+            name: "Game".to_string(),
+            status: "ACTIVE".to_string(),
+            jackpot_balance: BigDecimal::zero(),
+            created_at: None,
+            ended_at: None,
+            current_turn_user_id: None,
+            turn_order: None,
+            winner_participant_id: None,
+            win_reason: None,
+            house_rules: Default::default(),
+            deadline_at: None,
+            rematch_game_id: None,
+            banker_user_id: None,
+            bank_balance: None,
+            version: 0,
+        })));
+
+        let mut transaction_repo = MockTransactionRepository::new();
+        transaction_repo.expect_execute_batch().returning(move |batch| {
+            booked.lock().unwrap().push(batch.clone());
+            Ok(BatchResult { transactions: batch.legs, ..Default::default() })
+        });
+
+        let (tx, _rx) = broadcast::channel(10);
+        let standings_service = Arc::new(StandingsService::new(
+            Arc::new(MockGameRepository::new()),
+            Arc::new(MockParticipantRepository::new()),
+            Arc::new(crate::domain::repositories::MockPropertyRepository::new()),
+            Arc::new(MockStandingsRepository::new()),
+            Arc::new(MockLoanRepository::new()),
+            tx.clone(),
+        ));
+        TransactionService::new(
+            Arc::new(transaction_repo),
+            Arc::new(participant_repo),
+            Arc::new(MockCardRepository::new()),
+            Arc::new(game_repo),
+            Arc::new(MockBankIouRepository::new()),
+            standings_service,
+            tx,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_transfer_multi_pays_each_counterparty_once() {
+        let (alice, bob, carol) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let participants = vec![participant(alice), participant(bob), participant(carol)];
+        let (alice_p, bob_p, carol_p) = (participants[0].id, participants[1].id, participants[2].id);
+        let booked = Arc::new(Mutex::new(Vec::new()));
+        let service = multi_service(alice, participants, booked.clone());
+        let pay = |counterparties: Vec<Uuid>| service.transfer_multi(
+            Uuid::nil(), alice, alice_p, MultiTransferDirection::PayEach, counterparties,
+            BigDecimal::from(50), TransferDetails::new(TransactionCategory::Manual, "Chairman of the board"),
+        );
+
+        // A repeated counterparty or the payer among them is refused before anything is booked
+        assert!(pay(vec![bob_p, bob_p]).await.is_err());
+        assert!(pay(vec![bob_p, alice_p]).await.is_err());
+        assert!(booked.lock().unwrap().is_empty());
+
+        // Everybody else by default, one leg each, all in one group
+        let transactions = pay(vec![]).await.unwrap();
+        assert_eq!(transactions.len(), 2);
+        let mut payees: Vec<Uuid> = transactions.iter().filter_map(|t| t.to_participant_id).collect();
+        payees.sort();
+        let mut expected = vec![bob_p, carol_p];
+        expected.sort();
+        assert_eq!(payees, expected);
+        assert!(transactions.iter().all(|t| t.from_participant_id == Some(alice_p) && t.amount == 50));
+        assert!(transactions[0].group_id.is_some() && transactions[0].group_id == transactions[1].group_id);
+        assert_eq!(booked.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_jackpot_history_and_sources() {
+        let now = time::OffsetDateTime::now_utc();
+        let entry = |minutes: i64, delta: i32, category: TransactionCategory| Transaction {
+            id: Uuid::new_v4(),
+            game_id: Uuid::nil(),
+            from_participant_id: None,
+            to_participant_id: None,
+            amount: BigDecimal::from(delta.abs()),
+            description: None,
+            created_at: Some(now + time::Duration::minutes(minutes)),
+            category,
+            property_id: None,
+            card_id: None,
+            trade_id: None,
+            auction_id: None,
+            loan_id: None,
+            group_id: None,
+            jackpot_delta: BigDecimal::from(delta),
+            reverses_transaction_id: None,
+            reversed_by_transaction_id: None,
+        };
+
+        // Newest first, as the repository returns them
+        let history = jackpot_history(vec![
+            entry(3, -250, TransactionCategory::Jackpot),
+            entry(2, 0, TransactionCategory::Rent),
+            entry(1, 200, TransactionCategory::Tax),
+            entry(0, 50, TransactionCategory::Jackpot),
+        ]);
+        let balances: Vec<BigDecimal> = history.iter().map(|h| h.balance_after.clone()).collect();
+        assert_eq!(balances, vec![BigDecimal::from(50), BigDecimal::from(250), BigDecimal::zero()]);
+
+        let amount = BigDecimal::from(100);
+        let rules = |source| JackpotRules { source, ..Default::default() };
+        assert_eq!(rules(JackpotSource::AllBankPayments).contribution(TransactionCategory::Purchase, &amount), amount);
+        assert_eq!(rules(JackpotSource::TaxesOnly).contribution(TransactionCategory::Tax, &amount), amount);
+        assert!(rules(JackpotSource::TaxesOnly).contribution(TransactionCategory::Purchase, &amount).is_zero());
+        assert!(rules(JackpotSource::SeedOnly).contribution(TransactionCategory::Tax, &amount).is_zero());
+    }
+
+    #[test]
+    fn test_short_pay_writes_ious_once_the_bank_runs_dry() {
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut rent_in = Transaction::from_bank(Uuid::nil(), None, BigDecimal::from(50), "Tax");
+        rent_in.from_participant_id = Some(bob);
+        let mut rows = vec![
+            Transaction::from_bank(Uuid::nil(), Some(alice), BigDecimal::from(200), "Salary"),
+            rent_in,
+            Transaction::from_bank(Uuid::nil(), Some(bob), BigDecimal::from(200), "Salary"),
+        ];
+
+        let shortfalls = short_pay(&mut rows, BigDecimal::from(300));
 
-        result
+        // 300 - 200 + 50 leaves 150 for Bob's 200
+        assert_eq!(rows[0].amount, BigDecimal::from(200));
+        assert_eq!(rows[2].amount, BigDecimal::from(150));
+        assert_eq!(shortfalls, vec![(rows[2].id, bob, BigDecimal::from(50))]);
     }
 }
@@ -16,7 +16,7 @@ impl UserService {
 
     pub async fn register(&self, username: String, password: String, first_name: String, last_name: String) -> Result<User, anyhow::Error> {
         // Check if user exists
-        if let Some(_) = self.user_repo.find_by_username(&username).await? {
+        if self.user_repo.find_by_username(&username).await?.is_some() {
             return Err(anyhow::anyhow!("Username already exists"));
         }
 
@@ -108,7 +108,7 @@ mod tests {
         // Expect create to be called
         mock_repo.expect_create()
             .times(1)
-            .returning(|u| Ok(u));
+            .returning(Ok);
 
         let service = UserService::new(Arc::new(mock_repo));
         let result = service.register("testuser".to_string(), "pass123".to_string(), "Test".to_string(), "User".to_string()).await;
@@ -151,7 +151,7 @@ mod tests {
         // Expect update
         mock_repo.expect_update()
             .times(1)
-            .returning(|u| Ok(u));
+            .returning(Ok);
 
         let service = UserService::new(Arc::new(mock_repo));
         let result = service.update_profile(uid, "New".to_string(), "Name".to_string()).await;
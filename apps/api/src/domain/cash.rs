@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use bigdecimal::{BigDecimal, Signed, ToPrimitive};
+
+/// Bill values of the physical game, largest first
+pub const DENOMINATIONS: [i64; 7] = [500, 100, 50, 20, 10, 5, 1];
+
+/// Cash held as bills: denomination -> number of bills
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bills(pub BTreeMap<i64, i64>);
+
+impl Bills {
+    /// The fewest bills for an amount, as the Bank hands them out
+    pub fn from_amount(amount: i64) -> Self {
+        let mut bills = Bills::default();
+        let mut remaining = amount.max(0);
+        for d in DENOMINATIONS {
+            let n = remaining / d;
+            if n > 0 {
+                bills.0.insert(d, n);
+                remaining -= n * d;
+            }
+        }
+        bills
+    }
+
+    pub fn total(&self) -> i64 {
+        self.0.iter().map(|(d, n)| d * n).sum()
+    }
+
+    pub fn count(&self, denomination: i64) -> i64 {
+        self.0.get(&denomination).copied().unwrap_or(0)
+    }
+
+    pub fn add(&mut self, other: &Bills) {
+        for (d, n) in &other.0 {
+            *self.0.entry(*d).or_default() += n;
+        }
+        self.0.retain(|_, n| *n > 0);
+    }
+
+    pub fn remove(&mut self, other: &Bills) -> Result<(), anyhow::Error> {
+        for (d, n) in &other.0 {
+            if self.count(*d) < *n {
+                return Err(anyhow::anyhow!("Not enough {} bills", d));
+            }
+        }
+        for (d, n) in &other.0 {
+            *self.0.entry(*d).or_default() -= n;
+        }
+        self.0.retain(|_, n| *n > 0);
+        Ok(())
+    }
+
+    /// Every bill of one denomination ("todos los de 50")
+    pub fn all_of(&self, denomination: i64) -> Bills {
+        let mut bills = Bills::default();
+        let n = self.count(denomination);
+        if n > 0 {
+            bills.0.insert(denomination, n);
+        }
+        bills
+    }
+}
+
+/// Bills the payer hands over for `amount` and the change that comes back.
+/// Pays exactly when the wallet allows it, otherwise overpays as little as the bills permit.
+pub fn pay_with_change(wallet: &Bills, amount: i64) -> Result<(Bills, Bills), anyhow::Error> {
+    if wallet.total() < amount {
+        return Err(anyhow::anyhow!("Not enough cash: has {}, needs {}", wallet.total(), amount));
+    }
+
+    // Bounded knapsack over the sums the wallet can form. A minimal overpayment never
+    // reaches amount + largest bill (that bill could be left out), which bounds the search.
+    let amount = amount.max(0) as usize;
+    let limit = amount + DENOMINATIONS[0] as usize - 1;
+    let mut reachable = vec![false; limit + 1];
+    let mut last_bill = vec![0i64; limit + 1];
+    reachable[0] = true;
+
+    for d in DENOMINATIONS {
+        let available = wallet.count(d);
+        let step = d as usize;
+        let mut used = vec![0i64; limit + 1];
+        for sum in step..=limit {
+            if !reachable[sum] && reachable[sum - step] && used[sum - step] < available {
+                reachable[sum] = true;
+                last_bill[sum] = d;
+                used[sum] = used[sum - step] + 1;
+            }
+        }
+    }
+
+    let paid = (amount..=limit).find(|s| reachable[*s])
+        .ok_or_else(|| anyhow::anyhow!("Not enough cash"))?;
+
+    let mut handed = Bills::default();
+    let mut sum = paid;
+    while sum > 0 {
+        let d = last_bill[sum];
+        *handed.0.entry(d).or_default() += 1;
+        sum -= d as usize;
+    }
+
+    Ok((handed, Bills::from_amount((paid - amount) as i64)))
+}
+
+/// Wallets after a payment of `amount`; `None` on either side is the Bank, which never runs out of bills.
+/// The payer hands over `explicit` bills when given, which must add up to the amount, otherwise pays and takes change.
+/// The payee gives the change back from what they now hold; if they cannot make it exactly,
+/// the Bank breaks the bills: the payee keeps `amount` in fresh bills and the Bank returns the change.
+pub fn hand_over(payer: Option<&Bills>, payee: Option<&Bills>, amount: i64, explicit: Option<&Bills>) -> Result<(Option<Bills>, Option<Bills>), anyhow::Error> {
+    if let Some(bills) = explicit.filter(|b| b.total() != amount) {
+        return Err(anyhow::anyhow!("The bills add up to {}, not {}", bills.total(), amount));
+    }
+
+    let (handed, change) = match (explicit, payer) {
+        (Some(bills), _) => (bills.clone(), Bills::default()),
+        (None, Some(wallet)) => pay_with_change(wallet, amount)?,
+        (None, None) => (Bills::from_amount(amount), Bills::default()),
+    };
+
+    let mut payer = payer.cloned();
+    if let Some(wallet) = payer.as_mut() {
+        wallet.remove(&handed)?;
+    }
+
+    let mut returned = change.clone();
+    let payee = match payee {
+        None => None,
+        Some(wallet) => {
+            let mut received = wallet.clone();
+            received.add(&handed);
+            match pay_with_change(&received, change.total()) {
+                Ok((exact, rest)) if rest.0.is_empty() => {
+                    received.remove(&exact)?;
+                    returned = exact;
+                }
+                _ => {
+                    received = wallet.clone();
+                    received.add(&Bills::from_amount(amount));
+                }
+            }
+            Some(received)
+        }
+    };
+
+    if let Some(wallet) = payer.as_mut() {
+        wallet.add(&returned);
+    }
+    Ok((payer, payee))
+}
+
+/// A balance as the fewest bills, when switching a game to bills; a debt has no bills to show for it
+pub fn wallet_for(balance: &BigDecimal) -> Result<Bills, anyhow::Error> {
+    if balance.is_negative() {
+        return Err(anyhow::anyhow!("is in debt; settle it before switching to bills"));
+    }
+    Ok(Bills::from_amount(whole_amount(balance)?))
+}
+
+/// Bills only cover whole amounts
+pub fn whole_amount(amount: &BigDecimal) -> Result<i64, anyhow::Error> {
+    if !amount.is_integer() {
+        return Err(anyhow::anyhow!("Cash mode only handles whole amounts, got {}", amount));
+    }
+    amount.to_i64().ok_or_else(|| anyhow::anyhow!("Amount out of range"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bills(pairs: &[(i64, i64)]) -> Bills {
+        Bills(pairs.iter().copied().collect())
+    }
+
+    #[test]
+    fn test_pay_with_change() {
+        let wallet = bills(&[(500, 1), (100, 2), (20, 1), (5, 3)]);
+
+        // Exact
+        let (handed, change) = pay_with_change(&wallet, 125).unwrap();
+        assert_eq!(handed, bills(&[(100, 1), (20, 1), (5, 1)]));
+        assert!(change.0.is_empty());
+
+        // 50 needs a 100 broken: change 50
+        let wallet = bills(&[(100, 1), (20, 1)]);
+        let (handed, change) = pay_with_change(&wallet, 50).unwrap();
+        assert_eq!(handed, bills(&[(100, 1)]));
+        assert_eq!(change, bills(&[(50, 1)]));
+
+        assert!(pay_with_change(&wallet, 500).is_err());
+        assert_eq!(Bills::from_amount(1735), bills(&[(500, 3), (100, 2), (20, 1), (10, 1), (5, 1)]));
+
+        // The payee makes change from their own wallet
+        let payee = bills(&[(20, 2), (10, 1)]);
+        let (payer, payee) = hand_over(Some(&wallet), Some(&payee), 50, None).unwrap();
+        assert_eq!(payer.unwrap(), bills(&[(20, 3), (10, 1)]));
+        assert_eq!(payee.unwrap(), bills(&[(100, 1)]));
+
+        // Explicit bills that do not add up are refused rather than replaced by automatic change
+        assert!(hand_over(Some(&wallet), None, 50, Some(&bills(&[(20, 1)]))).is_err());
+        assert!(hand_over(Some(&wallet), None, 20, Some(&bills(&[(20, 1)]))).is_ok());
+    }
+}
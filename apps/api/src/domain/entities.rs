@@ -3,6 +3,7 @@ use uuid::Uuid;
 use time::OffsetDateTime;
 use sqlx::FromRow;
 use bigdecimal::BigDecimal;
+use crate::domain::cash::Bills;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct User {
@@ -17,6 +18,7 @@ pub struct User {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum GameStatus {
     WAITING,
     ACTIVE,
@@ -25,15 +27,16 @@ pub enum GameStatus {
     CANCELLED,
 }
 
-impl ToString for GameStatus {
-    fn to_string(&self) -> String {
-        match self {
-            GameStatus::WAITING => "WAITING".to_string(),
-            GameStatus::ACTIVE => "ACTIVE".to_string(),
-            GameStatus::PAUSED => "PAUSED".to_string(),
-            GameStatus::FINISHED => "FINISHED".to_string(),
-            GameStatus::CANCELLED => "CANCELLED".to_string(),
-        }
+impl std::fmt::Display for GameStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            GameStatus::WAITING => "WAITING",
+            GameStatus::ACTIVE => "ACTIVE",
+            GameStatus::PAUSED => "PAUSED",
+            GameStatus::FINISHED => "FINISHED",
+            GameStatus::CANCELLED => "CANCELLED",
+        };
+        f.write_str(s)
     }
 }
 
@@ -50,6 +53,162 @@ impl From<String> for GameStatus {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WinReason {
+    Card,
+    Bankruptcy,
+    TimeLimit,
+    HostDecision,
+    // The Bank ran dry under the "end game" reserve policy
+    BankExhausted,
+}
+
+impl std::fmt::Display for WinReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            WinReason::Card => "card",
+            WinReason::Bankruptcy => "bankruptcy",
+            WinReason::TimeLimit => "time_limit",
+            WinReason::HostDecision => "host_decision",
+            WinReason::BankExhausted => "bank_exhausted",
+        };
+        f.write_str(s)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    #[default]
+    Classic,
+    // Ends automatically at a deadline, ranked by net worth
+    Timed,
+}
+
+// What feeds the Free Parking jackpot
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JackpotSource {
+    #[default]
+    AllBankPayments,
+    TaxesOnly,
+    // Only the seed amount, payments to the Bank stay in the Bank
+    SeedOnly,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JackpotRules {
+    pub source: JackpotSource,
+    // Put in by the Bank when the game starts and again after every claim
+    pub seed_amount: BigDecimal,
+    // Skip the "must stand on Parada Libre" requirement
+    pub claim_anywhere: bool,
+}
+
+impl JackpotRules {
+    /// Share of a payment to the Bank that goes into the jackpot
+    pub fn contribution(&self, category: TransactionCategory, amount: &BigDecimal) -> BigDecimal {
+        match self.source {
+            JackpotSource::AllBankPayments => amount.clone(),
+            JackpotSource::TaxesOnly if category == TransactionCategory::Tax => amount.clone(),
+            JackpotSource::TaxesOnly | JackpotSource::SeedOnly => BigDecimal::from(0),
+        }
+    }
+}
+
+// Per-game rule settings chosen by the host (stored as JSONB)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HouseRules {
+    pub mode: GameMode,
+    pub time_limit_minutes: Option<i64>,
+    // Let the current round finish once the deadline passes
+    pub finish_round_at_deadline: bool,
+    pub jackpot: JackpotRules,
+    pub cash_mode: CashMode,
+    pub bank: BankRules,
+    pub auction: AuctionRules,
+}
+
+// Auction timing: every auction runs against a deadline, pushed back by late bids
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuctionRules {
+    pub duration_seconds: i64,
+    // A bid with less than this left on the clock...
+    pub snipe_window_seconds: i64,
+    // ...puts this much time back on it
+    pub extension_seconds: i64,
+    // Each bid has to beat the current one by at least this much
+    pub min_increment: BigDecimal,
+    // Players with less cash than this sit the auction out
+    pub min_balance: BigDecimal,
+    // Dutch auctions: the asking price drops by this much every `dutch_step_seconds`
+    pub dutch_step: BigDecimal,
+    pub dutch_step_seconds: i64,
+}
+
+impl Default for AuctionRules {
+    fn default() -> Self {
+        Self {
+            duration_seconds: 60,
+            snipe_window_seconds: 10,
+            extension_seconds: 10,
+            min_increment: BigDecimal::from(1),
+            min_balance: BigDecimal::from(10),
+            dutch_step: BigDecimal::from(10),
+            dutch_step_seconds: 3,
+        }
+    }
+}
+
+impl AuctionRules {
+    /// Dutch asking price at `now`: one step down per interval since the start, never below `floor`
+    pub fn dutch_price(&self, start_price: &BigDecimal, started_at: OffsetDateTime, now: OffsetDateTime, floor: &BigDecimal) -> BigDecimal {
+        let steps = (now - started_at).whole_seconds().max(0) / self.dutch_step_seconds.max(1);
+        let price = start_price - &self.dutch_step * BigDecimal::from(steps);
+        if price < *floor { floor.clone() } else { price }
+    }
+
+    /// New deadline when a bid lands inside the anti-sniping window
+    pub fn extended_deadline(&self, ends_at: OffsetDateTime, now: OffsetDateTime) -> Option<OffsetDateTime> {
+        let extended = now + time::Duration::seconds(self.extension_seconds);
+        (ends_at - now < time::Duration::seconds(self.snipe_window_seconds) && extended > ends_at).then_some(extended)
+    }
+}
+
+// What happens when a payout needs more than the Bank holds
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BankEmptyPolicy {
+    // Keeps paying, the reserve just goes negative
+    #[default]
+    Unlimited,
+    // Pays what it has and owes the rest, settled as money comes back in
+    Iou,
+    // The payout goes through, then the game ends ranked by net worth
+    EndGame,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BankRules {
+    // Cash in the box when the game starts, players' opening funds included. None = infinite Bank
+    pub reserve: Option<BigDecimal>,
+    pub when_empty: BankEmptyPolicy,
+}
+
+// How participants' money is tracked
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CashMode {
+    #[default]
+    Balance,
+    // Physical bills per participant, so effects like "todos los de 50" can target a denomination
+    Denominations,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct GameSession {
@@ -64,6 +223,32 @@ pub struct GameSession {
     pub ended_at: Option<OffsetDateTime>,
     pub current_turn_user_id: Option<Uuid>,
     pub turn_order: Option<sqlx::types::Json<Vec<Uuid>>>,
+    #[sqlx(default)]
+    pub winner_participant_id: Option<Uuid>,
+    #[sqlx(default)]
+    pub win_reason: Option<String>,
+    #[sqlx(default)]
+    pub house_rules: sqlx::types::Json<HouseRules>,
+    #[sqlx(default)]
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub deadline_at: Option<OffsetDateTime>,
+    #[sqlx(default)]
+    pub rematch_game_id: Option<Uuid>,
+    // Physical play: who runs the Bank. None = the host
+    #[sqlx(default)]
+    pub banker_user_id: Option<Uuid>,
+    // Cash left in the Bank; None while the Bank is infinite (see HouseRules::bank)
+    #[sqlx(default)]
+    pub bank_balance: Option<BigDecimal>,
+    // Bumped on every update; writes based on an older version are rejected
+    #[sqlx(default)]
+    pub version: i32,
+}
+
+impl GameSession {
+    pub fn banker_id(&self) -> Uuid {
+        self.banker_user_id.unwrap_or(self.host_user_id)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -86,6 +271,12 @@ pub struct GameParticipant {
     #[sqlx(default)]
     pub position: i32,
     pub joined_at: Option<OffsetDateTime>,
+    #[sqlx(default)]
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub bankrupt_at: Option<OffsetDateTime>,
+    // Bills held in denomination mode; None while the game tracks plain balances
+    #[sqlx(default)]
+    pub bills: Option<sqlx::types::Json<Bills>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -99,6 +290,11 @@ pub struct ParticipantDetail {
     pub username: String,
     pub first_name: String,
     pub last_name: String,
+    #[sqlx(default)]
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub bankrupt_at: Option<OffsetDateTime>,
+    #[sqlx(default)]
+    pub bills: Option<sqlx::types::Json<Bills>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -111,6 +307,368 @@ pub struct Transaction {
     pub description: Option<String>,
     #[serde(with = "time::serde::rfc3339::option")]
     pub created_at: Option<OffsetDateTime>,
+    #[sqlx(try_from = "String")]
+    pub category: TransactionCategory,
+    #[sqlx(default)]
+    pub property_id: Option<Uuid>,
+    #[sqlx(default)]
+    pub card_id: Option<Uuid>,
+    #[sqlx(default)]
+    pub trade_id: Option<Uuid>,
+    #[sqlx(default)]
+    pub auction_id: Option<Uuid>,
+    #[sqlx(default)]
+    pub loan_id: Option<Uuid>,
+    // Shared by all rows booked as one grouped operation (e.g. "pay everyone")
+    #[sqlx(default)]
+    pub group_id: Option<Uuid>,
+    // Change applied to the Free Parking jackpot (+ paid into it, - paid out of it)
+    #[sqlx(default)]
+    pub jackpot_delta: BigDecimal,
+    // Set on compensating entries: the transaction this one undoes
+    #[sqlx(default)]
+    pub reverses_transaction_id: Option<Uuid>,
+    // Set on the original once it has been undone
+    #[sqlx(default)]
+    pub reversed_by_transaction_id: Option<Uuid>,
+}
+
+impl Transaction {
+    /// Change to the Bank's reserve. Whatever reaches the Bank minus its jackpot share comes in;
+    /// payouts and jackpot seeds go out; jackpot claims never touch the Bank.
+    pub fn bank_delta(&self) -> BigDecimal {
+        match (self.from_participant_id, self.to_participant_id) {
+            (Some(_), None) => &self.amount - &self.jackpot_delta,
+            (None, Some(_)) => -(&self.amount + &self.jackpot_delta),
+            (None, None) => -self.jackpot_delta.clone(),
+            (Some(_), Some(_)) => BigDecimal::from(0),
+        }
+    }
+
+    /// A row paid out by the Bank (to the jackpot when `to` is None)
+    pub fn from_bank(game_id: Uuid, to: Option<Uuid>, amount: BigDecimal, description: &str) -> Self {
+        Transaction {
+            id: Uuid::new_v4(),
+            game_id,
+            from_participant_id: None,
+            to_participant_id: to,
+            amount,
+            description: Some(description.to_string()),
+            created_at: Some(OffsetDateTime::now_utc()),
+            category: TransactionCategory::Manual,
+            property_id: None,
+            card_id: None,
+            trade_id: None,
+            auction_id: None,
+            loan_id: None,
+            group_id: None,
+            jackpot_delta: BigDecimal::from(0),
+            reverses_transaction_id: None,
+            reversed_by_transaction_id: None,
+        }
+    }
+}
+
+/// Cut Bank payouts down to what the reserve still holds, in booking order.
+/// Returns (transaction, participant, amount owed) for every payout that came up short.
+pub fn short_pay(rows: &mut [Transaction], mut available: BigDecimal) -> Vec<(Uuid, Uuid, BigDecimal)> {
+    let mut shortfalls = Vec::new();
+    for row in rows.iter_mut() {
+        if let (None, Some(to)) = (row.from_participant_id, row.to_participant_id) {
+            let after = &available + row.bank_delta();
+            if after < 0 {
+                let shortfall = (-after).min(row.amount.clone());
+                row.amount -= &shortfall;
+                shortfalls.push((row.id, to, shortfall));
+            }
+        }
+        available += row.bank_delta();
+    }
+    shortfalls
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionCategory {
+    Salary, // Passing Go and the starting cash
+    Rent,
+    Tax,
+    Purchase,
+    Building,
+    Mortgage,
+    Trade,
+    Auction,
+    Card,
+    Jackpot,
+    #[default]
+    Manual,
+    Reversal,
+    Adjustment, // Reconciliation entry, books drift without moving money
+    Loan,       // Disbursements and repayments
+    Bankruptcy, // Assets seized from a bankrupt participant
+    Iou,        // The Bank settling what it owed after running dry
+}
+
+impl std::fmt::Display for TransactionCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            TransactionCategory::Salary => "salary",
+            TransactionCategory::Rent => "rent",
+            TransactionCategory::Tax => "tax",
+            TransactionCategory::Purchase => "purchase",
+            TransactionCategory::Building => "building",
+            TransactionCategory::Mortgage => "mortgage",
+            TransactionCategory::Trade => "trade",
+            TransactionCategory::Auction => "auction",
+            TransactionCategory::Card => "card",
+            TransactionCategory::Jackpot => "jackpot",
+            TransactionCategory::Manual => "manual",
+            TransactionCategory::Reversal => "reversal",
+            TransactionCategory::Adjustment => "adjustment",
+            TransactionCategory::Loan => "loan",
+            TransactionCategory::Bankruptcy => "bankruptcy",
+            TransactionCategory::Iou => "iou",
+        };
+        f.write_str(s)
+    }
+}
+
+// Decoding from the VARCHAR column
+impl TryFrom<String> for TransactionCategory {
+    type Error = serde_json::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        serde_json::from_value(serde_json::Value::String(value))
+    }
+}
+
+// What a transfer is for, and what it refers to
+#[derive(Debug, Clone, Default)]
+pub struct TransferDetails {
+    pub category: TransactionCategory,
+    pub description: Option<String>,
+    pub property_id: Option<Uuid>,
+    pub card_id: Option<Uuid>,
+    pub trade_id: Option<Uuid>,
+    pub auction_id: Option<Uuid>,
+    pub loan_id: Option<Uuid>,
+    pub group_id: Option<Uuid>,
+    // Denomination mode: the exact bills to hand over instead of letting the payer make change
+    pub bills: Option<Bills>,
+}
+
+impl TransferDetails {
+    pub fn new(category: TransactionCategory, description: impl Into<String>) -> Self {
+        Self { category, description: Some(description.into()), ..Default::default() }
+    }
+
+    pub fn property(mut self, property_id: Uuid) -> Self {
+        self.property_id = Some(property_id);
+        self
+    }
+
+    pub fn card(mut self, card_id: Uuid) -> Self {
+        self.card_id = Some(card_id);
+        self
+    }
+
+    pub fn trade(mut self, trade_id: Uuid) -> Self {
+        self.trade_id = Some(trade_id);
+        self
+    }
+
+    pub fn auction(mut self, auction_id: Uuid) -> Self {
+        self.auction_id = Some(auction_id);
+        self
+    }
+
+    pub fn loan(mut self, loan_id: Uuid) -> Self {
+        self.loan_id = Some(loan_id);
+        self
+    }
+
+    pub fn bills(mut self, bills: Bills) -> Self {
+        self.bills = Some(bills);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiTransferDirection {
+    PayEach,         // e.g. "Presidente": pay 50 to each
+    CollectFromEach, // e.g. "Cumpleaños": +10 from each
+}
+
+// Stored balance vs. the balance the transaction history adds up to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceDiscrepancy {
+    pub participant_id: Uuid,
+    pub user_id: Uuid,
+    pub expected: BigDecimal,
+    pub actual: BigDecimal,
+    pub difference: BigDecimal, // actual - expected
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub game_id: Uuid,
+    pub transactions_replayed: usize,
+    pub discrepancies: Vec<BalanceDiscrepancy>,
+    pub expected_jackpot: BigDecimal,
+    pub actual_jackpot: BigDecimal,
+    pub jackpot_difference: BigDecimal, // actual - expected
+    pub is_consistent: bool,
+}
+
+// Balances, jackpot and full history of a game, read at one moment
+#[derive(Debug, Clone)]
+pub struct LedgerSnapshot {
+    pub game_id: Uuid,
+    pub participants: Vec<GameParticipant>,
+    pub jackpot_balance: BigDecimal,
+    pub transactions: Vec<Transaction>,
+}
+
+// Derives the reconciliation entries for a snapshot
+pub type LedgerAdjustment = fn(&LedgerSnapshot) -> Vec<Transaction>;
+
+// Output format of the ledger export
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Jsonl,
+}
+
+// One line of the ledger export: a transaction or a logged roll, spin or card use, names resolved
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LedgerEntry {
+    pub kind: String, // transaction, dice_roll, roulette_spin, special_dice_roll, card_usage
+    pub id: Uuid,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub occurred_at: Option<OffsetDateTime>,
+    pub from_participant_id: Option<Uuid>,
+    pub to_participant_id: Option<Uuid>,
+    // Payer for transactions, the acting player otherwise
+    pub from_name: Option<String>,
+    pub to_name: Option<String>,
+    pub amount: Option<BigDecimal>,
+    pub category: Option<String>,
+    pub description: Option<String>,
+    // Filled in while exporting, in time order
+    #[sqlx(skip)]
+    pub from_balance_after: Option<BigDecimal>,
+    #[sqlx(skip)]
+    pub to_balance_after: Option<BigDecimal>,
+}
+
+// One change of the Free Parking jackpot, derived from the ledger
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JackpotHistoryEntry {
+    pub transaction_id: Uuid,
+    pub category: TransactionCategory,
+    pub description: Option<String>,
+    pub participant_id: Option<Uuid>, // Who paid in or claimed, if anyone
+    pub delta: BigDecimal,
+    pub balance_after: BigDecimal,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+}
+
+// What the banker needs at a glance while running the Bank at the table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BankerDashboard {
+    pub game_id: Uuid,
+    pub banker_user_id: Uuid,
+    pub pending_payment_requests: Vec<PaymentRequest>,
+    pub pending_bank_loans: Vec<Loan>,
+    pub bank_outflow_total: BigDecimal, // Paid out by the Bank
+    pub bank_inflow_total: BigDecimal,  // Paid into the Bank (including the jackpot share)
+    pub bank_outflow_by_category: std::collections::BTreeMap<String, BigDecimal>,
+    pub jackpot_balance: BigDecimal,
+}
+
+// One money movement inside a batch; El Banco rules may expand it into several rows
+#[derive(Debug, Clone)]
+pub struct TransferLeg {
+    pub from_participant_id: Option<Uuid>,
+    pub to_participant_id: Option<Uuid>,
+    pub amount: BigDecimal,
+    pub details: TransferDetails,
+}
+
+// Property or card ownership change committed together with the money legs of a batch, or the status
+// change of whatever the legs pay for, so the money never moves without it
+#[derive(Debug, Clone)]
+pub enum OwnershipChange {
+    // Bank -> participant; fails if someone already owns the property
+    Assign { property_id: Uuid, participant_id: Uuid },
+    // Participant -> participant, keeping buildings and mortgage; fails if `from` no longer owns it
+    Transfer { property_id: Uuid, from_participant_id: Uuid, to_participant_id: Uuid },
+    // Transfer by trade: also fails if `from` has buildings anywhere in the property's color group,
+    // and bumps the version of the whole group so a build that read it beforehand conflicts
+    TradeProperty { property_id: Uuid, from_participant_id: Uuid, to_participant_id: Uuid },
+    // Participant -> Bank, buildings and mortgage are cleared
+    Release { property_id: Uuid, from_participant_id: Uuid },
+    // New buildings or mortgage state of an owned property; fails with a conflict if its version moved on
+    Update(ParticipantProperty),
+    // A house or hotel won at auction, used up by a later build
+    GrantBuildingRight { participant_id: Uuid, kind: AuctionLotType, auction_id: Uuid },
+    // Uses up a house or hotel won at auction; fails with a conflict if it was used meanwhile
+    UseBuildingRight { right_id: Uuid },
+    // A participant_cards entry changing hands; fails if `from` no longer holds it
+    TransferCard { inventory_id: Uuid, from_participant_id: Uuid, to_participant_id: Uuid },
+    // Bóveda market slot -> participant inventory; fails if the slot no longer holds that card
+    TakeBovedaCard { slot_index: i32, card_id: Uuid, participant_id: Uuid },
+    // PENDING -> APPROVED, linked to the batch's first leg; fails with a conflict if it was resolved meanwhile
+    ApprovePaymentRequest { request_id: Uuid, resolved_by_user_id: Uuid },
+    // PENDING -> ACCEPTED; fails with a conflict if the trade was resolved meanwhile
+    AcceptTrade { trade_id: Uuid },
+    // New balance or status of a loan; fails with a conflict if its status or version moved on
+    UpdateLoan { loan: Loan, expected_status: String },
+    // ACTIVE -> FINISHED at the winning bid; fails with a conflict if the auction moved on since it was read
+    CloseAuction { auction: Auction },
+    // Marks the participant bankrupt, defaults the loans they owe and drops them from the turn order;
+    // fails with a conflict if they already went bankrupt
+    DeclareBankrupt { participant_id: Uuid },
+}
+
+// Everything in a batch commits in one Postgres transaction, or nothing does
+#[derive(Debug, Clone)]
+pub struct TransferBatch {
+    pub game_id: Uuid,
+    pub legs: Vec<Transaction>,
+    pub ownership: Vec<OwnershipChange>,
+    // Explicit bills for some legs, keyed by transaction id
+    pub bills: std::collections::HashMap<Uuid, Bills>,
+    // Finite Bank under the IOU policy: payouts are cut to the locked reserve and owed as IOUs,
+    // and money coming in pays back outstanding IOUs, all in the batch's transaction
+    pub bank_ious: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BatchResult {
+    pub transactions: Vec<Transaction>,
+    // IOUs written for short payouts or paid back, and the repayments themselves
+    pub ious: Vec<BankIou>,
+    pub repayments: Vec<Transaction>,
+    pub ownership: Vec<ParticipantProperty>,
+    // Inventory entries moved by TransferCard or created by TakeBovedaCard changes
+    pub cards: Vec<Uuid>,
+    // Loans written by UpdateLoan or defaulted by DeclareBankrupt
+    pub loans: Vec<Loan>,
+}
+
+// Optional filters for listing a game's transactions
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TransactionFilter {
+    pub category: Option<TransactionCategory>,
+    pub property_id: Option<Uuid>,
+    pub card_id: Option<Uuid>,
+    pub trade_id: Option<Uuid>,
+    pub auction_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -297,6 +855,8 @@ pub struct ParticipantProperty {
     pub house_count: i32,
     #[sqlx(default)]
     pub hotel_count: i32,
+    #[sqlx(default)]
+    pub version: i32,
     // Joined fields (optional)
     #[sqlx(default)]
     pub property_name: Option<String>,
@@ -308,14 +868,297 @@ pub struct ParticipantProperty {
 pub struct Auction {
     pub id: Uuid,
     pub game_id: Uuid,
-    pub property_id: Uuid,
+    // What is being sold; the lot fields below that apply depend on it
+    #[sqlx(try_from = "String")]
+    pub lot_type: AuctionLotType,
+    pub property_id: Option<Uuid>,
+    // Participant card and Bóveda slot lots
+    #[sqlx(default)]
+    pub card_id: Option<Uuid>,
+    // Participant card lots: the inventory entry up for sale and who gets the money
+    #[sqlx(default)]
+    pub inventory_id: Option<Uuid>,
+    #[sqlx(default)]
+    pub seller_participant_id: Option<Uuid>,
+    // Bóveda slot lots
+    #[sqlx(default)]
+    pub slot_index: Option<i32>,
+    // Open: the standing bid. Sealed: the opening price until close. Dutch: the current asking price
     pub current_bid: BigDecimal,
     pub highest_bidder_id: Option<Uuid>,
     pub status: String,
+    // Bumped on every update; writes based on an older version are rejected
+    #[sqlx(default)]
+    pub version: i32,
     #[serde(with = "time::serde::rfc3339::option")]
     pub created_at: Option<OffsetDateTime>,
     #[serde(with = "time::serde::rfc3339::option")]
     pub ends_at: Option<OffsetDateTime>,
+    #[sqlx(try_from = "String")]
+    pub format: AuctionFormat,
+    // Dutch only: the asking price the clock counts down from
+    #[sqlx(default)]
+    pub start_price: Option<BigDecimal>,
+    // Sealed only: every bid, filled in once the auction closes
+    #[sqlx(skip)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revealed_bids: Option<Vec<AuctionBid>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuctionFormat {
+    // Ascending, everyone sees the standing bid
+    #[default]
+    Open,
+    // One hidden bid each, highest wins and pays their own bid
+    Sealed,
+    // The asking price drops on a clock until someone takes it
+    Dutch,
+}
+
+impl std::fmt::Display for AuctionFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            AuctionFormat::Open => "open",
+            AuctionFormat::Sealed => "sealed",
+            AuctionFormat::Dutch => "dutch",
+        };
+        f.write_str(s)
+    }
+}
+
+// Decoding from the VARCHAR column
+impl TryFrom<String> for AuctionFormat {
+    type Error = serde_json::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        serde_json::from_value(serde_json::Value::String(value))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuctionLotType {
+    // An unowned property, sold by the Bank
+    #[default]
+    Property,
+    // A card from a player's inventory, sold by that player
+    ParticipantCard,
+    // The card in a Bóveda market slot, sold by the Bank
+    BovedaSlot,
+    // The right to place one house or hotel, for when buildings run short
+    House,
+    Hotel,
+}
+
+impl std::fmt::Display for AuctionLotType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            AuctionLotType::Property => "property",
+            AuctionLotType::ParticipantCard => "participant_card",
+            AuctionLotType::BovedaSlot => "boveda_slot",
+            AuctionLotType::House => "house",
+            AuctionLotType::Hotel => "hotel",
+        };
+        f.write_str(s)
+    }
+}
+
+// Decoding from the VARCHAR column
+impl TryFrom<String> for AuctionLotType {
+    type Error = serde_json::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        serde_json::from_value(serde_json::Value::String(value))
+    }
+}
+
+// A house or hotel won at auction, used up by the next matching build
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BuildingRight {
+    pub id: Uuid,
+    pub game_id: Uuid,
+    pub participant_id: Uuid,
+    #[sqlx(try_from = "String")]
+    pub kind: AuctionLotType,
+    pub auction_id: Option<Uuid>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub used_at: Option<OffsetDateTime>,
+}
+
+// Auction request as sent by the client
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewAuction {
+    #[serde(default)]
+    pub lot_type: AuctionLotType,
+    pub property_id: Option<Uuid>,
+    // Participant card lots: one of the requester's own cards
+    pub inventory_id: Option<Uuid>,
+    // Bóveda slot lots
+    pub slot_index: Option<i32>,
+    // Defaults to the game's auction house rule
+    pub duration_seconds: Option<i64>,
+    #[serde(default)]
+    pub format: AuctionFormat,
+    // Dutch only; defaults to twice the property's price or the Bóveda card's cost
+    pub start_price: Option<BigDecimal>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BidAction {
+    Bid,
+    // Out of the bidding, any standing bid still counts
+    Pass,
+    // Out of the bidding and the participant's bids are taken back
+    Withdraw,
+}
+
+impl std::fmt::Display for BidAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            BidAction::Bid => "bid",
+            BidAction::Pass => "pass",
+            BidAction::Withdraw => "withdraw",
+        };
+        f.write_str(s)
+    }
+}
+
+// Decoding from the VARCHAR column
+impl TryFrom<String> for BidAction {
+    type Error = serde_json::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        serde_json::from_value(serde_json::Value::String(value))
+    }
+}
+
+// One entry of an auction's bidding history
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuctionBid {
+    pub id: Uuid,
+    pub auction_id: Uuid,
+    pub game_id: Uuid,
+    pub participant_id: Uuid,
+    #[sqlx(try_from = "String")]
+    pub action: BidAction,
+    // None for passes and withdrawals
+    pub amount: Option<BigDecimal>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InterestPeriod {
+    Lap,  // Interest is added each time the borrower passes Go
+    Turn, // Interest is added at the end of each of the borrower's turns
+}
+
+impl std::fmt::Display for InterestPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            InterestPeriod::Lap => "lap",
+            InterestPeriod::Turn => "turn",
+        })
+    }
+}
+
+impl TryFrom<String> for InterestPeriod {
+    type Error = serde_json::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        serde_json::from_value(serde_json::Value::String(value))
+    }
+}
+
+// Status: PENDING (requested), ACTIVE, REPAID, DEFAULTED, REJECTED
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Loan {
+    pub id: Uuid,
+    pub game_id: Uuid,
+    pub lender_participant_id: Option<Uuid>, // None = Bank
+    pub borrower_participant_id: Uuid,
+    pub principal: BigDecimal,
+    pub interest_percent: BigDecimal, // Simple interest on the principal, per period
+    #[sqlx(try_from = "String")]
+    pub interest_period: InterestPeriod,
+    pub due_after_periods: Option<i32>, // None = open-ended
+    pub periods_elapsed: i32,
+    pub outstanding: BigDecimal, // Principal + accrued interest - repayments
+    pub status: String,
+    #[sqlx(default)]
+    pub version: i32,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub settled_at: Option<OffsetDateTime>,
+}
+
+// What the Bank still owes a participant after short-paying them (IOU reserve policy)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BankIou {
+    pub id: Uuid,
+    pub game_id: Uuid,
+    pub participant_id: Uuid,
+    pub amount: BigDecimal,
+    pub outstanding: BigDecimal,
+    // The payout that came up short
+    pub transaction_id: Option<Uuid>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub settled_at: Option<OffsetDateTime>,
+}
+
+// Loan request as sent by the borrower
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewLoan {
+    pub lender_participant_id: Option<Uuid>,
+    pub principal: BigDecimal,
+    pub interest_percent: BigDecimal,
+    pub interest_period: InterestPeriod,
+    pub due_after_periods: Option<i32>,
+}
+
+// Money owed to the requester, moved only once the payer (or the host) approves.
+// Status: PENDING, APPROVED, REJECTED, CANCELLED
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PaymentRequest {
+    pub id: Uuid,
+    pub game_id: Uuid,
+    pub payee_participant_id: Option<Uuid>, // None = owed to the Bank, raised by the banker
+    pub payer_participant_id: Uuid,
+    pub amount: BigDecimal,
+    #[sqlx(try_from = "String")]
+    pub category: TransactionCategory,
+    pub description: Option<String>,
+    pub property_id: Option<Uuid>,
+    pub status: String,
+    pub requested_by_user_id: Uuid,
+    pub resolved_by_user_id: Option<Uuid>,
+    pub transaction_id: Option<Uuid>, // Set once approved and paid
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub resolved_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewPaymentRequest {
+    pub payer_participant_id: Uuid,
+    // Banker only: the money is owed to the Bank rather than to the caller
+    #[serde(default)]
+    pub to_bank: bool,
+    pub amount: BigDecimal,
+    #[serde(default)]
+    pub category: TransactionCategory,
+    pub description: Option<String>,
+    pub property_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -334,3 +1177,54 @@ pub struct Trade {
     #[serde(with = "time::serde::rfc3339::option")]
     pub created_at: Option<OffsetDateTime>,
 }
+
+// Final ranking of a participant, stored when the game ends
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Standing {
+    pub game_id: Uuid,
+    pub participant_id: Uuid,
+    pub user_id: Uuid,
+    pub rank: i32,
+    pub cash: BigDecimal,
+    pub property_value: BigDecimal,
+    pub building_value: BigDecimal,
+    pub loans_receivable: BigDecimal,
+    pub loans_payable: BigDecimal,
+    pub net_worth: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameResult {
+    pub game: GameSession,
+    pub standings: Vec<Standing>,
+}
+
+// Full game state sent over the WebSocket on connect (or when a resume gap can't be filled)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameStateSnapshot {
+    pub game: GameSession,
+    pub participants: Vec<ParticipantDetail>,
+    pub ownership: Vec<ParticipantProperty>,
+    pub market: Vec<GameBovedaMarket>,
+    pub active_auction: Option<Auction>,
+    pub pending_trades: Vec<Trade>,
+    pub jackpot_balance: BigDecimal,
+    pub last_seq: u64,
+}
+
+// First response to a request sent with an Idempotency-Key, replayed to retries
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct IdempotencyRecord {
+    pub game_id: Uuid,
+    pub idempotency_key: String,
+    pub user_id: Uuid,
+    pub endpoint: String, // "POST /games/:id/roll" as requested
+    // SHA-256 of the request body, hex: a retry must send the same payload
+    pub request_hash: String,
+    // None while the first request is still running
+    pub status_code: Option<i32>,
+    pub content_type: Option<String>,
+    pub response_body: Option<Vec<u8>>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub created_at: Option<OffsetDateTime>,
+}
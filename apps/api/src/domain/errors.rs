@@ -0,0 +1,16 @@
+use std::fmt;
+
+/// A compare-and-swap update lost the race: the row changed since it was read.
+/// Handlers answer 409 so the client can reload and retry.
+#[derive(Debug)]
+pub struct ConcurrencyConflict {
+    pub entity: &'static str,
+}
+
+impl fmt::Display for ConcurrencyConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The {} was changed by someone else; reload and try again", self.entity)
+    }
+}
+
+impl std::error::Error for ConcurrencyConflict {}
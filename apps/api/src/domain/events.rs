@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use crate::domain::entities::{Transaction, DiceRoll, RouletteSpin, SpecialDiceRoll, Participant};
+use crate::domain::entities::{Transaction, DiceRoll, RouletteSpin, SpecialDiceRoll, Participant, GameStateSnapshot};
 use uuid::Uuid;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -13,9 +13,26 @@ pub enum GameEvent {
     GameUpdated { id: Uuid, status: String }, // For status changes
     MarketUpdated { game_id: Uuid },
     AuctionUpdated(crate::domain::entities::Auction),
+    AuctionCountdown { game_id: Uuid, auction_id: Uuid, remaining_seconds: i64 },
     TradeUpdated(crate::domain::entities::Trade),
     TurnUpdated { game_id: Uuid, current_turn_user_id: Uuid },
     PropertyUpdated(crate::domain::entities::ParticipantProperty),
+    GameCountdown { game_id: Uuid, remaining_seconds: i64, finishing_round: bool },
+    GameEnded { game_id: Uuid, winner_participant_id: Option<Uuid>, reason: String, standings: Vec<crate::domain::entities::Standing> },
+    RematchCreated { game_id: Uuid, new_game_id: Uuid, code: String },
+    TransactionsGrouped { game_id: Uuid, group_id: Uuid, transactions: Vec<Transaction> },
+    TransactionReversed { game_id: Uuid, original: Box<Transaction>, reversal: Box<Transaction> },
+    LoanUpdated(crate::domain::entities::Loan),
+    ParticipantBankrupt { game_id: Uuid, participant_id: Uuid, creditor_participant_id: Option<Uuid> },
+    PaymentRequestUpdated(crate::domain::entities::PaymentRequest),
+    BankerChanged { game_id: Uuid, banker_user_id: Uuid },
+    JackpotUpdated { game_id: Uuid, balance: bigdecimal::BigDecimal, delta: bigdecimal::BigDecimal },
+    CashModeChanged { game_id: Uuid, cash_mode: crate::domain::entities::CashMode },
+    BankUpdated { game_id: Uuid, balance: bigdecimal::BigDecimal, delta: bigdecimal::BigDecimal },
+    BankIouUpdated(crate::domain::entities::BankIou),
+    GameDeleted { game_id: Uuid },
+    // Only sent directly to a socket on connect, never broadcast
+    GameStateSnapshot(Box<GameStateSnapshot>),
 }
 
 impl GameEvent {
@@ -29,9 +46,34 @@ impl GameEvent {
             GameEvent::GameUpdated { id, .. } => *id,
             GameEvent::MarketUpdated { game_id } => *game_id,
             GameEvent::AuctionUpdated(a) => a.game_id,
+            GameEvent::AuctionCountdown { game_id, .. } => *game_id,
             GameEvent::TradeUpdated(t) => t.game_id,
             GameEvent::TurnUpdated { game_id, .. } => *game_id,
             GameEvent::PropertyUpdated(p) => p.game_id,
+            GameEvent::GameCountdown { game_id, .. } => *game_id,
+            GameEvent::GameEnded { game_id, .. } => *game_id,
+            GameEvent::RematchCreated { game_id, .. } => *game_id,
+            GameEvent::TransactionReversed { game_id, .. } => *game_id,
+            GameEvent::TransactionsGrouped { game_id, .. } => *game_id,
+            GameEvent::LoanUpdated(l) => l.game_id,
+            GameEvent::ParticipantBankrupt { game_id, .. } => *game_id,
+            GameEvent::PaymentRequestUpdated(r) => r.game_id,
+            GameEvent::BankerChanged { game_id, .. } => *game_id,
+            GameEvent::JackpotUpdated { game_id, .. } => *game_id,
+            GameEvent::CashModeChanged { game_id, .. } => *game_id,
+            GameEvent::BankUpdated { game_id, .. } => *game_id,
+            GameEvent::BankIouUpdated(i) => i.game_id,
+            GameEvent::GameDeleted { game_id } => *game_id,
+            GameEvent::GameStateSnapshot(s) => s.game.id,
         }
     }
 }
+
+/// A broadcast event stamped with its per-game sequence number.
+/// Serializes as the plain event plus a `seq` field, so existing clients keep working.
+#[derive(Clone, Debug, Serialize)]
+pub struct SequencedEvent {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub event: GameEvent,
+}
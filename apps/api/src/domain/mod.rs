@@ -1,3 +1,5 @@
 pub mod entities;
 pub mod events;
 pub mod repositories;
+pub mod cash;
+pub mod errors;
@@ -23,6 +23,14 @@ pub trait GameRepository {
     async fn find_played_by_user(&self, user_id: Uuid) -> Result<Vec<GameSession>, anyhow::Error>;
     async fn update(&self, game: GameSession) -> Result<GameSession, anyhow::Error>;
     async fn delete(&self, id: Uuid) -> Result<(), anyhow::Error>;
+    async fn find_active_with_deadline(&self) -> Result<Vec<GameSession>, anyhow::Error>;
+    /// Open (Some) or drop (None) the finite Bank reserve; transfers keep it up to date afterwards
+    async fn set_bank_balance(&self, game_id: Uuid, balance: Option<bigdecimal::BigDecimal>) -> Result<(), anyhow::Error>;
+    /// Store the game's new cash mode and convert every wallet to it in one transaction; conflict if the game changed
+    async fn set_cash_mode(&self, game: GameSession) -> Result<GameSession, anyhow::Error>;
+    /// Create the follow-up game with the players (in the given order) already funded, and link the finished
+    /// game to it, in one transaction; conflict if the finished game changed or already has a rematch
+    async fn create_rematch(&self, finished: GameSession, game: GameSession, user_ids: Vec<Uuid>, funding: bigdecimal::BigDecimal) -> Result<GameSession, anyhow::Error>;
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -38,13 +46,20 @@ pub trait ParticipantRepository {
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait TransactionRepository {
-    async fn find_by_game(&self, game_id: Uuid) -> Result<Vec<Transaction>, anyhow::Error>;
-    async fn delete(&self, id: Uuid) -> Result<(), anyhow::Error>;
-    async fn execute_transfer(
-        &self, 
-        transaction: Transaction
-    ) -> Result<Transaction, anyhow::Error>;
+    async fn find_by_game(&self, game_id: Uuid, filter: crate::domain::entities::TransactionFilter) -> Result<Vec<Transaction>, anyhow::Error>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Transaction>, anyhow::Error>;
+    /// Undo `original_id` with a compensating entry; returns (updated original, reversal)
+    async fn reverse(&self, original_id: Uuid, description: Option<String>) -> Result<(Transaction, Transaction), anyhow::Error>;
+    /// Balances, jackpot and history read in one transaction with the balances locked, so they agree with each other
+    async fn ledger_snapshot(&self, game_id: Uuid) -> Result<crate::domain::entities::LedgerSnapshot, anyhow::Error>;
+    /// Book the entries `adjust` derives from a snapshot without touching balances or the jackpot (reconciliation
+    /// adjustments). The balances stay locked from the read to the commit, so no payment lands in between.
+    async fn repair_ledger(&self, game_id: Uuid, adjust: crate::domain::entities::LedgerAdjustment) -> Result<Vec<Transaction>, anyhow::Error>;
     async fn claim_jackpot(&self, game_id: Uuid, user_id: Uuid) -> Result<Transaction, anyhow::Error>;
+    /// Apply all legs and ownership changes atomically
+    async fn execute_batch(&self, batch: crate::domain::entities::TransferBatch) -> Result<crate::domain::entities::BatchResult, anyhow::Error>;
+    /// Transactions, rolls, spins and card uses of a game in time order, streamed row by row
+    fn stream_ledger(&self, game_id: Uuid) -> futures::stream::BoxStream<'static, Result<crate::domain::entities::LedgerEntry, anyhow::Error>>;
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -83,12 +98,10 @@ pub trait PropertyRepository {
     // Ownership
     async fn find_ownership_by_game(&self, game_id: Uuid) -> Result<Vec<crate::domain::entities::ParticipantProperty>, anyhow::Error>;
     async fn find_participant_properties(&self, game_id: Uuid, participant_id: Uuid) -> Result<Vec<crate::domain::entities::ParticipantProperty>, anyhow::Error>;
-    async fn assign_property(&self, pp: crate::domain::entities::ParticipantProperty) -> Result<crate::domain::entities::ParticipantProperty, anyhow::Error>;
-    async fn update_property_ownership(&self, pp: crate::domain::entities::ParticipantProperty) -> Result<crate::domain::entities::ParticipantProperty, anyhow::Error>;
-    
-    // Helper to transfer (update participant_id)
-    async fn transfer_property(&self, game_id: Uuid, property_id: Uuid, new_participant_id: Uuid) -> Result<(), anyhow::Error>;
-    async fn delete_ownership(&self, game_id: Uuid, property_id: Uuid) -> Result<(), anyhow::Error>;
+
+    // Unused houses and hotels a participant won at auction, oldest first.
+    // Granted and used up inside transfer batches (OwnershipChange::GrantBuildingRight / UseBuildingRight).
+    async fn find_building_rights(&self, game_id: Uuid, participant_id: Uuid) -> Result<Vec<crate::domain::entities::BuildingRight>, anyhow::Error>;
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -98,8 +111,14 @@ pub trait AuctionRepository {
     #[allow(dead_code)]
     async fn find_by_game(&self, game_id: Uuid) -> Result<Vec<crate::domain::entities::Auction>, anyhow::Error>;
     async fn find_active_by_game(&self, game_id: Uuid) -> Result<Option<crate::domain::entities::Auction>, anyhow::Error>;
-    async fn update(&self, auction: crate::domain::entities::Auction) -> Result<crate::domain::entities::Auction, anyhow::Error>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<crate::domain::entities::Auction>, anyhow::Error>;
+    // Active auctions across all games, for the auction scheduler
+    async fn find_all_active(&self) -> Result<Vec<crate::domain::entities::Auction>, anyhow::Error>;
+    // Update only while still ACTIVE and at the version that was read; None when a close or another write got there first
+    async fn update_active(&self, auction: crate::domain::entities::Auction) -> Result<Option<crate::domain::entities::Auction>, anyhow::Error>;
+    async fn add_bid(&self, bid: crate::domain::entities::AuctionBid) -> Result<crate::domain::entities::AuctionBid, anyhow::Error>;
+    // Oldest first
+    async fn find_bids(&self, auction_id: Uuid) -> Result<Vec<crate::domain::entities::AuctionBid>, anyhow::Error>;
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -109,5 +128,55 @@ pub trait TradeRepository {
     #[allow(dead_code)]
     async fn find_by_game(&self, game_id: Uuid) -> Result<Vec<crate::domain::entities::Trade>, anyhow::Error>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<crate::domain::entities::Trade>, anyhow::Error>;
-    async fn update(&self, trade: crate::domain::entities::Trade) -> Result<crate::domain::entities::Trade, anyhow::Error>;
+    // Compare-and-set on status: None when the trade is no longer in `expected_status`
+    async fn update(&self, trade: crate::domain::entities::Trade, expected_status: &str) -> Result<Option<crate::domain::entities::Trade>, anyhow::Error>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait StandingsRepository {
+    /// Mark the game finished and freeze its standings in one transaction; conflict if the game changed or already finished
+    async fn finish_game(&self, game: GameSession, standings: Vec<crate::domain::entities::Standing>) -> Result<(GameSession, Vec<crate::domain::entities::Standing>), anyhow::Error>;
+    async fn find_by_game(&self, game_id: Uuid) -> Result<Vec<crate::domain::entities::Standing>, anyhow::Error>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait LoanRepository {
+    async fn create(&self, loan: crate::domain::entities::Loan) -> Result<crate::domain::entities::Loan, anyhow::Error>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<crate::domain::entities::Loan>, anyhow::Error>;
+    async fn find_by_game(&self, game_id: Uuid) -> Result<Vec<crate::domain::entities::Loan>, anyhow::Error>;
+    // Active loans the participant owes
+    async fn find_active_by_borrower(&self, game_id: Uuid, participant_id: Uuid) -> Result<Vec<crate::domain::entities::Loan>, anyhow::Error>;
+    // Compare-and-set on status and version: None when the loan changed since it was read
+    async fn update(&self, loan: crate::domain::entities::Loan, expected_status: &str) -> Result<Option<crate::domain::entities::Loan>, anyhow::Error>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PaymentRequestRepository {
+    async fn create(&self, request: crate::domain::entities::PaymentRequest) -> Result<crate::domain::entities::PaymentRequest, anyhow::Error>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<crate::domain::entities::PaymentRequest>, anyhow::Error>;
+    async fn find_by_game(&self, game_id: Uuid) -> Result<Vec<crate::domain::entities::PaymentRequest>, anyhow::Error>;
+    // Compare-and-set on status: None when the request is no longer in `expected_status`
+    async fn update(&self, request: crate::domain::entities::PaymentRequest, expected_status: &str) -> Result<Option<crate::domain::entities::PaymentRequest>, anyhow::Error>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait BankIouRepository {
+    // Written and paid back inside transfer batches (TransferBatch::bank_ious)
+    async fn find_by_game(&self, game_id: Uuid) -> Result<Vec<crate::domain::entities::BankIou>, anyhow::Error>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait IdempotencyRepository {
+    /// Store the record unless the key is already taken for this game; true if it was stored.
+    /// An unanswered claim of the same request made before `stale_before` is taken over.
+    async fn claim(&self, record: crate::domain::entities::IdempotencyRecord, stale_before: time::OffsetDateTime) -> Result<bool, anyhow::Error>;
+    async fn find(&self, game_id: Uuid, key: &str) -> Result<Option<crate::domain::entities::IdempotencyRecord>, anyhow::Error>;
+    async fn complete(&self, game_id: Uuid, key: &str, status_code: i32, content_type: Option<String>, body: Vec<u8>) -> Result<(), anyhow::Error>;
+    /// Forget a claim so the request can run again (the first attempt lost a race and rolled back)
+    async fn release(&self, game_id: Uuid, key: &str) -> Result<(), anyhow::Error>;
 }
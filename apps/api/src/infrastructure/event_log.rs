@@ -0,0 +1,197 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+use crate::domain::events::{GameEvent, SequencedEvent};
+
+// How long a finished game's history stays around for late reconnects (and the rematch announcement)
+const FINISHED_RETENTION: Duration = Duration::from_secs(15 * 60);
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+// In-memory, per-game history of recent events so reconnecting sockets can catch up.
+// Sequence numbers start at 1 for every game and reset when the server restarts.
+// Deleted games are dropped right away, finished ones after FINISHED_RETENTION.
+pub struct EventLog {
+    capacity: usize,
+    games: Mutex<HashMap<Uuid, GameLog>>,
+    sequenced_tx: broadcast::Sender<SequencedEvent>,
+}
+
+#[derive(Default)]
+struct GameLog {
+    last_seq: u64,
+    events: VecDeque<SequencedEvent>,
+    ended_at: Option<Instant>,
+}
+
+impl EventLog {
+    pub fn new(capacity: usize) -> Self {
+        let (sequenced_tx, _rx) = broadcast::channel(100);
+        Self { capacity, games: Mutex::new(HashMap::new()), sequenced_tx }
+    }
+
+    // Consume the raw broadcast channel and re-publish every event with a sequence number
+    pub fn spawn(self: &Arc<Self>, mut rx: broadcast::Receiver<GameEvent>) {
+        let log = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => {
+                        log.record(event);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Event log lagged behind, {} events were not sequenced", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        let log = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Some(ended_before) = Instant::now().checked_sub(FINISHED_RETENTION) {
+                    log.evict_ended(ended_before);
+                }
+            }
+        });
+    }
+
+    pub fn record(&self, event: GameEvent) -> SequencedEvent {
+        let mut games = self.games.lock().unwrap();
+        let log = games.entry(event.game_id()).or_default();
+
+        log.last_seq += 1;
+        let sequenced = SequencedEvent { seq: log.last_seq, event };
+
+        log.events.push_back(sequenced.clone());
+        while log.events.len() > self.capacity {
+            log.events.pop_front();
+        }
+
+        match &sequenced.event {
+            GameEvent::GameEnded { .. } => log.ended_at = Some(Instant::now()),
+            // Nothing more will happen in a deleted game
+            GameEvent::GameDeleted { game_id } => {
+                games.remove(game_id);
+            }
+            _ => {}
+        }
+
+        // Ignore errors if nobody is listening
+        let _ = self.sequenced_tx.send(sequenced.clone());
+        sequenced
+    }
+
+    // Drop the history of games that finished before `ended_before`
+    fn evict_ended(&self, ended_before: Instant) {
+        self.games.lock().unwrap().retain(|_, log| log.ended_at.is_none_or(|ended_at| ended_at >= ended_before));
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<SequencedEvent> {
+        self.sequenced_tx.subscribe()
+    }
+
+    pub fn last_seq(&self, game_id: Uuid) -> u64 {
+        self.games.lock().unwrap()
+            .get(&game_id)
+            .map(|log| log.last_seq)
+            .unwrap_or(0)
+    }
+
+    /// Events after `last_seq`, or None if the gap can no longer be filled from memory
+    /// (events were evicted, or the client's sequence comes from before a restart).
+    pub fn events_since(&self, game_id: Uuid, last_seq: u64) -> Option<Vec<SequencedEvent>> {
+        let games = self.games.lock().unwrap();
+        let Some(log) = games.get(&game_id) else {
+            return if last_seq == 0 { Some(Vec::new()) } else { None };
+        };
+
+        if last_seq > log.last_seq {
+            return None;
+        }
+
+        let oldest = log.events.front().map(|e| e.seq).unwrap_or(log.last_seq + 1);
+        if last_seq + 1 < oldest {
+            return None;
+        }
+
+        Some(log.events.iter().filter(|e| e.seq > last_seq).cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market_event(game_id: Uuid) -> GameEvent {
+        GameEvent::MarketUpdated { game_id }
+    }
+
+    #[test]
+    fn test_sequences_are_per_game() {
+        let log = EventLog::new(10);
+        let game_a = Uuid::new_v4();
+        let game_b = Uuid::new_v4();
+
+        assert_eq!(log.record(market_event(game_a)).seq, 1);
+        assert_eq!(log.record(market_event(game_a)).seq, 2);
+        assert_eq!(log.record(market_event(game_b)).seq, 1);
+
+        assert_eq!(log.last_seq(game_a), 2);
+        assert_eq!(log.last_seq(game_b), 1);
+        assert_eq!(log.last_seq(Uuid::new_v4()), 0);
+    }
+
+    #[test]
+    fn test_events_since_returns_only_the_gap() {
+        let log = EventLog::new(10);
+        let game_id = Uuid::new_v4();
+        for _ in 0..5 {
+            log.record(market_event(game_id));
+        }
+
+        let gap = log.events_since(game_id, 3).unwrap();
+        assert_eq!(gap.iter().map(|e| e.seq).collect::<Vec<_>>(), vec![4, 5]);
+        assert!(log.events_since(game_id, 5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_finished_and_deleted_games_are_evicted() {
+        let log = EventLog::new(10);
+        let (finished, deleted, running) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        log.record(market_event(finished));
+        log.record(GameEvent::GameEnded { game_id: finished, winner_participant_id: None, reason: "TIME_LIMIT".to_string(), standings: vec![] });
+        log.record(market_event(deleted));
+        log.record(GameEvent::GameDeleted { game_id: deleted });
+        log.record(market_event(running));
+
+        assert_eq!(log.last_seq(deleted), 0);
+        // Still there for late reconnects until the retention runs out
+        assert_eq!(log.last_seq(finished), 2);
+        log.evict_ended(Instant::now() + Duration::from_secs(1));
+        assert_eq!(log.last_seq(finished), 0);
+        assert_eq!(log.last_seq(running), 1);
+        // A reconnect with an old sequence gets a full snapshot instead
+        assert!(log.events_since(finished, 2).is_none());
+    }
+
+    #[test]
+    fn test_events_since_detects_unfillable_gaps() {
+        let log = EventLog::new(3);
+        let game_id = Uuid::new_v4();
+        for _ in 0..6 {
+            log.record(market_event(game_id));
+        }
+
+        // Only 4..=6 are retained
+        assert!(log.events_since(game_id, 2).is_none());
+        assert_eq!(log.events_since(game_id, 3).unwrap().len(), 3);
+        // Client is ahead of the server (e.g. after a restart)
+        assert!(log.events_since(game_id, 10).is_none());
+        assert!(log.events_since(Uuid::new_v4(), 1).is_none());
+    }
+}
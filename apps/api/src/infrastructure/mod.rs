@@ -1,2 +1,3 @@
 pub mod postgres;
 pub mod board_config;
+pub mod event_log;
@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use sqlx::PgPool;
 use uuid::Uuid;
 use crate::domain::{
-    entities::Auction,
+    entities::{Auction, AuctionBid},
     repositories::AuctionRepository,
 };
 
@@ -21,8 +21,9 @@ impl AuctionRepository for PostgresAuctionRepository {
     async fn create(&self, auction: Auction) -> Result<Auction, anyhow::Error> {
         let created = sqlx::query_as::<_, Auction>(
             r#"
-            INSERT INTO auctions (game_id, property_id, current_bid, highest_bidder_id, status, ends_at)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO auctions (game_id, property_id, current_bid, highest_bidder_id, status, ends_at, format, start_price,
+                                  lot_type, card_id, inventory_id, seller_participant_id, slot_index)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
             RETURNING *
             "#
         )
@@ -32,6 +33,13 @@ impl AuctionRepository for PostgresAuctionRepository {
         .bind(auction.highest_bidder_id)
         .bind(auction.status)
         .bind(auction.ends_at)
+        .bind(auction.format.to_string())
+        .bind(auction.start_price)
+        .bind(auction.lot_type.to_string())
+        .bind(auction.card_id)
+        .bind(auction.inventory_id)
+        .bind(auction.seller_participant_id)
+        .bind(auction.slot_index)
         .fetch_one(&self.pool)
         .await?;
         Ok(created)
@@ -57,12 +65,31 @@ impl AuctionRepository for PostgresAuctionRepository {
         Ok(auction)
     }
 
-    async fn update(&self, auction: Auction) -> Result<Auction, anyhow::Error> {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Auction>, anyhow::Error> {
+        let auction = sqlx::query_as::<_, Auction>(
+            "SELECT * FROM auctions WHERE id = $1"
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(auction)
+    }
+
+    async fn find_all_active(&self) -> Result<Vec<Auction>, anyhow::Error> {
+        let auctions = sqlx::query_as::<_, Auction>(
+            "SELECT * FROM auctions WHERE status = 'ACTIVE' ORDER BY ends_at ASC"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(auctions)
+    }
+
+    async fn update_active(&self, auction: Auction) -> Result<Option<Auction>, anyhow::Error> {
         let updated = sqlx::query_as::<_, Auction>(
             r#"
             UPDATE auctions
-            SET current_bid = $1, highest_bidder_id = $2, status = $3, ends_at = $4
-            WHERE id = $5
+            SET current_bid = $1, highest_bidder_id = $2, status = $3, ends_at = $4, version = version + 1
+            WHERE id = $5 AND status = 'ACTIVE' AND version = $6
             RETURNING *
             "#
         )
@@ -71,18 +98,37 @@ impl AuctionRepository for PostgresAuctionRepository {
         .bind(auction.status)
         .bind(auction.ends_at)
         .bind(auction.id)
-        .fetch_one(&self.pool)
+        .bind(auction.version)
+        .fetch_optional(&self.pool)
         .await?;
         Ok(updated)
     }
 
-    async fn find_by_id(&self, id: Uuid) -> Result<Option<Auction>, anyhow::Error> {
-        let auction = sqlx::query_as::<_, Auction>(
-            "SELECT * FROM auctions WHERE id = $1"
+    async fn add_bid(&self, bid: AuctionBid) -> Result<AuctionBid, anyhow::Error> {
+        let created = sqlx::query_as::<_, AuctionBid>(
+            r#"
+            INSERT INTO auction_bids (auction_id, game_id, participant_id, action, amount)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#
         )
-        .bind(id)
-        .fetch_optional(&self.pool)
+        .bind(bid.auction_id)
+        .bind(bid.game_id)
+        .bind(bid.participant_id)
+        .bind(bid.action.to_string())
+        .bind(bid.amount)
+        .fetch_one(&self.pool)
         .await?;
-        Ok(auction)
+        Ok(created)
+    }
+
+    async fn find_bids(&self, auction_id: Uuid) -> Result<Vec<AuctionBid>, anyhow::Error> {
+        let bids = sqlx::query_as::<_, AuctionBid>(
+            "SELECT * FROM auction_bids WHERE auction_id = $1 ORDER BY created_at ASC, id"
+        )
+        .bind(auction_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(bids)
     }
 }
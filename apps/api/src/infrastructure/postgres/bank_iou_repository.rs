@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::domain::{
+    entities::BankIou,
+    repositories::BankIouRepository,
+};
+
+pub struct PostgresBankIouRepository {
+    pool: PgPool,
+}
+
+impl PostgresBankIouRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BankIouRepository for PostgresBankIouRepository {
+    async fn find_by_game(&self, game_id: Uuid) -> Result<Vec<BankIou>, anyhow::Error> {
+        let ious = sqlx::query_as::<_, BankIou>("SELECT * FROM bank_ious WHERE game_id = $1 ORDER BY created_at DESC")
+            .bind(game_id)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(ious)
+    }
+}
@@ -1,7 +1,8 @@
 use async_trait::async_trait;
 use sqlx::PgPool;
 use uuid::Uuid;
-use crate::domain::{entities::GameSession, repositories::GameRepository};
+use bigdecimal::BigDecimal;
+use crate::domain::{cash::wallet_for, entities::{CashMode, GameSession, TransactionCategory}, errors::ConcurrencyConflict, repositories::GameRepository};
 
 pub struct PostgresGameRepository {
     pool: PgPool,
@@ -18,8 +19,8 @@ impl GameRepository for PostgresGameRepository {
     async fn create(&self, game: GameSession) -> Result<GameSession, anyhow::Error> {
         let rec = sqlx::query_as::<_, GameSession>(
             r#"
-            INSERT INTO game_sessions (id, host_user_id, name, status, created_at, ended_at, code, current_turn_user_id, turn_order)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            INSERT INTO game_sessions (id, host_user_id, name, status, created_at, ended_at, code, current_turn_user_id, turn_order, house_rules, deadline_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             RETURNING *
             "#
         )
@@ -32,6 +33,8 @@ impl GameRepository for PostgresGameRepository {
         .bind(game.code)
         .bind(game.current_turn_user_id)
         .bind(game.turn_order)
+        .bind(game.house_rules)
+        .bind(game.deadline_at)
         .fetch_one(&self.pool)
         .await?;
 
@@ -79,11 +82,15 @@ impl GameRepository for PostgresGameRepository {
     }
 
     async fn update(&self, game: GameSession) -> Result<GameSession, anyhow::Error> {
+        // Compare-and-swap on version. jackpot_balance and bank_balance are left alone:
+        // they only move through atomic increments in the transaction repository.
         let updated = sqlx::query_as::<_, GameSession>(
             r#"
             UPDATE game_sessions 
-            SET host_user_id = $1, name = $2, status = $3, ended_at = $4, current_turn_user_id = $5, turn_order = $6, jackpot_balance = $7
-            WHERE id = $8
+            SET host_user_id = $1, name = $2, status = $3, ended_at = $4, current_turn_user_id = $5, turn_order = $6,
+                winner_participant_id = $7, win_reason = $8, house_rules = $9, deadline_at = $10, rematch_game_id = $11,
+                banker_user_id = $12, version = version + 1
+            WHERE id = $13 AND version = $14
             RETURNING *
             "#
         )
@@ -93,11 +100,17 @@ impl GameRepository for PostgresGameRepository {
         .bind(game.ended_at)
         .bind(game.current_turn_user_id)
         .bind(game.turn_order)
-        .bind(game.jackpot_balance)
+        .bind(game.winner_participant_id)
+        .bind(game.win_reason)
+        .bind(game.house_rules)
+        .bind(game.deadline_at)
+        .bind(game.rematch_game_id)
+        .bind(game.banker_user_id)
         .bind(game.id)
-        .fetch_one(&self.pool)
+        .bind(game.version)
+        .fetch_optional(&self.pool)
         .await?;
-        Ok(updated)
+        updated.ok_or_else(|| ConcurrencyConflict { entity: "game" }.into())
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), anyhow::Error> {
@@ -107,4 +120,128 @@ impl GameRepository for PostgresGameRepository {
             .await?;
         Ok(())
     }
+
+    async fn find_active_with_deadline(&self) -> Result<Vec<GameSession>, anyhow::Error> {
+        let games = sqlx::query_as::<_, GameSession>(
+            "SELECT * FROM game_sessions WHERE status = 'ACTIVE' AND deadline_at IS NOT NULL"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(games)
+    }
+
+    async fn set_bank_balance(&self, game_id: Uuid, balance: Option<bigdecimal::BigDecimal>) -> Result<(), anyhow::Error> {
+        sqlx::query("UPDATE game_sessions SET bank_balance = $1 WHERE id = $2")
+            .bind(balance)
+            .bind(game_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_cash_mode(&self, game: GameSession) -> Result<GameSession, anyhow::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        // Lock the balances so no payment lands between reading them and handing out the bills
+        let balances: Vec<(Uuid, BigDecimal)> = sqlx::query_as(
+            "SELECT id, balance FROM game_participants WHERE game_id = $1 FOR UPDATE"
+        )
+        .bind(game.id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for (participant_id, balance) in balances {
+            let bills = match game.house_rules.cash_mode {
+                CashMode::Balance => None,
+                CashMode::Denominations => Some(wallet_for(&balance)
+                    .map_err(|e| anyhow::anyhow!("Participant {} {}", participant_id, e))?),
+            };
+            sqlx::query("UPDATE game_participants SET bills = $1 WHERE id = $2")
+                .bind(bills.map(sqlx::types::Json))
+                .bind(participant_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let updated = sqlx::query_as::<_, GameSession>(
+            "UPDATE game_sessions SET house_rules = $1, version = version + 1 WHERE id = $2 AND version = $3 RETURNING *"
+        )
+        .bind(game.house_rules)
+        .bind(game.id)
+        .bind(game.version)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ConcurrencyConflict { entity: "game" })?;
+
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    async fn create_rematch(&self, finished: GameSession, game: GameSession, user_ids: Vec<Uuid>, funding: BigDecimal) -> Result<GameSession, anyhow::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let created = sqlx::query_as::<_, GameSession>(
+            r#"
+            INSERT INTO game_sessions (id, host_user_id, name, status, created_at, ended_at, code, current_turn_user_id, turn_order, house_rules, deadline_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING *
+            "#
+        )
+        .bind(game.id)
+        .bind(game.host_user_id)
+        .bind(game.name)
+        .bind(game.status)
+        .bind(game.created_at)
+        .bind(game.ended_at)
+        .bind(game.code)
+        .bind(game.current_turn_user_id)
+        .bind(game.turn_order)
+        .bind(&game.house_rules)
+        .bind(game.deadline_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // Everybody starts funded by the Bank, as if they had joined one by one (clock_timestamp keeps the order)
+        let bills = match game.house_rules.cash_mode {
+            CashMode::Balance => None,
+            CashMode::Denominations => Some(sqlx::types::Json(wallet_for(&funding)?)),
+        };
+        for user_id in user_ids {
+            let (participant_id,): (Uuid,) = sqlx::query_as(
+                "INSERT INTO game_participants (id, game_id, user_id, balance, bills, joined_at) VALUES ($1, $2, $3, $4, $5, clock_timestamp()) RETURNING id"
+            )
+            .bind(Uuid::new_v4())
+            .bind(created.id)
+            .bind(user_id)
+            .bind(&funding)
+            .bind(&bills)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            sqlx::query("INSERT INTO transactions (id, game_id, to_participant_id, amount, description, category) VALUES ($1, $2, $3, $4, $5, $6)")
+                .bind(Uuid::new_v4())
+                .bind(created.id)
+                .bind(participant_id)
+                .bind(&funding)
+                .bind("Initial Funding")
+                .bind(TransactionCategory::Salary.to_string())
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let linked = sqlx::query(
+            "UPDATE game_sessions SET rematch_game_id = $1, version = version + 1 WHERE id = $2 AND version = $3 AND rematch_game_id IS NULL"
+        )
+        .bind(created.id)
+        .bind(finished.id)
+        .bind(finished.version)
+        .execute(&mut *tx)
+        .await?;
+        if linked.rows_affected() == 0 {
+            return Err(ConcurrencyConflict { entity: "game" }.into());
+        }
+
+        tx.commit().await?;
+        Ok(created)
+    }
 }
@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::domain::{
+    entities::IdempotencyRecord,
+    repositories::IdempotencyRepository,
+};
+
+pub struct PostgresIdempotencyRepository {
+    pool: PgPool,
+}
+
+impl PostgresIdempotencyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl IdempotencyRepository for PostgresIdempotencyRepository {
+    async fn claim(&self, record: IdempotencyRecord, stale_before: time::OffsetDateTime) -> Result<bool, anyhow::Error> {
+        // The primary key settles races between concurrent retries; a claim whose request died
+        // without an answer is taken over by a retry of the same request once it is stale
+        let result = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (game_id, idempotency_key, user_id, endpoint, request_hash, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (game_id, idempotency_key) DO UPDATE SET created_at = EXCLUDED.created_at
+            WHERE idempotency_keys.status_code IS NULL
+              AND idempotency_keys.created_at < $7
+              AND idempotency_keys.user_id = EXCLUDED.user_id
+              AND idempotency_keys.endpoint = EXCLUDED.endpoint
+              AND idempotency_keys.request_hash = EXCLUDED.request_hash
+            "#
+        )
+        .bind(record.game_id)
+        .bind(record.idempotency_key)
+        .bind(record.user_id)
+        .bind(record.endpoint)
+        .bind(record.request_hash)
+        .bind(record.created_at)
+        .bind(stale_before)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn find(&self, game_id: Uuid, key: &str) -> Result<Option<IdempotencyRecord>, anyhow::Error> {
+        let record = sqlx::query_as::<_, IdempotencyRecord>(
+            "SELECT * FROM idempotency_keys WHERE game_id = $1 AND idempotency_key = $2"
+        )
+        .bind(game_id)
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(record)
+    }
+
+    async fn complete(&self, game_id: Uuid, key: &str, status_code: i32, content_type: Option<String>, body: Vec<u8>) -> Result<(), anyhow::Error> {
+        sqlx::query(
+            "UPDATE idempotency_keys SET status_code = $1, content_type = $2, response_body = $3 WHERE game_id = $4 AND idempotency_key = $5"
+        )
+        .bind(status_code)
+        .bind(content_type)
+        .bind(body)
+        .bind(game_id)
+        .bind(key)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn release(&self, game_id: Uuid, key: &str) -> Result<(), anyhow::Error> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE game_id = $1 AND idempotency_key = $2")
+            .bind(game_id)
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
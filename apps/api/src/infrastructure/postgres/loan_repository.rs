@@ -0,0 +1,94 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::domain::{
+    entities::Loan,
+    repositories::LoanRepository,
+};
+
+pub struct PostgresLoanRepository {
+    pool: PgPool,
+}
+
+impl PostgresLoanRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl LoanRepository for PostgresLoanRepository {
+    async fn create(&self, loan: Loan) -> Result<Loan, anyhow::Error> {
+        let created = sqlx::query_as::<_, Loan>(
+            r#"
+            INSERT INTO loans (id, game_id, lender_participant_id, borrower_participant_id, principal, interest_percent,
+                               interest_period, due_after_periods, periods_elapsed, outstanding, status, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING *
+            "#
+        )
+        .bind(loan.id)
+        .bind(loan.game_id)
+        .bind(loan.lender_participant_id)
+        .bind(loan.borrower_participant_id)
+        .bind(loan.principal)
+        .bind(loan.interest_percent)
+        .bind(loan.interest_period.to_string())
+        .bind(loan.due_after_periods)
+        .bind(loan.periods_elapsed)
+        .bind(loan.outstanding)
+        .bind(loan.status)
+        .bind(loan.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(created)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Loan>, anyhow::Error> {
+        let loan = sqlx::query_as::<_, Loan>("SELECT * FROM loans WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(loan)
+    }
+
+    async fn find_by_game(&self, game_id: Uuid) -> Result<Vec<Loan>, anyhow::Error> {
+        let loans = sqlx::query_as::<_, Loan>("SELECT * FROM loans WHERE game_id = $1 ORDER BY created_at DESC")
+            .bind(game_id)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(loans)
+    }
+
+    async fn find_active_by_borrower(&self, game_id: Uuid, participant_id: Uuid) -> Result<Vec<Loan>, anyhow::Error> {
+        let loans = sqlx::query_as::<_, Loan>(
+            "SELECT * FROM loans WHERE game_id = $1 AND borrower_participant_id = $2 AND status = 'ACTIVE' ORDER BY created_at ASC"
+        )
+        .bind(game_id)
+        .bind(participant_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(loans)
+    }
+
+    async fn update(&self, loan: Loan, expected_status: &str) -> Result<Option<Loan>, anyhow::Error> {
+        let updated = sqlx::query_as::<_, Loan>(
+            r#"
+            UPDATE loans
+            SET periods_elapsed = $1, outstanding = $2, status = $3, settled_at = $4, version = version + 1
+            WHERE id = $5 AND status = $6 AND version = $7
+            RETURNING *
+            "#
+        )
+        .bind(loan.periods_elapsed)
+        .bind(loan.outstanding)
+        .bind(loan.status)
+        .bind(loan.settled_at)
+        .bind(loan.id)
+        .bind(expected_status)
+        .bind(loan.version)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(updated)
+    }
+}
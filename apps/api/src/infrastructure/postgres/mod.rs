@@ -9,3 +9,8 @@ pub mod card_repository;
 pub mod property_repository;
 pub mod auction_repository;
 pub mod trade_repository;
+pub mod standings_repository;
+pub mod loan_repository;
+pub mod payment_request_repository;
+pub mod bank_iou_repository;
+pub mod idempotency_repository;
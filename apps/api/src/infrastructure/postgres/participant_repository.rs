@@ -19,8 +19,8 @@ impl ParticipantRepository for PostgresParticipantRepository {
     async fn add_participant(&self, participant: GameParticipant) -> Result<GameParticipant, anyhow::Error> {
         let rec = sqlx::query_as::<_, GameParticipant>(
             r#"
-            INSERT INTO game_participants (id, game_id, user_id, balance)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO game_participants (id, game_id, user_id, balance, bills)
+            VALUES ($1, $2, $3, $4, $5)
             RETURNING *
             "#
         )
@@ -28,6 +28,7 @@ impl ParticipantRepository for PostgresParticipantRepository {
         .bind(participant.game_id)
         .bind(participant.user_id)
         .bind(participant.balance)
+        .bind(participant.bills)
         .fetch_one(&self.pool)
         .await?;
 
@@ -46,7 +47,7 @@ impl ParticipantRepository for PostgresParticipantRepository {
         let participants = sqlx::query_as::<_, crate::domain::entities::ParticipantDetail>(
             r#"
             SELECT 
-                gp.id, gp.game_id, gp.user_id, gp.balance, gp.position,
+                gp.id, gp.game_id, gp.user_id, gp.balance, gp.position, gp.bankrupt_at, gp.bills,
                 u.username, u.first_name, u.last_name
             FROM game_participants gp
             JOIN users u ON gp.user_id = u.id
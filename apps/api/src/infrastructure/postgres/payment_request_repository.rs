@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::domain::{
+    entities::PaymentRequest,
+    repositories::PaymentRequestRepository,
+};
+
+pub struct PostgresPaymentRequestRepository {
+    pool: PgPool,
+}
+
+impl PostgresPaymentRequestRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PaymentRequestRepository for PostgresPaymentRequestRepository {
+    async fn create(&self, request: PaymentRequest) -> Result<PaymentRequest, anyhow::Error> {
+        let created = sqlx::query_as::<_, PaymentRequest>(
+            r#"
+            INSERT INTO payment_requests (id, game_id, payee_participant_id, payer_participant_id, amount, category,
+                                          description, property_id, status, requested_by_user_id, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING *
+            "#
+        )
+        .bind(request.id)
+        .bind(request.game_id)
+        .bind(request.payee_participant_id)
+        .bind(request.payer_participant_id)
+        .bind(request.amount)
+        .bind(request.category.to_string())
+        .bind(request.description)
+        .bind(request.property_id)
+        .bind(request.status)
+        .bind(request.requested_by_user_id)
+        .bind(request.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(created)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PaymentRequest>, anyhow::Error> {
+        let request = sqlx::query_as::<_, PaymentRequest>("SELECT * FROM payment_requests WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(request)
+    }
+
+    async fn find_by_game(&self, game_id: Uuid) -> Result<Vec<PaymentRequest>, anyhow::Error> {
+        let requests = sqlx::query_as::<_, PaymentRequest>(
+            "SELECT * FROM payment_requests WHERE game_id = $1 ORDER BY created_at DESC"
+        )
+        .bind(game_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(requests)
+    }
+
+    async fn update(&self, request: PaymentRequest, expected_status: &str) -> Result<Option<PaymentRequest>, anyhow::Error> {
+        let updated = sqlx::query_as::<_, PaymentRequest>(
+            r#"
+            UPDATE payment_requests
+            SET status = $1, resolved_by_user_id = $2, transaction_id = $3, resolved_at = $4
+            WHERE id = $5 AND status = $6
+            RETURNING *
+            "#
+        )
+        .bind(request.status)
+        .bind(request.resolved_by_user_id)
+        .bind(request.transaction_id)
+        .bind(request.resolved_at)
+        .bind(request.id)
+        .bind(expected_status)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(updated)
+    }
+}
@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use sqlx::PgPool;
 use uuid::Uuid;
 use crate::domain::{
-    entities::{Property, ParticipantProperty},
+    entities::{BuildingRight, Property, ParticipantProperty},
     repositories::PropertyRepository,
 };
 
@@ -68,63 +68,14 @@ impl PropertyRepository for PostgresPropertyRepository {
         Ok(ownership)
     }
 
-    async fn assign_property(&self, pp: ParticipantProperty) -> Result<ParticipantProperty, anyhow::Error> {
-        let created = sqlx::query_as::<_, ParticipantProperty>(
-            r#"
-            INSERT INTO participant_properties (game_id, participant_id, property_id, is_mortgaged, house_count, hotel_count)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING *
-            "#
-        )
-        .bind(pp.game_id)
-        .bind(pp.participant_id)
-        .bind(pp.property_id)
-        .bind(pp.is_mortgaged)
-        .bind(pp.house_count)
-        .bind(pp.hotel_count)
-        .fetch_one(&self.pool)
-        .await?;
-        Ok(created)
-    }
-
-    async fn update_property_ownership(&self, pp: ParticipantProperty) -> Result<ParticipantProperty, anyhow::Error> {
-        let updated = sqlx::query_as::<_, ParticipantProperty>(
-            r#"
-            UPDATE participant_properties
-            SET is_mortgaged = $1, house_count = $2, hotel_count = $3
-            WHERE id = $4
-            RETURNING *
-            "#
+    async fn find_building_rights(&self, game_id: Uuid, participant_id: Uuid) -> Result<Vec<BuildingRight>, anyhow::Error> {
+        let rights = sqlx::query_as::<_, BuildingRight>(
+            "SELECT * FROM building_rights WHERE game_id = $1 AND participant_id = $2 AND used_at IS NULL ORDER BY created_at ASC"
         )
-        .bind(pp.is_mortgaged)
-        .bind(pp.house_count)
-        .bind(pp.hotel_count)
-        .bind(pp.id)
-        .fetch_one(&self.pool)
-        .await?;
-        Ok(updated)
-    }
-
-    async fn transfer_property(&self, game_id: Uuid, property_id: Uuid, new_participant_id: Uuid) -> Result<(), anyhow::Error> {
-        sqlx::query(
-            "UPDATE participant_properties SET participant_id = $1 WHERE game_id = $2 AND property_id = $3"
-        )
-        .bind(new_participant_id)
         .bind(game_id)
-        .bind(property_id)
-        .execute(&self.pool)
-        .await?;
-        Ok(())
-    }
-
-    async fn delete_ownership(&self, game_id: Uuid, property_id: Uuid) -> Result<(), anyhow::Error> {
-        sqlx::query(
-            "DELETE FROM participant_properties WHERE game_id = $1 AND property_id = $2"
-        )
-        .bind(game_id)
-        .bind(property_id)
-        .execute(&self.pool)
+        .bind(participant_id)
+        .fetch_all(&self.pool)
         .await?;
-        Ok(())
+        Ok(rights)
     }
 }
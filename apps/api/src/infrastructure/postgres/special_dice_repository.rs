@@ -11,6 +11,7 @@ impl PostgresSpecialDiceRepository {
         Self { pool }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         &self, 
         game_id: Uuid, 
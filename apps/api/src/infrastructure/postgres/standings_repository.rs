@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::domain::{
+    entities::{GameSession, Standing},
+    errors::ConcurrencyConflict,
+    repositories::StandingsRepository,
+};
+
+pub struct PostgresStandingsRepository {
+    pool: PgPool,
+}
+
+impl PostgresStandingsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl StandingsRepository for PostgresStandingsRepository {
+    async fn finish_game(&self, game: GameSession, standings: Vec<Standing>) -> Result<(GameSession, Vec<Standing>), anyhow::Error> {
+        let mut tx = self.pool.begin().await?;
+        let game_id = game.id;
+
+        // Only one ending wins: a concurrent end (or any other write to the game) conflicts
+        let finished = sqlx::query_as::<_, GameSession>(
+            r#"
+            UPDATE game_sessions
+            SET status = $1, ended_at = $2, winner_participant_id = $3, win_reason = $4, version = version + 1
+            WHERE id = $5 AND version = $6 AND status <> $1
+            RETURNING *
+            "#
+        )
+        .bind(game.status)
+        .bind(game.ended_at)
+        .bind(game.winner_participant_id)
+        .bind(game.win_reason)
+        .bind(game_id)
+        .bind(game.version)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ConcurrencyConflict { entity: "game" })?;
+
+        // Replace any previous result for this game
+        sqlx::query("DELETE FROM game_standings WHERE game_id = $1")
+            .bind(game_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut saved = Vec::with_capacity(standings.len());
+        for s in standings {
+            let rec = sqlx::query_as::<_, Standing>(
+                r#"
+                INSERT INTO game_standings (game_id, participant_id, user_id, rank, cash, property_value, building_value, loans_receivable, loans_payable, net_worth)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                RETURNING game_id, participant_id, user_id, rank, cash, property_value, building_value, loans_receivable, loans_payable, net_worth
+                "#
+            )
+            .bind(game_id)
+            .bind(s.participant_id)
+            .bind(s.user_id)
+            .bind(s.rank)
+            .bind(&s.cash)
+            .bind(&s.property_value)
+            .bind(&s.building_value)
+            .bind(&s.loans_receivable)
+            .bind(&s.loans_payable)
+            .bind(&s.net_worth)
+            .fetch_one(&mut *tx)
+            .await?;
+            saved.push(rec);
+        }
+
+        tx.commit().await?;
+        Ok((finished, saved))
+    }
+
+    async fn find_by_game(&self, game_id: Uuid) -> Result<Vec<Standing>, anyhow::Error> {
+        let standings = sqlx::query_as::<_, Standing>(
+            r#"
+            SELECT game_id, participant_id, user_id, rank, cash, property_value, building_value, loans_receivable, loans_payable, net_worth
+            FROM game_standings
+            WHERE game_id = $1
+            ORDER BY rank ASC
+            "#
+        )
+        .bind(game_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(standings)
+    }
+}
@@ -66,18 +66,19 @@ impl TradeRepository for PostgresTradeRepository {
         Ok(trade)
     }
 
-    async fn update(&self, trade: Trade) -> Result<Trade, anyhow::Error> {
+    async fn update(&self, trade: Trade, expected_status: &str) -> Result<Option<Trade>, anyhow::Error> {
         let updated = sqlx::query_as::<_, Trade>(
             r#"
             UPDATE trades
             SET status = $1
-            WHERE id = $2
+            WHERE id = $2 AND status = $3
             RETURNING *
             "#
         )
         .bind(trade.status)
         .bind(trade.id)
-        .fetch_one(&self.pool)
+        .bind(expected_status)
+        .fetch_optional(&self.pool)
         .await?;
         Ok(updated)
     }
@@ -1,7 +1,14 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
+use futures::{stream::BoxStream, StreamExt};
+use sqlx::{types::Json, PgConnection, PgPool};
 use uuid::Uuid;
-use crate::domain::{entities::Transaction, repositories::TransactionRepository};
+use bigdecimal::Zero;
+use crate::domain::{
+    cash::{hand_over, whole_amount, Bills},
+    entities::{short_pay, Auction, BankIou, BatchResult, GameParticipant, LedgerAdjustment, LedgerEntry, LedgerSnapshot, Loan, OwnershipChange, ParticipantProperty, Transaction, TransactionCategory, TransactionFilter, TransferBatch},
+    errors::ConcurrencyConflict,
+    repositories::TransactionRepository,
+};
 
 pub struct PostgresTransactionRepository {
     pool: PgPool,
@@ -15,136 +22,167 @@ impl PostgresTransactionRepository {
 
 #[async_trait]
 impl TransactionRepository for PostgresTransactionRepository {
-    async fn find_by_game(&self, game_id: Uuid) -> Result<Vec<Transaction>, anyhow::Error> {
+    async fn find_by_game(&self, game_id: Uuid, filter: TransactionFilter) -> Result<Vec<Transaction>, anyhow::Error> {
         let transactions = sqlx::query_as::<_, Transaction>(
             r#"
-            SELECT * FROM transactions WHERE game_id = $1 ORDER BY created_at DESC
+            SELECT * FROM transactions
+            WHERE game_id = $1
+              AND ($2::VARCHAR IS NULL OR category = $2)
+              AND ($3::UUID IS NULL OR property_id = $3)
+              AND ($4::UUID IS NULL OR card_id = $4)
+              AND ($5::UUID IS NULL OR trade_id = $5)
+              AND ($6::UUID IS NULL OR auction_id = $6)
+            ORDER BY created_at DESC
             "#
         )
         .bind(game_id)
+        .bind(filter.category.map(|c| c.to_string()))
+        .bind(filter.property_id)
+        .bind(filter.card_id)
+        .bind(filter.trade_id)
+        .bind(filter.auction_id)
         .fetch_all(&self.pool)
         .await?;
 
         Ok(transactions)
     }
 
-    async fn delete(&self, id: Uuid) -> Result<(), anyhow::Error> {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Transaction>, anyhow::Error> {
+        let transaction = sqlx::query_as::<_, Transaction>("SELECT * FROM transactions WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(transaction)
+    }
+
+    async fn reverse(&self, original_id: Uuid, description: Option<String>) -> Result<(Transaction, Transaction), anyhow::Error> {
         let mut tx = self.pool.begin().await?;
 
-        // 1. Fetch transaction details to know what to reverse
-        let transaction: Option<Transaction> = sqlx::query_as("SELECT * FROM transactions WHERE id = $1 FOR UPDATE")
-            .bind(id)
+        // 1. Lock the original so it can't be reversed twice concurrently
+        let original: Transaction = sqlx::query_as("SELECT * FROM transactions WHERE id = $1 FOR UPDATE")
+            .bind(original_id)
             .fetch_optional(&mut *tx)
-            .await?;
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Transaction not found"))?;
 
-        let transaction = match transaction {
-            Some(t) => t,
-            None => return Ok(()) // Already deleted or not found
-        };
+        if original.reversed_by_transaction_id.is_some() {
+            return Err(anyhow::anyhow!("Transaction was already reversed"));
+        }
+        if original.reverses_transaction_id.is_some() {
+            return Err(anyhow::anyhow!("A reversal cannot be reversed"));
+        }
 
-        // 2. Reverse effects
-        // Refund Sender (Add back)
-        if let Some(from_id) = transaction.from_participant_id {
+        // 2. Money flows back the other way
+        move_cash(&mut tx, original.to_participant_id, original.from_participant_id, &original.amount, None).await?;
+        if let Some(from_id) = original.from_participant_id {
              sqlx::query("UPDATE game_participants SET balance = balance + $1 WHERE id = $2")
-                 .bind(&transaction.amount)
+                 .bind(&original.amount)
                  .bind(from_id)
                  .execute(&mut *tx)
                  .await?;
         }
 
-        // Reclaim from Receiver (Deduct)
-        if let Some(to_id) = transaction.to_participant_id {
-             // We allow negative balance here? Typically user wants undo, so yes, we force deduction even if it goes negative (debt).
+        // Forced even if the receiver goes negative (debt)
+        if let Some(to_id) = original.to_participant_id {
              sqlx::query("UPDATE game_participants SET balance = balance - $1 WHERE id = $2")
-                 .bind(&transaction.amount)
+                 .bind(&original.amount)
                  .bind(to_id)
                  .execute(&mut *tx)
                  .await?;
         }
 
-        // 3. Delete Record
-        sqlx::query("DELETE FROM transactions WHERE id = $1")
-            .bind(id)
-            .execute(&mut *tx)
-            .await?;
-
-        tx.commit().await?;
-        Ok(())
-    }
-
-    async fn execute_transfer(
-        &self, 
-        transaction: Transaction
-    ) -> Result<Transaction, anyhow::Error> {
-        let mut tx = self.pool.begin().await?;
-
-        // Set Isolation Level
-        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ").execute(&mut *tx).await?;
-
-        // 1. Handle Sender (Deduct)
-        if let Some(from_id) = transaction.from_participant_id {
-             // Check Balance and Lock Row
-             let balance_row: Option<(bigdecimal::BigDecimal,)> = sqlx::query_as(
-                "SELECT balance FROM game_participants WHERE id = $1 FOR UPDATE"
-             )
-             .bind(from_id)
-             .fetch_optional(&mut *tx)
-             .await?;
-
-             if let Some((_balance,)) = balance_row {
-                 // Allow negative balance, so no check here.
-                 
-                 // Deduct
-                 sqlx::query("UPDATE game_participants SET balance = balance - $1 WHERE id = $2")
-                     .bind(&transaction.amount)
-                     .bind(from_id)
-                     .execute(&mut *tx)
-                     .await?;
-
-             } else {
-                 return Err(anyhow::anyhow!("Sender participant not found for ID: {}", from_id));
-             }
-         }
-
-        // 2. Handle Receiver (Add)
-        if let Some(to_id) = transaction.to_participant_id {
-             sqlx::query("UPDATE game_participants SET balance = balance + $1 WHERE id = $2")
-                 .bind(&transaction.amount)
-                 .bind(to_id)
+        // 3. Undo whatever the original did to the jackpot and the Bank reserve
+        move_bank(&mut tx, original.game_id, -original.bank_delta()).await?;
+        if !original.jackpot_delta.is_zero() {
+             sqlx::query("UPDATE game_sessions SET jackpot_balance = jackpot_balance - $1 WHERE id = $2")
+                 .bind(&original.jackpot_delta)
+                 .bind(original.game_id)
                  .execute(&mut *tx)
                  .await?;
         }
 
-        // 3. Create Transaction Record
-        let rec = sqlx::query_as::<_, Transaction>(
+        // 4. Record the compensating entry, linked both ways
+        let reversal = sqlx::query_as::<_, Transaction>(
             r#"
-            INSERT INTO transactions (id, game_id, from_participant_id, to_participant_id, amount, description, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            INSERT INTO transactions (id, game_id, from_participant_id, to_participant_id, amount, description, created_at, jackpot_delta, reverses_transaction_id,
+                                      category, property_id, card_id, trade_id, auction_id, loan_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
             RETURNING *
             "#
         )
-        .bind(transaction.id)
-        .bind(transaction.game_id)
-        .bind(transaction.from_participant_id)
-        .bind(transaction.to_participant_id)
-        .bind(&transaction.amount) // Use ref for consistency, though Copy works for some types
-        .bind(transaction.description)
-        .bind(transaction.created_at)
+        .bind(Uuid::new_v4())
+        .bind(original.game_id)
+        .bind(original.to_participant_id)
+        .bind(original.from_participant_id)
+        .bind(&original.amount)
+        .bind(description)
+        .bind(time::OffsetDateTime::now_utc())
+        .bind(-&original.jackpot_delta)
+        .bind(original.id)
+        .bind(TransactionCategory::Reversal.to_string())
+        .bind(original.property_id)
+        .bind(original.card_id)
+        .bind(original.trade_id)
+        .bind(original.auction_id)
+        .bind(original.loan_id)
         .fetch_one(&mut *tx)
         .await?;
 
-        // 4. Jackpot Logic: If paying to Bank (from_id=Some, to_id=None), add to jackpot
-        if transaction.from_participant_id.is_some() && transaction.to_participant_id.is_none() {
-             sqlx::query("UPDATE game_sessions SET jackpot_balance = jackpot_balance + $1 WHERE id = $2")
-                 .bind(&transaction.amount)
-                 .bind(transaction.game_id)
-                 .execute(&mut *tx)
-                 .await?;
-        }
+        let original = sqlx::query_as::<_, Transaction>(
+            "UPDATE transactions SET reversed_by_transaction_id = $1 WHERE id = $2 RETURNING *"
+        )
+        .bind(reversal.id)
+        .bind(original.id)
+        .fetch_one(&mut *tx)
+        .await?;
 
         tx.commit().await?;
+        Ok((original, reversal))
+    }
 
-        Ok(rec)
+    async fn ledger_snapshot(&self, game_id: Uuid) -> Result<LedgerSnapshot, anyhow::Error> {
+        let mut tx = self.pool.begin().await?;
+        let snapshot = read_snapshot(&mut tx, game_id, "FOR SHARE").await?;
+        tx.commit().await?;
+        Ok(snapshot)
+    }
+
+    async fn repair_ledger(&self, game_id: Uuid, adjust: LedgerAdjustment) -> Result<Vec<Transaction>, anyhow::Error> {
+        let mut tx = self.pool.begin().await?;
+        let snapshot = read_snapshot(&mut tx, game_id, "FOR UPDATE").await?;
+
+        let mut recorded = Vec::new();
+        for entry in adjust(&snapshot) {
+            let rec = sqlx::query_as::<_, Transaction>(
+                r#"
+                INSERT INTO transactions (id, game_id, from_participant_id, to_participant_id, amount, description, created_at, jackpot_delta,
+                                          category, property_id, card_id, trade_id, auction_id, loan_id, group_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+                RETURNING *
+                "#
+            )
+            .bind(entry.id)
+            .bind(entry.game_id)
+            .bind(entry.from_participant_id)
+            .bind(entry.to_participant_id)
+            .bind(&entry.amount)
+            .bind(entry.description)
+            .bind(entry.created_at)
+            .bind(&entry.jackpot_delta)
+            .bind(entry.category.to_string())
+            .bind(entry.property_id)
+            .bind(entry.card_id)
+            .bind(entry.trade_id)
+            .bind(entry.auction_id)
+            .bind(entry.loan_id)
+            .bind(entry.group_id)
+            .fetch_one(&mut *tx)
+            .await?;
+            recorded.push(rec);
+        }
+
+        tx.commit().await?;
+        Ok(recorded)
     }
 
     async fn claim_jackpot(&self, game_id: Uuid, user_id: Uuid) -> Result<Transaction, anyhow::Error> {
@@ -158,7 +196,7 @@ impl TransactionRepository for PostgresTransactionRepository {
         
         let amount = row.0;
 
-        if amount <= bigdecimal::BigDecimal::from(0) {
+        if amount <= bigdecimal::BigDecimal::zero() {
             return Err(anyhow::anyhow!("Jackpot is empty"));
         }
 
@@ -175,6 +213,7 @@ impl TransactionRepository for PostgresTransactionRepository {
         };
 
         // 3. Transfer to User
+        move_cash(&mut tx, None, Some(to_pid), &amount, None).await?;
         sqlx::query("UPDATE game_participants SET balance = balance + $1 WHERE id = $2")
             .bind(&amount)
             .bind(to_pid)
@@ -190,8 +229,8 @@ impl TransactionRepository for PostgresTransactionRepository {
         // 5. Record Transaction
         let rec = sqlx::query_as::<_, Transaction>(
             r#"
-            INSERT INTO transactions (id, game_id, from_participant_id, to_participant_id, amount, description, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            INSERT INTO transactions (id, game_id, from_participant_id, to_participant_id, amount, description, created_at, jackpot_delta, category)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
             RETURNING *
             "#
         )
@@ -202,6 +241,8 @@ impl TransactionRepository for PostgresTransactionRepository {
         .bind(&amount)
         .bind("Jackpot Win!".to_string())
         .bind(time::OffsetDateTime::now_utc())
+        .bind(-&amount)
+        .bind(TransactionCategory::Jackpot.to_string())
         .fetch_one(&mut *tx)
         .await?;
 
@@ -209,4 +250,678 @@ impl TransactionRepository for PostgresTransactionRepository {
 
         Ok(rec)
     }
+
+    async fn execute_batch(&self, batch: TransferBatch) -> Result<BatchResult, anyhow::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ").execute(&mut *tx).await?;
+
+        // Dropping `tx` on any error rolls everything back
+        let result = apply_batch(&mut tx, batch).await.map_err(serialization_conflict)?;
+        tx.commit().await.map_err(|e| serialization_conflict(e.into()))?;
+        Ok(result)
+    }
+
+    fn stream_ledger(&self, game_id: Uuid) -> BoxStream<'static, Result<LedgerEntry, anyhow::Error>> {
+        // The query borrows the pool, so it runs in its own task and hands rows over a bounded
+        // channel: a slow download holds the query back instead of buffering the whole game
+        let pool = self.pool.clone();
+        let (sender, receiver) = tokio::sync::mpsc::channel(64);
+
+        tokio::spawn(async move {
+            let mut rows = sqlx::query_as::<_, LedgerEntry>(LEDGER_EXPORT_QUERY)
+                .bind(game_id)
+                .fetch(&pool);
+
+            while let Some(row) = rows.next().await {
+                let failed = row.is_err();
+                if sender.send(row.map_err(anyhow::Error::from)).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        futures::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|row| (row, receiver))
+        }).boxed()
+    }
+}
+
+// Every loggable event of a game as one timeline, names resolved
+const LEDGER_EXPORT_QUERY: &str = r#"
+    SELECT 'transaction' AS kind, t.id, t.created_at AS occurred_at,
+           t.from_participant_id, t.to_participant_id,
+           COALESCE(fu.first_name || ' ' || fu.last_name,
+                    CASE WHEN t.category = 'jackpot' AND t.to_participant_id IS NOT NULL THEN 'Jackpot' ELSE 'Bank' END) AS from_name,
+           COALESCE(tu.first_name || ' ' || tu.last_name,
+                    CASE WHEN t.category = 'jackpot' THEN 'Jackpot' ELSE 'Bank' END) AS to_name,
+           t.amount, t.category, t.description
+    FROM transactions t
+    LEFT JOIN game_participants fp ON fp.id = t.from_participant_id
+    LEFT JOIN users fu ON fu.id = fp.user_id
+    LEFT JOIN game_participants tp ON tp.id = t.to_participant_id
+    LEFT JOIN users tu ON tu.id = tp.user_id
+    WHERE t.game_id = $1
+
+    UNION ALL
+    SELECT 'dice_roll', d.id, d.created_at, gp.id, NULL,
+           u.first_name || ' ' || u.last_name, NULL, NULL, NULL,
+           d.dice_count || 'd' || d.dice_sides || ' ' || d.results::TEXT || ' = ' || d.total
+    FROM dice_rolls d
+    JOIN users u ON u.id = d.user_id
+    LEFT JOIN game_participants gp ON gp.game_id = d.game_id AND gp.user_id = d.user_id
+    WHERE d.game_id = $1
+
+    UNION ALL
+    SELECT 'roulette_spin', r.id, r.created_at, gp.id, NULL,
+           u.first_name || ' ' || u.last_name, NULL, NULL, r.result_type,
+           r.result_label || ' (' || r.result_value || ')'
+    FROM roulette_spins r
+    JOIN users u ON u.id = r.user_id
+    LEFT JOIN game_participants gp ON gp.game_id = r.game_id AND gp.user_id = r.user_id
+    WHERE r.game_id = $1
+
+    UNION ALL
+    SELECT 'special_dice_roll', s.id, s.created_at, gp.id, NULL,
+           u.first_name || ' ' || u.last_name, NULL, NULL, s.face_action,
+           s.die_name || ': ' || s.face_label
+    FROM special_dice_rolls s
+    JOIN users u ON u.id = s.user_id
+    LEFT JOIN game_participants gp ON gp.game_id = s.game_id AND gp.user_id = s.user_id
+    WHERE s.game_id = $1
+
+    UNION ALL
+    SELECT 'card_usage', h.id, h.used_at, h.participant_id, NULL,
+           u.first_name || ' ' || u.last_name, NULL, NULL, c.title,
+           h.action_description
+    FROM card_usage_history h
+    JOIN game_participants gp ON gp.id = h.participant_id
+    JOIN users u ON u.id = gp.user_id
+    JOIN cards c ON c.id = h.card_id
+    WHERE h.game_id = $1
+
+    ORDER BY occurred_at ASC, kind ASC
+"#;
+
+// The game row and every balance are locked first (`lock` is FOR SHARE or FOR UPDATE): a payment either
+// committed before, and is in the history read after, or waits until the caller's transaction ends
+async fn read_snapshot(conn: &mut PgConnection, game_id: Uuid, lock: &str) -> Result<LedgerSnapshot, anyhow::Error> {
+    let (jackpot_balance,): (bigdecimal::BigDecimal,) = sqlx::query_as(&format!("SELECT jackpot_balance FROM game_sessions WHERE id = $1 {}", lock))
+        .bind(game_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+
+    let participants = sqlx::query_as::<_, GameParticipant>(&format!("SELECT * FROM game_participants WHERE game_id = $1 ORDER BY id {}", lock))
+        .bind(game_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+    let transactions = sqlx::query_as::<_, Transaction>("SELECT * FROM transactions WHERE game_id = $1 ORDER BY created_at ASC")
+        .bind(game_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+    Ok(LedgerSnapshot { game_id, participants, jackpot_balance, transactions })
+}
+
+async fn apply_batch(conn: &mut PgConnection, batch: TransferBatch) -> Result<BatchResult, anyhow::Error> {
+    let TransferBatch { game_id, mut legs, ownership, bills, bank_ious } = batch;
+    let mut result = BatchResult::default();
+
+    // The reserve stays locked until the commit, so two batches cannot both spend the same money
+    let mut shortfalls = Vec::new();
+    if bank_ious {
+        if let Some(reserve) = lock_bank(conn, game_id).await? {
+            shortfalls = short_pay(&mut legs, reserve);
+        }
+    }
+
+    let mut bank_delta = bigdecimal::BigDecimal::zero();
+    for leg in legs {
+        let bills = bills.get(&leg.id).cloned();
+        bank_delta += leg.bank_delta();
+        result.transactions.push(apply_leg(conn, leg, bills.as_ref()).await?);
+    }
+
+    for (transaction_id, participant_id, shortfall) in shortfalls {
+        let iou = sqlx::query_as::<_, BankIou>(
+            r#"
+            INSERT INTO bank_ious (game_id, participant_id, amount, outstanding, transaction_id, created_at)
+            VALUES ($1, $2, $3, $3, $4, $5)
+            RETURNING *
+            "#
+        )
+        .bind(game_id)
+        .bind(participant_id)
+        .bind(shortfall)
+        .bind(transaction_id)
+        .bind(time::OffsetDateTime::now_utc())
+        .fetch_one(&mut *conn)
+        .await?;
+        result.ious.push(iou);
+    }
+
+    // Fresh money in the Bank pays back what it owes
+    if bank_ious && bank_delta > bigdecimal::BigDecimal::zero() {
+        settle_ious(conn, game_id, &mut result).await?;
+    }
+
+    for change in ownership {
+        match change {
+            OwnershipChange::TransferCard { inventory_id, from_participant_id, to_participant_id } => {
+                result.cards.push(move_card(conn, inventory_id, from_participant_id, to_participant_id).await?);
+            }
+            OwnershipChange::TakeBovedaCard { slot_index, card_id, participant_id } => {
+                result.cards.push(take_boveda_card(conn, game_id, slot_index, card_id, participant_id).await?);
+            }
+            OwnershipChange::GrantBuildingRight { participant_id, kind, auction_id } => {
+                sqlx::query("INSERT INTO building_rights (game_id, participant_id, kind, auction_id) VALUES ($1, $2, $3, $4)")
+                    .bind(game_id)
+                    .bind(participant_id)
+                    .bind(kind.to_string())
+                    .bind(auction_id)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+            OwnershipChange::UseBuildingRight { right_id } => use_building_right(conn, right_id).await?,
+            OwnershipChange::ApprovePaymentRequest { request_id, resolved_by_user_id } => {
+                let transaction_id = result.transactions.first().map(|t| t.id);
+                approve_payment_request(conn, request_id, resolved_by_user_id, transaction_id).await?;
+            }
+            OwnershipChange::AcceptTrade { trade_id } => {
+                let accepted = sqlx::query("UPDATE trades SET status = 'ACCEPTED' WHERE id = $1 AND status = 'PENDING'")
+                    .bind(trade_id)
+                    .execute(&mut *conn)
+                    .await?;
+                if accepted.rows_affected() == 0 {
+                    return Err(ConcurrencyConflict { entity: "trade" }.into());
+                }
+            }
+            OwnershipChange::UpdateLoan { loan, expected_status } => {
+                result.loans.push(update_loan(conn, loan, &expected_status).await?);
+            }
+            OwnershipChange::DeclareBankrupt { participant_id } => {
+                result.loans.extend(declare_bankrupt(conn, game_id, participant_id).await?);
+            }
+            OwnershipChange::CloseAuction { auction } => close_auction(conn, auction).await?,
+            change => result.ownership.push(apply_ownership(conn, game_id, change).await?),
+        }
+    }
+
+    Ok(result)
+}
+
+// A REPEATABLE READ batch that lost a race with a concurrent write (or deadlocked with one) is a
+// conflict the client can retry, not a server error
+fn serialization_conflict(e: anyhow::Error) -> anyhow::Error {
+    match e.downcast_ref::<sqlx::Error>() {
+        Some(sqlx::Error::Database(db)) if matches!(db.code().as_deref(), Some("40001" | "40P01")) => {
+            ConcurrencyConflict { entity: "game" }.into()
+        }
+        _ => e,
+    }
+}
+
+// Finite Bank reserve, locked for the rest of the transaction; None for an infinite Bank
+async fn lock_bank(conn: &mut PgConnection, game_id: Uuid) -> Result<Option<bigdecimal::BigDecimal>, anyhow::Error> {
+    let (reserve,): (Option<bigdecimal::BigDecimal>,) = sqlx::query_as("SELECT bank_balance FROM game_sessions WHERE id = $1 FOR UPDATE")
+        .bind(game_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+    Ok(reserve)
+}
+
+// Oldest IOUs first, as far as the locked reserve reaches. Each repayment is a Bank payout
+// booked like any other leg; the IOU only shrinks if nobody settled it meanwhile.
+async fn settle_ious(conn: &mut PgConnection, game_id: Uuid, result: &mut BatchResult) -> Result<(), anyhow::Error> {
+    let Some(mut available) = lock_bank(conn, game_id).await? else {
+        return Ok(());
+    };
+
+    let outstanding = sqlx::query_as::<_, BankIou>(
+        "SELECT * FROM bank_ious WHERE game_id = $1 AND outstanding > 0 ORDER BY created_at ASC FOR UPDATE"
+    )
+    .bind(game_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for iou in outstanding {
+        if available <= bigdecimal::BigDecimal::zero() {
+            break;
+        }
+        let paid = iou.outstanding.clone().min(available.clone());
+
+        let mut row = Transaction::from_bank(game_id, Some(iou.participant_id), paid.clone(), "Bank IOU repayment");
+        row.category = TransactionCategory::Iou;
+        result.repayments.push(apply_leg(conn, row, None).await?);
+        available -= &paid;
+
+        let settled = sqlx::query_as::<_, BankIou>(
+            r#"
+            UPDATE bank_ious
+            SET outstanding = outstanding - $1,
+                settled_at = CASE WHEN outstanding - $1 = 0 THEN NOW() ELSE settled_at END
+            WHERE id = $2 AND outstanding = $3
+            RETURNING *
+            "#
+        )
+        .bind(&paid)
+        .bind(iou.id)
+        .bind(&iou.outstanding)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(ConcurrencyConflict { entity: "bank IOU" })?;
+        result.ious.push(settled);
+    }
+    Ok(())
+}
+
+// Move the money of one leg, record it and feed the jackpot, inside the caller's transaction
+async fn apply_leg(conn: &mut PgConnection, transaction: Transaction, bills: Option<&Bills>) -> Result<Transaction, anyhow::Error> {
+    move_cash(conn, transaction.from_participant_id, transaction.to_participant_id, &transaction.amount, bills).await?;
+    move_bank(conn, transaction.game_id, transaction.bank_delta()).await?;
+
+    // 1. Handle Sender (Deduct)
+    if let Some(from_id) = transaction.from_participant_id {
+         // Check Balance and Lock Row
+         let balance_row: Option<(bigdecimal::BigDecimal,)> = sqlx::query_as(
+            "SELECT balance FROM game_participants WHERE id = $1 FOR UPDATE"
+         )
+         .bind(from_id)
+         .fetch_optional(&mut *conn)
+         .await?;
+
+         if let Some((_balance,)) = balance_row {
+             // Allow negative balance, so no check here.
+             
+             // Deduct
+             sqlx::query("UPDATE game_participants SET balance = balance - $1 WHERE id = $2")
+                 .bind(&transaction.amount)
+                 .bind(from_id)
+                 .execute(&mut *conn)
+                 .await?;
+
+         } else {
+             return Err(anyhow::anyhow!("Sender participant not found for ID: {}", from_id));
+         }
+     }
+
+    // 2. Handle Receiver (Add)
+    if let Some(to_id) = transaction.to_participant_id {
+         sqlx::query("UPDATE game_participants SET balance = balance + $1 WHERE id = $2")
+             .bind(&transaction.amount)
+             .bind(to_id)
+             .execute(&mut *conn)
+             .await?;
+    }
+
+    // The service decides what feeds the jackpot (see the game's jackpot rules)
+    let jackpot_delta = transaction.jackpot_delta.clone();
+
+    // 3. Create Transaction Record
+    let rec = sqlx::query_as::<_, Transaction>(
+        r#"
+        INSERT INTO transactions (id, game_id, from_participant_id, to_participant_id, amount, description, created_at, jackpot_delta,
+                                  category, property_id, card_id, trade_id, auction_id, loan_id, group_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+        RETURNING *
+        "#
+    )
+    .bind(transaction.id)
+    .bind(transaction.game_id)
+    .bind(transaction.from_participant_id)
+    .bind(transaction.to_participant_id)
+    .bind(&transaction.amount) // Use ref for consistency, though Copy works for some types
+    .bind(transaction.description)
+    .bind(transaction.created_at)
+    .bind(&jackpot_delta)
+    .bind(transaction.category.to_string())
+    .bind(transaction.property_id)
+    .bind(transaction.card_id)
+    .bind(transaction.trade_id)
+    .bind(transaction.auction_id)
+    .bind(transaction.loan_id)
+    .bind(transaction.group_id)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    // 4. Jackpot Logic
+    if !jackpot_delta.is_zero() {
+         sqlx::query("UPDATE game_sessions SET jackpot_balance = jackpot_balance + $1 WHERE id = $2")
+             .bind(&jackpot_delta)
+             .bind(transaction.game_id)
+             .execute(&mut *conn)
+             .await?;
+    }
+
+    Ok(rec)
+}
+
+async fn apply_ownership(conn: &mut PgConnection, game_id: Uuid, change: OwnershipChange) -> Result<ParticipantProperty, anyhow::Error> {
+    match change {
+        OwnershipChange::Assign { property_id, participant_id } => {
+            let owned: Option<(Uuid,)> = sqlx::query_as(
+                "SELECT id FROM participant_properties WHERE game_id = $1 AND property_id = $2 FOR UPDATE"
+            )
+            .bind(game_id)
+            .bind(property_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            if owned.is_some() {
+                return Err(anyhow::anyhow!("Property is already owned"));
+            }
+
+            let created = sqlx::query_as::<_, ParticipantProperty>(
+                r#"
+                INSERT INTO participant_properties (game_id, participant_id, property_id, is_mortgaged, house_count, hotel_count)
+                VALUES ($1, $2, $3, FALSE, 0, 0)
+                RETURNING *
+                "#
+            )
+            .bind(game_id)
+            .bind(participant_id)
+            .bind(property_id)
+            .fetch_one(&mut *conn)
+            .await?;
+            Ok(created)
+        }
+        OwnershipChange::Transfer { property_id, from_participant_id, to_participant_id } => {
+            move_property(conn, game_id, property_id, from_participant_id, to_participant_id).await
+        }
+        OwnershipChange::TradeProperty { property_id, from_participant_id, to_participant_id } => {
+            // Touching the giver's whole group makes a build that committed after this transaction
+            // started fail here, and one still in flight fail its own version check
+            let group: Vec<(i32, i32)> = sqlx::query_as(
+                r#"
+                UPDATE participant_properties pp SET version = pp.version + 1
+                FROM properties p
+                WHERE p.id = pp.property_id AND pp.game_id = $1 AND pp.participant_id = $2
+                  AND p.group_color = (SELECT group_color FROM properties WHERE id = $3)
+                RETURNING pp.house_count, pp.hotel_count
+                "#
+            )
+            .bind(game_id)
+            .bind(from_participant_id)
+            .bind(property_id)
+            .fetch_all(&mut *conn)
+            .await?;
+
+            if group.iter().any(|(houses, hotels)| *houses > 0 || *hotels > 0) {
+                return Err(anyhow::anyhow!("Property {} is in a color group with buildings; sell them first", property_id));
+            }
+            move_property(conn, game_id, property_id, from_participant_id, to_participant_id).await
+        }
+        OwnershipChange::Release { property_id, from_participant_id } => {
+            let released = sqlx::query_as::<_, ParticipantProperty>(
+                "DELETE FROM participant_properties WHERE game_id = $1 AND property_id = $2 AND participant_id = $3 RETURNING *"
+            )
+            .bind(game_id)
+            .bind(property_id)
+            .bind(from_participant_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            released.ok_or_else(|| anyhow::anyhow!("Property {} is no longer owned by the sender", property_id))
+        }
+        OwnershipChange::Update(pp) => {
+            let updated = sqlx::query_as::<_, ParticipantProperty>(
+                r#"
+                UPDATE participant_properties
+                SET is_mortgaged = $1, house_count = $2, hotel_count = $3, version = version + 1
+                WHERE id = $4 AND game_id = $5 AND version = $6
+                RETURNING *
+                "#
+            )
+            .bind(pp.is_mortgaged)
+            .bind(pp.house_count)
+            .bind(pp.hotel_count)
+            .bind(pp.id)
+            .bind(game_id)
+            .bind(pp.version)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            updated.ok_or_else(|| ConcurrencyConflict { entity: "property" }.into())
+        }
+        OwnershipChange::TransferCard { .. }
+        | OwnershipChange::TakeBovedaCard { .. }
+        | OwnershipChange::GrantBuildingRight { .. }
+        | OwnershipChange::UseBuildingRight { .. }
+        | OwnershipChange::ApprovePaymentRequest { .. }
+        | OwnershipChange::AcceptTrade { .. }
+        | OwnershipChange::UpdateLoan { .. }
+        | OwnershipChange::CloseAuction { .. }
+        | OwnershipChange::DeclareBankrupt { .. } => {
+            Err(anyhow::anyhow!("Not a property change"))
+        }
+    }
+}
+
+async fn move_property(conn: &mut PgConnection, game_id: Uuid, property_id: Uuid, from_participant_id: Uuid, to_participant_id: Uuid) -> Result<ParticipantProperty, anyhow::Error> {
+    let moved = sqlx::query_as::<_, ParticipantProperty>(
+        r#"
+        UPDATE participant_properties SET participant_id = $1, version = version + 1
+        WHERE game_id = $2 AND property_id = $3 AND participant_id = $4
+        RETURNING *
+        "#
+    )
+    .bind(to_participant_id)
+    .bind(game_id)
+    .bind(property_id)
+    .bind(from_participant_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    moved.ok_or_else(|| anyhow::anyhow!("Property {} is no longer owned by the sender", property_id))
+}
+
+async fn move_card(conn: &mut PgConnection, inventory_id: Uuid, from_participant_id: Uuid, to_participant_id: Uuid) -> Result<Uuid, anyhow::Error> {
+    let moved: Option<(Uuid,)> = sqlx::query_as(
+        "UPDATE participant_cards SET participant_id = $1 WHERE id = $2 AND participant_id = $3 RETURNING id"
+    )
+    .bind(to_participant_id)
+    .bind(inventory_id)
+    .bind(from_participant_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    moved.map(|(id,)| id).ok_or_else(|| anyhow::anyhow!("Card {} is no longer held by the sender", inventory_id))
+}
+
+async fn use_building_right(conn: &mut PgConnection, right_id: Uuid) -> Result<(), anyhow::Error> {
+    let used = sqlx::query("UPDATE building_rights SET used_at = NOW() WHERE id = $1 AND used_at IS NULL")
+        .bind(right_id)
+        .execute(&mut *conn)
+        .await?;
+    if used.rows_affected() == 0 {
+        return Err(ConcurrencyConflict { entity: "building right" }.into());
+    }
+    Ok(())
+}
+
+async fn approve_payment_request(conn: &mut PgConnection, request_id: Uuid, resolved_by_user_id: Uuid, transaction_id: Option<Uuid>) -> Result<(), anyhow::Error> {
+    let approved = sqlx::query(
+        r#"
+        UPDATE payment_requests
+        SET status = 'APPROVED', resolved_by_user_id = $1, resolved_at = NOW(), transaction_id = $2
+        WHERE id = $3 AND status = 'PENDING'
+        "#
+    )
+    .bind(resolved_by_user_id)
+    .bind(transaction_id)
+    .bind(request_id)
+    .execute(&mut *conn)
+    .await?;
+    if approved.rows_affected() == 0 {
+        return Err(ConcurrencyConflict { entity: "payment request" }.into());
+    }
+    Ok(())
+}
+
+async fn update_loan(conn: &mut PgConnection, loan: Loan, expected_status: &str) -> Result<Loan, anyhow::Error> {
+    let updated = sqlx::query_as::<_, Loan>(
+        r#"
+        UPDATE loans
+        SET periods_elapsed = $1, outstanding = $2, status = $3, settled_at = $4, version = version + 1
+        WHERE id = $5 AND status = $6 AND version = $7
+        RETURNING *
+        "#
+    )
+    .bind(loan.periods_elapsed)
+    .bind(loan.outstanding)
+    .bind(loan.status)
+    .bind(loan.settled_at)
+    .bind(loan.id)
+    .bind(expected_status)
+    .bind(loan.version)
+    .fetch_optional(&mut *conn)
+    .await?;
+    updated.ok_or_else(|| ConcurrencyConflict { entity: "loan" }.into())
+}
+
+async fn close_auction(conn: &mut PgConnection, auction: Auction) -> Result<(), anyhow::Error> {
+    let closed = sqlx::query(
+        r#"
+        UPDATE auctions
+        SET current_bid = $1, highest_bidder_id = $2, status = 'FINISHED', ends_at = $3, version = version + 1
+        WHERE id = $4 AND status = 'ACTIVE' AND version = $5
+        "#
+    )
+    .bind(auction.current_bid)
+    .bind(auction.highest_bidder_id)
+    .bind(auction.ends_at)
+    .bind(auction.id)
+    .bind(auction.version)
+    .execute(&mut *conn)
+    .await?;
+    if closed.rows_affected() == 0 {
+        return Err(ConcurrencyConflict { entity: "auction" }.into());
+    }
+    Ok(())
+}
+
+async fn declare_bankrupt(conn: &mut PgConnection, game_id: Uuid, participant_id: Uuid) -> Result<Vec<Loan>, anyhow::Error> {
+    let debtor: Option<(Uuid,)> = sqlx::query_as(
+        "UPDATE game_participants SET bankrupt_at = NOW() WHERE id = $1 AND game_id = $2 AND bankrupt_at IS NULL RETURNING user_id"
+    )
+    .bind(participant_id)
+    .bind(game_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let (user_id,) = debtor.ok_or(ConcurrencyConflict { entity: "participant" })?;
+
+    // Whatever else they owed can no longer be collected
+    let loans = sqlx::query_as::<_, Loan>(
+        r#"
+        UPDATE loans SET status = 'DEFAULTED', settled_at = NOW(), version = version + 1
+        WHERE game_id = $1 AND borrower_participant_id = $2 AND status = 'ACTIVE'
+        RETURNING *
+        "#
+    )
+    .bind(game_id)
+    .bind(participant_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    // Drop them from the turn order, passing the turn on if it was theirs
+    let (order, current): (Option<Json<Vec<Uuid>>>, Option<Uuid>) = sqlx::query_as(
+        "SELECT turn_order, current_turn_user_id FROM game_sessions WHERE id = $1 FOR UPDATE"
+    )
+    .bind(game_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    if let Some(Json(list)) = order {
+        if let Some(idx) = list.iter().position(|u| *u == user_id) {
+            let remaining: Vec<Uuid> = list.iter().copied().filter(|u| *u != user_id).collect();
+            let current = match current {
+                Some(u) if u == user_id && !remaining.is_empty() => Some(remaining[idx % remaining.len()]),
+                other => other,
+            };
+            sqlx::query("UPDATE game_sessions SET turn_order = $1, current_turn_user_id = $2, version = version + 1 WHERE id = $3")
+                .bind(Json(remaining))
+                .bind(current)
+                .bind(game_id)
+                .execute(&mut *conn)
+                .await?;
+        }
+    }
+
+    Ok(loans)
+}
+
+async fn take_boveda_card(conn: &mut PgConnection, game_id: Uuid, slot_index: i32, card_id: Uuid, participant_id: Uuid) -> Result<Uuid, anyhow::Error> {
+    // The market refills the emptied slot the next time it is read
+    let cleared = sqlx::query(
+        "DELETE FROM game_boveda_market WHERE game_id = $1 AND slot_index = $2 AND card_id = $3"
+    )
+    .bind(game_id)
+    .bind(slot_index)
+    .bind(card_id)
+    .execute(&mut *conn)
+    .await?;
+    if cleared.rows_affected() == 0 {
+        return Err(anyhow::anyhow!("The card is no longer in Bóveda slot {}", slot_index));
+    }
+
+    let (inventory_id,): (Uuid,) = sqlx::query_as(
+        "INSERT INTO participant_cards (participant_id, card_id) VALUES ($1, $2) RETURNING id"
+    )
+    .bind(participant_id)
+    .bind(card_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    Ok(inventory_id)
+}
+
+// Finite Bank: keep the reserve in step with what the Bank pays and receives (no-op for an infinite Bank)
+async fn move_bank(conn: &mut PgConnection, game_id: Uuid, delta: bigdecimal::BigDecimal) -> Result<(), anyhow::Error> {
+    if delta.is_zero() {
+        return Ok(());
+    }
+    sqlx::query("UPDATE game_sessions SET bank_balance = bank_balance + $1 WHERE id = $2 AND bank_balance IS NOT NULL")
+        .bind(delta)
+        .bind(game_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+// Denomination mode: move the bills that back a payment. Participants in balance mode have no wallet
+// and are left alone; `None` is the Bank.
+async fn move_cash(conn: &mut PgConnection, from: Option<Uuid>, to: Option<Uuid>, amount: &bigdecimal::BigDecimal, bills: Option<&Bills>) -> Result<(), anyhow::Error> {
+    let payer = match from {
+        Some(id) => load_wallet(conn, id).await?,
+        None => None,
+    };
+    let payee = match to {
+        Some(id) => load_wallet(conn, id).await?,
+        None => None,
+    };
+    if payer.is_none() && payee.is_none() {
+        return Ok(());
+    }
+
+    let (payer, payee) = hand_over(payer.as_ref(), payee.as_ref(), whole_amount(amount)?, bills)?;
+    for (id, wallet) in [(from, payer), (to, payee)] {
+        if let (Some(id), Some(wallet)) = (id, wallet) {
+            sqlx::query("UPDATE game_participants SET bills = $1 WHERE id = $2")
+                .bind(sqlx::types::Json(wallet))
+                .bind(id)
+                .execute(&mut *conn)
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn load_wallet(conn: &mut PgConnection, participant_id: Uuid) -> Result<Option<Bills>, anyhow::Error> {
+    let row: Option<(Option<sqlx::types::Json<Bills>>,)> = sqlx::query_as(
+        "SELECT bills FROM game_participants WHERE id = $1 FOR UPDATE"
+    )
+    .bind(participant_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+    Ok(row.and_then(|(bills,)| bills).map(|b| b.0))
 }
@@ -48,21 +48,42 @@ async fn main() -> anyhow::Result<()> {
     let property_repo = Arc::new(infrastructure::postgres::property_repository::PostgresPropertyRepository::new(pool.clone()));
     let auction_repo = Arc::new(infrastructure::postgres::auction_repository::PostgresAuctionRepository::new(pool.clone()));
     let trade_repo = Arc::new(infrastructure::postgres::trade_repository::PostgresTradeRepository::new(pool.clone()));
+    let standings_repo = Arc::new(infrastructure::postgres::standings_repository::PostgresStandingsRepository::new(pool.clone()));
+    let loan_repo = Arc::new(infrastructure::postgres::loan_repository::PostgresLoanRepository::new(pool.clone()));
+    let payment_request_repo = Arc::new(infrastructure::postgres::payment_request_repository::PostgresPaymentRequestRepository::new(pool.clone()));
+    let bank_iou_repo = Arc::new(infrastructure::postgres::bank_iou_repository::PostgresBankIouRepository::new(pool.clone()));
+    let idempotency_repo = Arc::new(infrastructure::postgres::idempotency_repository::PostgresIdempotencyRepository::new(pool.clone()));
 
     // Services
     // Broadcast Channel
     let (tx, _rx) = tokio::sync::broadcast::channel(100);
 
+    // Sequenced event history for WebSocket resume
+    let event_log = Arc::new(infrastructure::event_log::EventLog::new(500));
+    event_log.spawn(tx.subscribe());
+
     let user_service = Arc::new(application::user_service::UserService::new(user_repo.clone()));
-    let transaction_service = Arc::new(application::transaction_service::TransactionService::new(transaction_repo.clone(), participant_repo.clone(), card_repo.clone(), tx.clone()));
-    let game_service = Arc::new(application::game_service::GameService::new(game_repo.clone(), participant_repo.clone(), transaction_service.clone(), tx.clone()));
-    let dice_service = Arc::new(application::dice_service::DiceService::new(dice_repo.clone(), participant_repo.clone(), transaction_service.clone(), tx.clone()));
+    let standings_service = Arc::new(application::standings_service::StandingsService::new(game_repo.clone(), participant_repo.clone(), property_repo.clone(), standings_repo.clone(), loan_repo.clone(), tx.clone()));
+    let transaction_service = Arc::new(application::transaction_service::TransactionService::new(transaction_repo.clone(), participant_repo.clone(), card_repo.clone(), game_repo.clone(), bank_iou_repo.clone(), standings_service.clone(), tx.clone()));
+    let bankruptcy_service = Arc::new(application::bankruptcy_service::BankruptcyService::new(game_repo.clone(), participant_repo.clone(), property_repo.clone(), transaction_service.clone(), standings_service.clone(), tx.clone()));
+    let loan_service = Arc::new(application::loan_service::LoanService::new(loan_repo.clone(), game_repo.clone(), participant_repo.clone(), transaction_service.clone(), bankruptcy_service.clone(), tx.clone()));
+    let game_service = Arc::new(application::game_service::GameService::new(game_repo.clone(), participant_repo.clone(), transaction_service.clone(), standings_service.clone(), loan_service.clone(), tx.clone()));
+    let dice_service = Arc::new(application::dice_service::DiceService::new(dice_repo.clone(), participant_repo.clone(), transaction_service.clone(), loan_service.clone(), tx.clone()));
     let roulette_service = Arc::new(application::roulette_service::RouletteService::new(roulette_repo.clone(), tx.clone())); // Removed transaction_repo, participant_repo
     let special_dice_service = Arc::new(application::special_dice_service::SpecialDiceService::new(special_dice_repo.clone(), tx.clone())); // Removed transaction_repo, participant_repo
-    let card_service = Arc::new(application::card_service::CardService::new(card_repo.clone(), transaction_repo.clone(), game_repo.clone(), participant_repo.clone(), tx.clone()));
+    let cash_service = Arc::new(application::cash_service::CashService::new(game_repo.clone(), participant_repo.clone(), transaction_service.clone(), tx.clone()));
+    let card_service = Arc::new(application::card_service::CardService::new(card_repo.clone(), game_repo.clone(), participant_repo.clone(), transaction_service.clone(), standings_service.clone(), cash_service.clone(), tx.clone()));
     let property_service = Arc::new(application::property_service::PropertyService::new(property_repo.clone(), participant_repo.clone(), transaction_service.clone(), tx.clone()));
-    let auction_service = Arc::new(application::auction_service::AuctionService::new(auction_repo.clone(), participant_repo.clone(), property_repo.clone(), transaction_service.clone(), tx.clone()));
+    let auction_service = Arc::new(application::auction_service::AuctionService::new(auction_repo.clone(), game_repo.clone(), participant_repo.clone(), property_repo.clone(), card_repo.clone(), transaction_service.clone(), tx.clone()));
     let trade_service = Arc::new(application::trade_service::TradeService::new(trade_repo.clone(), property_repo.clone(), card_repo.clone(), participant_repo.clone(), transaction_service.clone(), tx.clone()));
+    let payment_request_service = Arc::new(application::payment_request_service::PaymentRequestService::new(payment_request_repo.clone(), game_repo.clone(), participant_repo.clone(), transaction_service.clone(), tx.clone()));
+    let banker_service = Arc::new(application::banker_service::BankerService::new(game_repo.clone(), participant_repo.clone(), transaction_repo.clone(), payment_request_repo.clone(), loan_repo.clone(), tx.clone()));
+    let ledger_service = Arc::new(application::ledger_service::LedgerService::new(game_repo.clone(), participant_repo.clone(), transaction_repo.clone()));
+    let idempotency_service = Arc::new(application::idempotency_service::IdempotencyService::new(idempotency_repo.clone()));
+
+    // Background jobs
+    Arc::new(application::timed_game_scheduler::TimedGameScheduler::new(game_repo.clone(), standings_service.clone(), tx.clone())).spawn();
+    Arc::new(application::auction_scheduler::AuctionScheduler::new(auction_repo.clone(), auction_service.clone(), tx.clone())).spawn();
 
     let app_state = state::AppState {
         user_service,
@@ -75,10 +96,20 @@ async fn main() -> anyhow::Result<()> {
         property_service,
         auction_service,
         trade_service,
+        standings_service,
+        ledger_service,
+        loan_service,
+        payment_request_service,
+        banker_service,
+        cash_service,
+        idempotency_service,
         config: config.clone(),
-        tx,
+        event_log,
     };
 
+    // Replays the stored response when a retried POST carries a known Idempotency-Key
+    let idempotent = axum::middleware::from_fn_with_state(app_state.clone(), web::idempotency::idempotent);
+
     // Routes
     let app = Router::new()
         .route("/health", get(|| async { "OK" }))
@@ -102,15 +133,41 @@ async fn main() -> anyhow::Result<()> {
         .route("/games/:id/join", axum::routing::post(web::handlers::game::join_game))
         .route("/games/:id/leave", axum::routing::post(web::handlers::game::leave_game))
         .route("/games/:id/end-turn", axum::routing::post(web::handlers::game::end_turn))
+        .route("/games/:id/rematch", axum::routing::post(web::handlers::game::rematch))
+        .route("/games/:id/standings", axum::routing::get(web::handlers::game::get_standings))
         .route("/games/:id/participants", axum::routing::get(web::handlers::game::get_game_participants)
             .put(web::handlers::game::update_participant_position))
         // Transaction Routes
         .route("/games/:id/transactions", axum::routing::get(web::handlers::transaction::get_transactions)
-            .post(web::handlers::transaction::perform_transfer))
-        .route("/games/:id/transactions/:tx_id", axum::routing::delete(web::handlers::transaction::delete_transaction))
+            .merge(axum::routing::post(web::handlers::transaction::perform_transfer).layer(idempotent.clone())))
+        .route("/games/:id/transactions/multi", axum::routing::post(web::handlers::transaction::perform_multi_transfer))
+        .route("/games/:id/transactions/:tx_id", axum::routing::delete(web::handlers::transaction::reverse_transaction))
+        .route("/games/:id/transactions/:tx_id/reverse", axum::routing::post(web::handlers::transaction::reverse_transaction))
+        // Banker Routes
+        .route("/games/:id/banker", axum::routing::put(web::handlers::banker::set_banker))
+        .route("/games/:id/banker/dashboard", axum::routing::get(web::handlers::banker::get_dashboard))
+        .route("/games/:id/bank/ious", axum::routing::get(web::handlers::banker::get_ious))
+        // Cash Routes
+        .route("/games/:id/cash-mode", axum::routing::put(web::handlers::cash::set_cash_mode))
+        // Payment Request Routes
+        .route("/games/:id/payment-requests", axum::routing::get(web::handlers::payment_request::get_requests)
+            .post(web::handlers::payment_request::create_request))
+        .route("/games/:id/payment-requests/:request_id/approve", axum::routing::post(web::handlers::payment_request::approve_request))
+        .route("/games/:id/payment-requests/:request_id/reject", axum::routing::post(web::handlers::payment_request::reject_request))
+        // Ledger Routes
+        .route("/games/:id/ledger/reconcile", axum::routing::get(web::handlers::ledger::reconcile))
+        .route("/games/:id/ledger/export", axum::routing::get(web::handlers::ledger::export))
+        .route("/games/:id/ledger/repair", axum::routing::post(web::handlers::ledger::repair))
+        // Loan Routes
+        .route("/games/:id/loans", axum::routing::get(web::handlers::loan::get_loans)
+            .post(web::handlers::loan::request_loan))
+        .route("/games/:id/loans/:loan_id/approve", axum::routing::post(web::handlers::loan::approve_loan))
+        .route("/games/:id/loans/:loan_id/reject", axum::routing::post(web::handlers::loan::reject_loan))
+        .route("/games/:id/loans/:loan_id/repay", axum::routing::post(web::handlers::loan::repay_loan))
         .route("/games/:id/jackpot/claim", axum::routing::post(web::handlers::transaction::claim_jackpot))
+        .route("/games/:id/jackpot/history", axum::routing::get(web::handlers::transaction::get_jackpot_history))
         // Dice Routes
-        .route("/games/:id/roll", axum::routing::post(web::handlers::dice::roll_dice))
+        .route("/games/:id/roll", axum::routing::post(web::handlers::dice::roll_dice).layer(idempotent.clone()))
         .route("/games/:id/rolls", axum::routing::get(web::handlers::dice::get_history))
         // Roulette Routes
         .route("/games/:id/roulette", axum::routing::get(web::handlers::roulette::get_history)
@@ -121,7 +178,7 @@ async fn main() -> anyhow::Result<()> {
         // Card Routes
         .route("/games/:id/cards/draw", axum::routing::post(web::handlers::card::draw_card))
         .route("/games/:id/cards/market", axum::routing::get(web::handlers::card::get_market))
-        .route("/games/:id/cards/market/buy", axum::routing::post(web::handlers::card::buy_market_card))
+        .route("/games/:id/cards/market/buy", axum::routing::post(web::handlers::card::buy_market_card).layer(idempotent.clone()))
         .route("/games/:id/cards/market/exchange", axum::routing::post(web::handlers::card::exchange_market_card))
         .route("/games/:id/cards/inventory", axum::routing::get(web::handlers::card::get_inventory))
         .route("/games/:id/cards/use", axum::routing::post(web::handlers::card::use_card))
@@ -130,16 +187,20 @@ async fn main() -> anyhow::Result<()> {
         .route("/games/:id/cards/special-action", axum::routing::post(web::handlers::card::execute_special_action))
         // Property Routes
         .route("/games/:id/properties", axum::routing::get(web::handlers::property::get_game_properties))
-        .route("/games/:id/properties/:prop_id/buy", axum::routing::post(web::handlers::property::buy_property))
+        .route("/games/:id/properties/decline", axum::routing::post(web::handlers::auction::decline_purchase))
+        .route("/games/:id/properties/:prop_id/buy", axum::routing::post(web::handlers::property::buy_property).layer(idempotent.clone()))
         .route("/games/:id/properties/:prop_id/mortgage", axum::routing::post(web::handlers::property::mortgage_property))
         .route("/games/:id/properties/:prop_id/unmortgage", axum::routing::post(web::handlers::property::unmortgage_property))
-        .route("/games/:id/properties/:prop_id/buy-building", axum::routing::post(web::handlers::property::buy_building))
+        .route("/games/:id/properties/:prop_id/buy-building", axum::routing::post(web::handlers::property::buy_building).layer(idempotent.clone()))
         .route("/games/:id/properties/:prop_id/sell-building", axum::routing::post(web::handlers::property::sell_building))
         .route("/properties", axum::routing::get(web::handlers::property::get_all_properties))
         // Auction Routes
         .route("/games/:id/auctions", axum::routing::post(web::handlers::auction::start_auction)
             .get(web::handlers::auction::get_active_auction))
-        .route("/games/:id/auctions/:auction_id/bid", axum::routing::post(web::handlers::auction::place_bid))
+        .route("/games/:id/auctions/:auction_id/bid", axum::routing::post(web::handlers::auction::place_bid).layer(idempotent.clone()))
+        .route("/games/:id/auctions/:auction_id/bids", axum::routing::get(web::handlers::auction::get_bids))
+        .route("/games/:id/auctions/:auction_id/pass", axum::routing::post(web::handlers::auction::pass))
+        .route("/games/:id/auctions/:auction_id/withdraw", axum::routing::post(web::handlers::auction::withdraw))
         .route("/games/:id/auctions/:auction_id/end", axum::routing::post(web::handlers::auction::end_auction))
         // Trade Routes
         .route("/games/:id/trades", axum::routing::get(web::handlers::trade::get_trades)
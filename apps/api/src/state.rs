@@ -10,10 +10,16 @@ use crate::application::{
     property_service::PropertyService,
     auction_service::AuctionService,
     trade_service::TradeService,
+    standings_service::StandingsService,
+    ledger_service::LedgerService,
+    loan_service::LoanService,
+    payment_request_service::PaymentRequestService,
+    banker_service::BankerService,
+    cash_service::CashService,
+    idempotency_service::IdempotencyService,
 };
 use crate::config::Config;
-use tokio::sync::broadcast;
-use crate::domain::events::GameEvent;
+use crate::infrastructure::event_log::EventLog;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -27,6 +33,13 @@ pub struct AppState {
     pub property_service: Arc<PropertyService>,
     pub auction_service: Arc<AuctionService>,
     pub trade_service: Arc<TradeService>,
+    pub standings_service: Arc<StandingsService>,
+    pub ledger_service: Arc<LedgerService>,
+    pub loan_service: Arc<LoanService>,
+    pub payment_request_service: Arc<PaymentRequestService>,
+    pub banker_service: Arc<BankerService>,
+    pub cash_service: Arc<CashService>,
+    pub idempotency_service: Arc<IdempotencyService>,
     pub config: Config,
-    pub tx: broadcast::Sender<GameEvent>,
+    pub event_log: Arc<EventLog>,
 }
@@ -6,22 +6,32 @@ use axum::{
 };
 use uuid::Uuid;
 use crate::state::AppState;
-use crate::domain::entities::Auction;
+use crate::domain::entities::NewAuction;
+use crate::web::handlers::error_status;
+use crate::web::extractors::AuthorizedUser;
 use bigdecimal::BigDecimal;
 
-#[derive(serde::Deserialize)]
-pub struct StartAuctionRequest {
-    pub property_id: Uuid,
+pub async fn start_auction(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth_user: AuthorizedUser,
+    Json(payload): Json<NewAuction>,
+) -> impl IntoResponse {
+    match state.auction_service.start_auction(game_id, auth_user.user_id, payload).await {
+        Ok(auction) => (StatusCode::OK, Json(auction)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
 }
 
-pub async fn start_auction(
+// Pass on buying the property at the player's position: it goes to auction instead
+pub async fn decline_purchase(
     State(state): State<AppState>,
     Path(game_id): Path<Uuid>,
-    Json(payload): Json<StartAuctionRequest>,
+    auth_user: AuthorizedUser,
 ) -> impl IntoResponse {
-    match state.auction_service.start_auction(game_id, payload.property_id).await {
+    match state.auction_service.decline_purchase(game_id, auth_user.user_id).await {
         Ok(auction) => (StatusCode::OK, Json(auction)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -31,35 +41,67 @@ pub async fn get_active_auction(
 ) -> impl IntoResponse {
     match state.auction_service.get_active_auction(game_id).await {
         Ok(auction) => (StatusCode::OK, Json(auction)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
 #[derive(serde::Deserialize)]
 pub struct PlaceBidRequest {
-    #[serde(rename = "bidder_user_id")]
-    pub user_id: Uuid, // Bidder
     pub amount: BigDecimal,
 }
 
 pub async fn place_bid(
     State(state): State<AppState>,
     Path((_game_id, auction_id)): Path<(Uuid, Uuid)>,
+    auth_user: AuthorizedUser,
     Json(payload): Json<PlaceBidRequest>,
 ) -> impl IntoResponse {
-    match state.auction_service.place_bid(auction_id, payload.user_id, payload.amount).await {
+    match state.auction_service.place_bid(auction_id, auth_user.user_id, payload.amount).await {
+        Ok(auction) => (StatusCode::OK, Json(auction)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+pub async fn get_bids(
+    State(state): State<AppState>,
+    Path((_game_id, auction_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match state.auction_service.get_bids(auction_id).await {
+        Ok(bids) => (StatusCode::OK, Json(bids)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
+    }
+}
+
+pub async fn pass(
+    State(state): State<AppState>,
+    Path((_game_id, auction_id)): Path<(Uuid, Uuid)>,
+    auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.auction_service.pass(auction_id, auth_user.user_id).await {
+        Ok(auction) => (StatusCode::OK, Json(auction)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+pub async fn withdraw(
+    State(state): State<AppState>,
+    Path((_game_id, auction_id)): Path<(Uuid, Uuid)>,
+    auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.auction_service.withdraw(auction_id, auth_user.user_id).await {
         Ok(auction) => (StatusCode::OK, Json(auction)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
-// End Auction (Usually triggered automatically or by host, but maybe exposure needed)
+// End Auction early (host only; the auction scheduler closes it when the clock runs out)
 pub async fn end_auction(
     State(state): State<AppState>,
     Path((_game_id, auction_id)): Path<(Uuid, Uuid)>,
+    auth_user: AuthorizedUser,
 ) -> impl IntoResponse {
-    match state.auction_service.end_auction(auction_id).await {
+    match state.auction_service.end_auction(auction_id, auth_user.user_id).await {
         Ok(auction) => (StatusCode::OK, Json(auction)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
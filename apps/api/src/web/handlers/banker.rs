@@ -0,0 +1,49 @@
+use axum::{
+    extract::{State, Json, Path},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::state::AppState;
+use crate::web::handlers::error_status;
+use crate::web::extractors::AuthorizedUser;
+
+#[derive(Deserialize)]
+pub struct SetBankerRequest {
+    pub user_id: Uuid,
+}
+
+pub async fn set_banker(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth_user: AuthorizedUser,
+    Json(payload): Json<SetBankerRequest>,
+) -> impl IntoResponse {
+    match state.banker_service.set_banker(game_id, auth_user.user_id, payload.user_id).await {
+        Ok(banker_user_id) => (StatusCode::OK, Json(serde_json::json!({ "banker_user_id": banker_user_id }))).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+pub async fn get_dashboard(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.banker_service.dashboard(game_id, auth_user.user_id).await {
+        Ok(dashboard) => (StatusCode::OK, Json(dashboard)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+pub async fn get_ious(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    _auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.transaction_service.get_bank_ious(game_id).await {
+        Ok(ious) => (StatusCode::OK, Json(ious)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
+    }
+}
@@ -7,6 +7,7 @@ use axum::{
 use uuid::Uuid;
 use serde::Deserialize;
 use crate::state::AppState;
+use crate::web::handlers::error_status;
 use crate::web::extractors::AuthorizedUser;
 
 // -- DTOs --
@@ -40,7 +41,7 @@ pub async fn draw_card(
 ) -> impl IntoResponse {
     match state.card_service.draw_card(game_id, auth_user.user_id, &payload.card_type).await {
         Ok(card) => (StatusCode::OK, Json(card)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
@@ -51,7 +52,7 @@ pub async fn get_market(
 ) -> impl IntoResponse {
     match state.card_service.get_market(game_id).await {
         Ok(market) => (StatusCode::OK, Json(market)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
@@ -63,7 +64,7 @@ pub async fn buy_market_card(
 ) -> impl IntoResponse {
     match state.card_service.buy_market_card(game_id, auth_user.user_id, payload.slot_index).await {
         Ok(card) => (StatusCode::OK, Json(card)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
@@ -76,7 +77,7 @@ pub async fn exchange_market_card(
     // Exchange requires validation? Generally driven by Special Dice.
     match state.card_service.exchange_market_card(game_id, payload.slot_index).await {
         Ok(market) => (StatusCode::OK, Json(market)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
@@ -87,7 +88,7 @@ pub async fn get_inventory(
 ) -> impl IntoResponse {
     match state.card_service.get_inventory(game_id, auth_user.user_id).await {
         Ok(inventory) => (StatusCode::OK, Json(inventory)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
@@ -99,7 +100,7 @@ pub async fn use_card(
 ) -> impl IntoResponse {
     match state.card_service.use_card(game_id, auth_user.user_id, payload.inventory_id).await {
         Ok(_) => (StatusCode::OK, Json("Success")).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
@@ -110,7 +111,7 @@ pub async fn discard_card(
 ) -> impl IntoResponse {
     match state.card_service.discard_card(game_id, auth_user.user_id, inventory_id).await {
         Ok(_) => (StatusCode::OK, Json("Success")).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
@@ -123,7 +124,7 @@ pub async fn get_all_inventories(
 ) -> impl IntoResponse {
     match state.card_service.get_all_inventories(game_id).await {
         Ok(inv) => (StatusCode::OK, Json(inv)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
@@ -148,6 +149,6 @@ pub async fn execute_special_action(
         payload.my_card_id
     ).await {
         Ok(_) => (StatusCode::OK, Json("Success")).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
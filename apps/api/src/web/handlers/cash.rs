@@ -0,0 +1,28 @@
+use axum::{
+    extract::{State, Json, Path},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::domain::entities::CashMode;
+use crate::state::AppState;
+use crate::web::handlers::error_status;
+use crate::web::extractors::AuthorizedUser;
+
+#[derive(Deserialize)]
+pub struct SetCashModeRequest {
+    pub cash_mode: CashMode,
+}
+
+pub async fn set_cash_mode(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth_user: AuthorizedUser,
+    Json(payload): Json<SetCashModeRequest>,
+) -> impl IntoResponse {
+    match state.cash_service.set_cash_mode(game_id, auth_user.user_id, payload.cash_mode).await {
+        Ok(game) => (StatusCode::OK, Json(game)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
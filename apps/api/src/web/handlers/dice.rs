@@ -6,6 +6,7 @@ use axum::{
 use serde::Deserialize;
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::web::handlers::error_status;
 use crate::web::extractors::AuthorizedUser;
 
 #[derive(Deserialize)]
@@ -27,7 +28,7 @@ pub async fn roll_dice(
     }
     match state.dice_service.roll_dice(game_id, auth_user.user_id, payload.sides, payload.count, payload.auto_salary).await {
         Ok(roll) => (StatusCode::CREATED, Json(roll)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
@@ -67,6 +68,6 @@ pub async fn get_history(
             }).collect();
             (StatusCode::OK, Json(response)).into_response()
         },
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
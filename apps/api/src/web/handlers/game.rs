@@ -6,13 +6,22 @@ use axum::{
 use serde::Deserialize;
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::web::handlers::error_status;
 use crate::web::extractors::AuthorizedUser;
+use crate::domain::entities::HouseRules;
 
 #[derive(Deserialize)]
 pub struct UpdateGameRequest {
     pub name: Option<String>,
     pub status: Option<String>,
     pub initiative_rolls: Option<std::collections::HashMap<Uuid, i32>>,
+    pub house_rules: Option<HouseRules>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct RematchRequest {
+    #[serde(default)]
+    pub reverse_turn_order: bool,
 }
 
 #[derive(Deserialize)]
@@ -31,7 +40,7 @@ pub async fn create_game(
 ) -> impl IntoResponse {
     match state.game_service.create_game(auth_user.user_id).await {
         Ok(game) => (StatusCode::CREATED, Json(game)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
@@ -47,7 +56,7 @@ pub async fn join_game(
 ) -> impl IntoResponse {
     match state.game_service.join_game(game_id, auth_user.user_id).await {
         Ok(participant) => (StatusCode::OK, Json(participant)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -58,7 +67,7 @@ pub async fn join_game_by_code(
 ) -> impl IntoResponse {
     match state.game_service.join_game_with_code(payload.code, auth_user.user_id).await {
         Ok(participant) => (StatusCode::OK, Json(participant)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -69,7 +78,7 @@ pub async fn leave_game(
 ) -> impl IntoResponse {
     match state.game_service.leave_game(game_id, auth_user.user_id).await {
         Ok(_) => (StatusCode::OK, "Left game").into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -79,9 +88,9 @@ pub async fn update_game(
     auth_user: AuthorizedUser,
     Json(payload): Json<UpdateGameRequest>,
 ) -> impl IntoResponse {
-    match state.game_service.update_game(game_id, auth_user.user_id, payload.name, payload.status, payload.initiative_rolls).await {
+    match state.game_service.update_game(game_id, auth_user.user_id, payload.name, payload.status, payload.initiative_rolls, payload.house_rules).await {
         Ok(game) => (StatusCode::OK, Json(game)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -92,7 +101,7 @@ pub async fn delete_game(
 ) -> impl IntoResponse {
      match state.game_service.delete_game(game_id, auth_user.user_id).await {
         Ok(_) => (StatusCode::NO_CONTENT, ()).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -121,12 +130,12 @@ pub async fn get_game_participants(
 pub async fn update_participant_position(
     State(state): State<AppState>,
     Path(game_id): Path<Uuid>,
-    _auth: AuthorizedUser, // Could check if host/admin
+    auth_user: AuthorizedUser,
     Json(payload): Json<UpdatePositionRequest>,
 ) -> impl IntoResponse {
-    match state.game_service.update_participant_position(game_id, payload.user_id, payload.position).await {
+    match state.game_service.update_participant_position(game_id, auth_user.user_id, payload.user_id, payload.position).await {
         Ok(_) => (StatusCode::OK, "Position updated").into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -137,6 +146,30 @@ pub async fn end_turn(
 ) -> impl IntoResponse {
     match state.game_service.end_turn(game_id, auth_user.user_id).await {
         Ok(game) => (StatusCode::OK, Json(game)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+pub async fn get_standings(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    _auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.standings_service.get_standings(game_id).await {
+        Ok(standings) => (StatusCode::OK, Json(standings)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
+    }
+}
+
+pub async fn rematch(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth_user: AuthorizedUser,
+    payload: Option<Json<RematchRequest>>,
+) -> impl IntoResponse {
+    let Json(payload) = payload.unwrap_or_default();
+    match state.game_service.rematch(game_id, auth_user.user_id, payload.reverse_turn_order).await {
+        Ok(game) => (StatusCode::CREATED, Json(game)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
@@ -0,0 +1,64 @@
+use axum::{
+    body::Body,
+    extract::{State, Json, Path, Query},
+    http::{header, StatusCode},
+    response::IntoResponse,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::domain::entities::ExportFormat;
+use crate::state::AppState;
+use crate::web::handlers::error_status;
+use crate::web::extractors::AuthorizedUser;
+
+pub async fn reconcile(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.ledger_service.reconcile(game_id, auth_user.user_id).await {
+        Ok(report) => (StatusCode::OK, Json(report)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+pub async fn repair(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.ledger_service.repair(game_id, auth_user.user_id).await {
+        Ok(report) => (StatusCode::OK, Json(report)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+pub async fn export(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth_user: AuthorizedUser,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
+    let (content_type, extension) = match query.format {
+        ExportFormat::Csv => ("text/csv; charset=utf-8", "csv"),
+        ExportFormat::Jsonl => ("application/x-ndjson", "jsonl"),
+    };
+
+    match state.ledger_service.export(game_id, auth_user.user_id, query.format).await {
+        Ok(lines) => (
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, content_type.to_string()),
+                (header::CONTENT_DISPOSITION, format!("attachment; filename=\"ledger-{}.{}\"", game_id, extension)),
+            ],
+            Body::from_stream(lines),
+        ).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
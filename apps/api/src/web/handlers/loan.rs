@@ -0,0 +1,74 @@
+use axum::{
+    extract::{State, Json, Path},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+use bigdecimal::BigDecimal;
+use crate::state::AppState;
+use crate::web::handlers::error_status;
+use crate::domain::entities::NewLoan;
+use crate::web::extractors::AuthorizedUser;
+
+pub async fn get_loans(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    _auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.loan_service.get_loans(game_id).await {
+        Ok(loans) => (StatusCode::OK, Json(loans)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
+    }
+}
+
+pub async fn request_loan(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth_user: AuthorizedUser,
+    Json(payload): Json<NewLoan>,
+) -> impl IntoResponse {
+    match state.loan_service.request_loan(game_id, auth_user.user_id, payload).await {
+        Ok(loan) => (StatusCode::CREATED, Json(loan)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+pub async fn approve_loan(
+    State(state): State<AppState>,
+    Path((game_id, loan_id)): Path<(Uuid, Uuid)>,
+    auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.loan_service.approve_loan(game_id, loan_id, auth_user.user_id).await {
+        Ok(loan) => (StatusCode::OK, Json(loan)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+pub async fn reject_loan(
+    State(state): State<AppState>,
+    Path((game_id, loan_id)): Path<(Uuid, Uuid)>,
+    auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.loan_service.reject_loan(game_id, loan_id, auth_user.user_id).await {
+        Ok(loan) => (StatusCode::OK, Json(loan)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+#[derive(serde::Deserialize, Default)]
+pub struct RepayLoanRequest {
+    pub amount: Option<BigDecimal>, // Omitted = repay everything outstanding
+}
+
+pub async fn repay_loan(
+    State(state): State<AppState>,
+    Path((game_id, loan_id)): Path<(Uuid, Uuid)>,
+    auth_user: AuthorizedUser,
+    payload: Option<Json<RepayLoanRequest>>,
+) -> impl IntoResponse {
+    let amount = payload.map(|Json(p)| p).unwrap_or_default().amount;
+    match state.loan_service.repay(game_id, loan_id, auth_user.user_id, amount).await {
+        Ok(loan) => (StatusCode::OK, Json(loan)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
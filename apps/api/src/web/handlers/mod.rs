@@ -9,3 +9,18 @@ pub mod ws;
 pub mod property;
 pub mod auction;
 pub mod trade;
+pub mod ledger;
+pub mod loan;
+pub mod payment_request;
+pub mod banker;
+pub mod cash;
+
+/// Status for a failed service call: lost optimistic-concurrency races are 409 so clients
+/// reload and retry; everything else keeps the handler's usual status.
+pub fn error_status(e: &anyhow::Error, fallback: axum::http::StatusCode) -> axum::http::StatusCode {
+    if e.downcast_ref::<crate::domain::errors::ConcurrencyConflict>().is_some() {
+        axum::http::StatusCode::CONFLICT
+    } else {
+        fallback
+    }
+}
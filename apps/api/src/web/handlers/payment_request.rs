@@ -0,0 +1,55 @@
+use axum::{
+    extract::{State, Json, Path},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+use crate::state::AppState;
+use crate::web::handlers::error_status;
+use crate::domain::entities::NewPaymentRequest;
+use crate::web::extractors::AuthorizedUser;
+
+pub async fn get_requests(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    _auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.payment_request_service.get_requests(game_id).await {
+        Ok(requests) => (StatusCode::OK, Json(requests)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
+    }
+}
+
+pub async fn create_request(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    auth_user: AuthorizedUser,
+    Json(payload): Json<NewPaymentRequest>,
+) -> impl IntoResponse {
+    match state.payment_request_service.create_request(game_id, auth_user.user_id, payload).await {
+        Ok(request) => (StatusCode::CREATED, Json(request)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+pub async fn approve_request(
+    State(state): State<AppState>,
+    Path((game_id, request_id)): Path<(Uuid, Uuid)>,
+    auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.payment_request_service.approve_request(game_id, request_id, auth_user.user_id).await {
+        Ok(request) => (StatusCode::OK, Json(request)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+pub async fn reject_request(
+    State(state): State<AppState>,
+    Path((game_id, request_id)): Path<(Uuid, Uuid)>,
+    auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.payment_request_service.reject_request(game_id, request_id, auth_user.user_id).await {
+        Ok(request) => (StatusCode::OK, Json(request)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
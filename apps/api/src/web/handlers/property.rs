@@ -6,14 +6,14 @@ use axum::{
 };
 use uuid::Uuid;
 use crate::state::AppState;
-use crate::domain::entities::{Property, ParticipantProperty};
+use crate::web::handlers::error_status;
 
 pub async fn get_all_properties(
     State(state): State<AppState>,
 ) -> impl IntoResponse {
     match state.property_service.get_all_properties().await {
         Ok(props) => (StatusCode::OK, Json(props)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
@@ -23,7 +23,7 @@ pub async fn get_game_properties(
 ) -> impl IntoResponse {
     match state.property_service.get_game_ownership(game_id).await {
         Ok(props) => (StatusCode::OK, Json(props)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
@@ -39,7 +39,7 @@ pub async fn buy_property(
 ) -> impl IntoResponse {
     match state.property_service.buy_property(game_id, payload.user_id, property_id).await {
         Ok(pp) => (StatusCode::OK, Json(pp)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -55,7 +55,7 @@ pub async fn mortgage_property(
 ) -> impl IntoResponse {
     match state.property_service.mortgage_property(game_id, payload.user_id, property_id).await {
         Ok(pp) => (StatusCode::OK, Json(pp)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -66,7 +66,7 @@ pub async fn unmortgage_property(
 ) -> impl IntoResponse {
     match state.property_service.unmortgage_property(game_id, payload.user_id, property_id).await {
         Ok(pp) => (StatusCode::OK, Json(pp)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -77,7 +77,7 @@ pub async fn buy_building(
 ) -> impl IntoResponse {
     match state.property_service.buy_building(game_id, payload.user_id, property_id).await {
         Ok(pp) => (StatusCode::OK, Json(pp)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -88,6 +88,6 @@ pub async fn sell_building(
 ) -> impl IntoResponse {
     match state.property_service.sell_building(game_id, payload.user_id, property_id).await {
         Ok(pp) => (StatusCode::OK, Json(pp)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
@@ -6,6 +6,7 @@ use axum::{
 };
 use uuid::Uuid;
 use crate::state::AppState;
+use crate::web::handlers::error_status;
 use crate::domain::entities::Trade;
 
 // Re-using Trade entity for create request for simplicity, 
@@ -46,7 +47,7 @@ pub async fn create_trade(
 
     match state.trade_service.create_trade(trade).await {
         Ok(t) => (StatusCode::OK, Json(t)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -62,7 +63,7 @@ pub async fn accept_trade(
 ) -> impl IntoResponse {
     match state.trade_service.accept_trade(trade_id, payload.user_id).await {
         Ok(t) => (StatusCode::OK, Json(t)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -73,7 +74,7 @@ pub async fn reject_trade(
 ) -> impl IntoResponse {
     match state.trade_service.reject_trade(trade_id, payload.user_id).await {
         Ok(t) => (StatusCode::OK, Json(t)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
@@ -83,6 +84,6 @@ pub async fn get_trades(
 ) -> impl IntoResponse {
     match state.trade_service.get_active_trades(game_id).await {
         Ok(t) => (StatusCode::OK, Json(t)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
@@ -1,5 +1,5 @@
 use axum::{
-    extract::{State, Json, Path},
+    extract::{State, Json, Path, Query},
     http::StatusCode,
     response::IntoResponse,
 };
@@ -7,7 +7,9 @@ use serde::{Deserialize};
 use uuid::Uuid;
 use bigdecimal::BigDecimal;
 use crate::state::AppState;
+use crate::web::handlers::error_status;
 use crate::web::extractors::AuthorizedUser;
+use crate::domain::entities::{MultiTransferDirection, TransactionCategory, TransactionFilter, TransferDetails};
 
 #[derive(Deserialize)]
 pub struct TransferRequest {
@@ -15,45 +17,99 @@ pub struct TransferRequest {
     pub to_participant_id: Option<Uuid>,
     pub amount: BigDecimal,
     pub description: Option<String>,
+    // Manual transfers default to "manual"; clients pick rent/tax etc. when known
+    #[serde(default)]
+    pub category: TransactionCategory,
+    pub property_id: Option<Uuid>,
+    pub card_id: Option<Uuid>,
+}
+
+#[derive(Deserialize)]
+pub struct MultiTransferRequest {
+    pub participant_id: Uuid,
+    pub direction: MultiTransferDirection,
+    // Empty or missing: everybody else in the game
+    #[serde(default)]
+    pub counterparty_ids: Vec<Uuid>,
+    pub amount_each: BigDecimal,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub category: TransactionCategory,
+    pub card_id: Option<Uuid>,
 }
 
 pub async fn perform_transfer(
     State(state): State<AppState>,
     Path(game_id): Path<Uuid>,
-    _auth_user: AuthorizedUser, // Ensure user is authenticated, though we don't use the ID yet for permission check
+    auth_user: AuthorizedUser,
     Json(payload): Json<TransferRequest>,
 ) -> impl IntoResponse {
-    match state.transaction_service.transfer(
-        game_id, 
+    match state.transaction_service.user_transfer(
+        game_id,
+        auth_user.user_id,
         payload.from_participant_id, 
         payload.to_participant_id, 
         payload.amount, 
-        payload.description
+        TransferDetails {
+            category: payload.category,
+            description: payload.description,
+            property_id: payload.property_id,
+            card_id: payload.card_id,
+            ..Default::default()
+        }
     ).await {
         Ok(tx) => (StatusCode::CREATED, Json(tx)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
-pub async fn delete_transaction(
+pub async fn perform_multi_transfer(
     State(state): State<AppState>,
-    Path((_game_id, tx_id)): Path<(Uuid, Uuid)>, // game_id in path for REST structure, but unneeded for logic
-    _auth_user: AuthorizedUser,
+    Path(game_id): Path<Uuid>,
+    auth_user: AuthorizedUser,
+    Json(payload): Json<MultiTransferRequest>,
+) -> impl IntoResponse {
+    let details = TransferDetails {
+        category: payload.category,
+        description: payload.description,
+        card_id: payload.card_id,
+        ..Default::default()
+    };
+    match state.transaction_service.transfer_multi(
+        game_id,
+        auth_user.user_id,
+        payload.participant_id,
+        payload.direction,
+        payload.counterparty_ids,
+        payload.amount_each,
+        details,
+    ).await {
+        Ok(txs) => (StatusCode::CREATED, Json(txs)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+// DELETE keeps its URL for existing clients but no longer removes anything: it books a reversal
+pub async fn reverse_transaction(
+    State(state): State<AppState>,
+    Path((game_id, tx_id)): Path<(Uuid, Uuid)>,
+    auth_user: AuthorizedUser,
 ) -> impl IntoResponse {
-    match state.transaction_service.delete_transaction(tx_id).await {
-        Ok(_) => (StatusCode::NO_CONTENT, ()).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    match state.transaction_service.reverse_transaction(game_id, tx_id, auth_user.user_id).await {
+        Ok(reversal) => (StatusCode::OK, Json(reversal)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
     }
 }
 
 pub async fn get_transactions(
     State(state): State<AppState>,
     Path(game_id): Path<Uuid>,
+    Query(filter): Query<TransactionFilter>,
     _auth_user: AuthorizedUser,
 ) -> impl IntoResponse {
-     match state.transaction_service.get_transactions(game_id).await {
+     match state.transaction_service.get_transactions(game_id, filter).await {
         Ok(txs) => (StatusCode::OK, Json(txs)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
 
@@ -64,6 +120,17 @@ pub async fn claim_jackpot(
 ) -> impl IntoResponse {
     match state.transaction_service.claim_jackpot(game_id, auth_user.user_id).await {
         Ok(tx) => (StatusCode::OK, Json(tx)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        Err(e) => (error_status(&e, StatusCode::BAD_REQUEST), e.to_string()).into_response(),
+    }
+}
+
+pub async fn get_jackpot_history(
+    State(state): State<AppState>,
+    Path(game_id): Path<Uuid>,
+    _auth_user: AuthorizedUser,
+) -> impl IntoResponse {
+    match state.transaction_service.get_jackpot_history(game_id).await {
+        Ok(history) => (StatusCode::OK, Json(history)).into_response(),
+        Err(e) => (error_status(&e, StatusCode::INTERNAL_SERVER_ERROR), e.to_string()).into_response(),
     }
 }
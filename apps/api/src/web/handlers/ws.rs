@@ -8,11 +8,16 @@ use axum::{
 
 use serde::Deserialize;
 use crate::state::AppState;
+use crate::domain::entities::GameStateSnapshot;
+use crate::domain::events::{GameEvent, SequencedEvent};
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
 #[derive(Deserialize)]
 pub struct WsParams {
     game_id: Uuid,
+    // Last sequence number the client processed, to resume after a reconnect
+    last_seq: Option<u64>,
 }
 
 pub async fn ws_handler(
@@ -20,22 +25,96 @@ pub async fn ws_handler(
     State(state): State<AppState>,
     Query(params): Query<WsParams>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket(socket, state, params.game_id))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, params.game_id, params.last_seq))
 }
 
-async fn handle_socket(mut socket: WebSocket, state: AppState, game_id: Uuid) {
-    let mut rx = state.tx.subscribe();
+async fn handle_socket(mut socket: WebSocket, state: AppState, game_id: Uuid, last_seq: Option<u64>) {
+    // Subscribe before reading state so nothing falls between the catch-up and the live stream
+    let mut rx = state.event_log.subscribe();
 
-    // Loop to receive messages from the broadcast channel
-    while let Ok(event) = rx.recv().await {
-        // Only send events related to the connected game
-        if event.game_id() == game_id {
-            if let Ok(msg_json) = serde_json::to_string(&event) {
-                if socket.send(Message::Text(msg_json)).await.is_err() {
-                    // Client disconnected
-                    break;
+    let backlog = last_seq.and_then(|seq| state.event_log.events_since(game_id, seq));
+
+    let mut sent_seq = match (last_seq, backlog) {
+        // Resume: replay just the gap
+        (Some(seq), Some(events)) => {
+            let mut sent = seq;
+            for event in events {
+                sent = event.seq;
+                if send_event(&mut socket, &event).await.is_err() {
+                    return;
+                }
+            }
+            sent
+        }
+        // Fresh connection or gap too old: send the full state
+        _ => {
+            let seq = state.event_log.last_seq(game_id);
+            match build_snapshot(&state, game_id, seq).await {
+                Ok(snapshot) => {
+                    let event = GameEvent::GameStateSnapshot(Box::new(snapshot));
+                    if let Ok(msg_json) = serde_json::to_string(&event) {
+                        if socket.send(Message::Text(msg_json)).await.is_err() {
+                            return;
+                        }
+                    }
                 }
+                Err(e) => tracing::error!("Failed to build snapshot for game {}: {}", game_id, e),
             }
+            seq
+        }
+    };
+
+    // Loop to receive messages from the broadcast channel
+    loop {
+        let event = match rx.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                // Client will notice the jump in `seq` and can reconnect with last_seq to resync
+                tracing::warn!("WebSocket for game {} lagged, skipped {} events", game_id, skipped);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+
+        // Only send events related to the connected game, and never twice
+        if event.event.game_id() != game_id || event.seq <= sent_seq {
+            continue;
+        }
+
+        sent_seq = event.seq;
+        if send_event(&mut socket, &event).await.is_err() {
+            // Client disconnected
+            break;
         }
     }
 }
+
+async fn send_event(socket: &mut WebSocket, event: &SequencedEvent) -> Result<(), axum::Error> {
+    match serde_json::to_string(event) {
+        Ok(msg_json) => socket.send(Message::Text(msg_json)).await,
+        Err(e) => {
+            tracing::error!("Failed to serialize event: {}", e);
+            Ok(())
+        }
+    }
+}
+
+async fn build_snapshot(state: &AppState, game_id: Uuid, last_seq: u64) -> Result<GameStateSnapshot, anyhow::Error> {
+    let game = state.game_service.get_game(game_id).await?;
+    let participants = state.game_service.get_participants_with_details(game_id).await?;
+    let ownership = state.property_service.get_game_ownership(game_id).await?;
+    let market = state.card_service.get_market(game_id).await?;
+    let active_auction = state.auction_service.get_active_auction(game_id).await?;
+    let pending_trades = state.trade_service.get_active_trades(game_id).await?;
+
+    Ok(GameStateSnapshot {
+        jackpot_balance: game.jackpot_balance.clone(),
+        game,
+        participants,
+        ownership,
+        market,
+        active_auction,
+        pending_trades,
+        last_seq,
+    })
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use axum::{
+    body::Body,
+    extract::{Path, Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use crate::application::idempotency_service::IdempotencyClaim;
+use crate::state::AppState;
+use crate::web::extractors::AuthorizedUser;
+
+pub const IDEMPOTENCY_KEY: &str = "Idempotency-Key";
+pub const IDEMPOTENT_REPLAYED: &str = "Idempotent-Replayed";
+
+// Stored responses are small JSON bodies; anything bigger is not worth keeping
+const MAX_STORED_BODY: usize = 1024 * 1024;
+
+/// Route layer for money-moving POSTs: the first response per (game, Idempotency-Key) is stored
+/// and returned again on retries instead of executing the action twice.
+/// Requests without the header pass straight through.
+pub async fn idempotent(
+    State(state): State<AppState>,
+    Path(params): Path<HashMap<String, String>>,
+    auth_user: AuthorizedUser,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY) else {
+        return next.run(request).await;
+    };
+    let Ok(key) = key.to_str().map(str::to_string) else {
+        return (StatusCode::BAD_REQUEST, "Invalid Idempotency-Key header".to_string()).into_response();
+    };
+    let Some(game_id) = params.get("id").and_then(|id| uuid::Uuid::parse_str(id).ok()) else {
+        return (StatusCode::BAD_REQUEST, "Invalid game id".to_string()).into_response();
+    };
+    let endpoint = format!("{} {}", request.method(), request.uri().path());
+
+    // The body is fingerprinted so a key reused for a different payload is refused, not replayed
+    let (parts, body) = request.into_parts();
+    let body = match axum::body::to_bytes(body, MAX_STORED_BODY).await {
+        Ok(body) => body,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+
+    let service = &state.idempotency_service;
+    match service.begin(game_id, auth_user.user_id, &key, &endpoint, &body).await {
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Ok(IdempotencyClaim::InProgress) => {
+            (StatusCode::CONFLICT, "A request with this Idempotency-Key is still being processed".to_string()).into_response()
+        }
+        Ok(IdempotencyClaim::Mismatch) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, "Idempotency-Key was already used for a different request".to_string()).into_response()
+        }
+        Ok(IdempotencyClaim::Replay(record)) => {
+            let status = record.status_code
+                .and_then(|s| u16::try_from(s).ok())
+                .and_then(|s| StatusCode::from_u16(s).ok())
+                .unwrap_or(StatusCode::OK);
+            let mut response = Response::new(Body::from(record.response_body.unwrap_or_default()));
+            *response.status_mut() = status;
+            if let Some(value) = record.content_type.and_then(|ct| HeaderValue::from_str(&ct).ok()) {
+                response.headers_mut().insert(header::CONTENT_TYPE, value);
+            }
+            response.headers_mut().insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+            response
+        }
+        Ok(IdempotencyClaim::Fresh) => {
+            let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+            let (parts, body) = response.into_parts();
+
+            // From here on the action may have committed, so the key is only given back on a lost race
+            let bytes = match axum::body::to_bytes(body, MAX_STORED_BODY).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    let (status, message) = (StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+                    if let Err(e) = service.complete(game_id, &key, status.as_u16(), None, message.clone().into_bytes()).await {
+                        tracing::warn!("Failed to store idempotent response for {}: {}", key, e);
+                    }
+                    return (status, message).into_response();
+                }
+            };
+
+            // A lost concurrency race rolled back: let the client retry for real
+            if parts.status == StatusCode::CONFLICT {
+                let _ = service.release(game_id, &key).await;
+            } else {
+                let content_type = parts.headers.get(header::CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string);
+                if let Err(e) = service.complete(game_id, &key, parts.status.as_u16(), content_type, bytes.to_vec()).await {
+                    // Left unanswered: a retry waits for the claim to go stale
+                    tracing::warn!("Failed to store idempotent response for {}: {}", key, e);
+                }
+            }
+
+            Response::from_parts(parts, Body::from(bytes))
+        }
+    }
+}
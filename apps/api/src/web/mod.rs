@@ -1,3 +1,4 @@
 
 pub mod handlers;
 pub mod extractors;
+pub mod idempotency;
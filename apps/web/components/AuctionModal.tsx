@@ -28,7 +28,7 @@ export default function AuctionModal({ auction, participants, isHost }: AuctionM
 
     const handleBid = (amount: number) => {
         if (!user) return;
-        placeBid.mutate({ auctionId: auction.id, userId: user.id, amount }, {
+        placeBid.mutate({ auctionId: auction.id, amount }, {
             onError: (e: any) => toast.error(e.response?.data || e.message || 'Error al pujar')
         });
     };
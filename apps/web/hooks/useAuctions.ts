@@ -26,8 +26,8 @@ export const useAuctionActions = (gameId: string) => {
     });
 
     const placeBid = useMutation({
-        mutationFn: async ({ auctionId, userId, amount }: { auctionId: string; userId: string; amount: number }) => {
-            const { data } = await axios.post<Auction>(`${baseUrl}/auctions/${auctionId}/bid`, { bidder_user_id: userId, amount });
+        mutationFn: async ({ auctionId, amount }: { auctionId: string; amount: number }) => {
+            const { data } = await axios.post<Auction>(`${baseUrl}/auctions/${auctionId}/bid`, { amount });
             return data;
         }
     });
@@ -49,6 +49,14 @@ CREATE TABLE game_sessions (
     jackpot_balance DECIMAL(15, 2) NOT NULL DEFAULT 0.00,
     current_turn_user_id UUID REFERENCES users(id),
     turn_order JSONB,
+    winner_participant_id UUID, -- game_participants(id), no FK since that table references this one
+    win_reason VARCHAR(20), -- card, bankruptcy, time_limit, host_decision
+    house_rules JSONB NOT NULL DEFAULT '{}',
+    deadline_at TIMESTAMP WITH TIME ZONE, -- Timed mode only
+    rematch_game_id UUID REFERENCES game_sessions(id) ON DELETE SET NULL, -- Follow-up game created from this one
+    banker_user_id UUID REFERENCES users(id), -- NULL = the host runs the Bank
+    bank_balance DECIMAL(15, 2), -- Finite Bank reserve; NULL = infinite Bank
+    version INT NOT NULL DEFAULT 0, -- Optimistic concurrency: bumped on every update
     created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
     ended_at TIMESTAMP WITH TIME ZONE
 );
@@ -61,6 +69,8 @@ CREATE TABLE game_participants (
     balance DECIMAL(15, 2) NOT NULL DEFAULT 1500.00,
     position INTEGER NOT NULL DEFAULT 0,
     joined_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    bankrupt_at TIMESTAMP WITH TIME ZONE, -- Out of the game
+    bills JSONB, -- Denomination mode: {"500": 2, "100": 4, ...}; NULL while tracking plain balances
     UNIQUE(game_id, user_id)
 );
 
@@ -72,8 +82,20 @@ CREATE TABLE transactions (
     to_participant_id UUID REFERENCES game_participants(id) ON DELETE CASCADE,
     amount DECIMAL(15, 2) NOT NULL,
     description TEXT,
+    category VARCHAR(20) NOT NULL DEFAULT 'manual', -- salary, rent, tax, purchase, building, mortgage, trade, auction, card, jackpot, manual, reversal, adjustment, loan, bankruptcy
+    -- Optional references; no FKs since those tables are created further down
+    property_id UUID,
+    card_id UUID,
+    trade_id UUID,
+    auction_id UUID,
+    loan_id UUID,
+    group_id UUID, -- Rows booked together as one grouped operation
+    jackpot_delta DECIMAL(15, 2) NOT NULL DEFAULT 0.00, -- Change applied to the jackpot by this entry
+    reverses_transaction_id UUID REFERENCES transactions(id) ON DELETE SET NULL, -- Compensating entry for
+    reversed_by_transaction_id UUID REFERENCES transactions(id) ON DELETE SET NULL, -- Undone by
     created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
-    CONSTRAINT chk_at_least_one_party CHECK (from_participant_id IS NOT NULL OR to_participant_id IS NOT NULL)
+    -- Jackpot-only entries (no participant) are allowed
+    CONSTRAINT chk_at_least_one_party CHECK (from_participant_id IS NOT NULL OR to_participant_id IS NOT NULL OR jackpot_delta <> 0)
 );
 
 -- Dice Rolls Table
@@ -307,6 +329,7 @@ CREATE TABLE participant_properties (
     is_mortgaged BOOLEAN DEFAULT FALSE,
     house_count INT DEFAULT 0, -- 0-4
     hotel_count INT DEFAULT 0, -- 0-1
+    version INT NOT NULL DEFAULT 0, -- Optimistic concurrency: bumped on every update
     UNIQUE(game_id, property_id) -- A property can only be owned by one person in a game
 );
 
@@ -314,12 +337,44 @@ CREATE TABLE participant_properties (
 CREATE TABLE auctions (
     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
     game_id UUID NOT NULL REFERENCES game_sessions(id) ON DELETE CASCADE,
-    property_id UUID NOT NULL REFERENCES properties(id),
+    lot_type VARCHAR(20) NOT NULL DEFAULT 'property', -- property, participant_card, boveda_slot, house, hotel
+    property_id UUID REFERENCES properties(id), -- Property lots
+    card_id UUID REFERENCES cards(id), -- Participant card and Bóveda slot lots
+    inventory_id UUID REFERENCES participant_cards(id) ON DELETE SET NULL, -- Participant card lots
+    seller_participant_id UUID REFERENCES game_participants(id), -- Participant card lots: receives the winning bid
+    slot_index INT, -- Bóveda slot lots
     current_bid DECIMAL(15, 2) DEFAULT 0,
     highest_bidder_id UUID REFERENCES game_participants(id),
     status VARCHAR(20) DEFAULT 'ACTIVE', -- ACTIVE, FINISHED, CANCELLED
+    version INT NOT NULL DEFAULT 0, -- Optimistic concurrency: bumped on every update
     created_at TIMESTAMPTZ DEFAULT NOW(),
-    ends_at TIMESTAMPTZ
+    ends_at TIMESTAMPTZ,
+    format VARCHAR(20) NOT NULL DEFAULT 'open', -- open, sealed, dutch
+    start_price DECIMAL(15, 2) -- Dutch only: where the asking price starts
+);
+
+-- Bidding history: bids, passes and withdrawals in order
+CREATE TABLE auction_bids (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    auction_id UUID NOT NULL REFERENCES auctions(id) ON DELETE CASCADE,
+    game_id UUID NOT NULL REFERENCES game_sessions(id) ON DELETE CASCADE,
+    participant_id UUID NOT NULL REFERENCES game_participants(id) ON DELETE CASCADE,
+    action VARCHAR(20) NOT NULL, -- bid, pass, withdraw
+    amount DECIMAL(15, 2), -- NULL for pass and withdraw
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX idx_auction_bids_auction ON auction_bids(auction_id, created_at);
+
+-- Houses and hotels won at auction: the next build of that kind is already paid for
+CREATE TABLE building_rights (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    game_id UUID NOT NULL REFERENCES game_sessions(id) ON DELETE CASCADE,
+    participant_id UUID NOT NULL REFERENCES game_participants(id) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL, -- house, hotel
+    auction_id UUID REFERENCES auctions(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    used_at TIMESTAMPTZ
 );
 
 -- Trades
@@ -391,3 +446,103 @@ INSERT INTO properties (name, group_color, price, rent_base, rent_house_1, rent_
 -- Utilities (Rent is multiplier based on dice, handled in code)
 ('Compañía de Electricidad', 'utility', 150, 0, 0, 0, 0, 0, 0, 75, 83, 0, 0, 12),
 ('Compañía de Agua', 'utility', 150, 0, 0, 0, 0, 0, 0, 75, 83, 0, 0, 28);
+
+-- ==========================================
+-- GAME RESULTS
+-- ==========================================
+
+-- Final standings, frozen when the game ends
+CREATE TABLE game_standings (
+    game_id UUID NOT NULL REFERENCES game_sessions(id) ON DELETE CASCADE,
+    participant_id UUID NOT NULL REFERENCES game_participants(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    rank INT NOT NULL,
+    cash DECIMAL(15, 2) NOT NULL,
+    property_value DECIMAL(15, 2) NOT NULL,
+    building_value DECIMAL(15, 2) NOT NULL,
+    loans_receivable DECIMAL(15, 2) NOT NULL DEFAULT 0.00,
+    loans_payable DECIMAL(15, 2) NOT NULL DEFAULT 0.00,
+    net_worth DECIMAL(15, 2) NOT NULL,
+    PRIMARY KEY (game_id, participant_id)
+);
+
+-- ==========================================
+-- LOANS
+-- ==========================================
+
+CREATE TABLE loans (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    game_id UUID NOT NULL REFERENCES game_sessions(id) ON DELETE CASCADE,
+    lender_participant_id UUID REFERENCES game_participants(id) ON DELETE CASCADE, -- NULL = Bank
+    borrower_participant_id UUID NOT NULL REFERENCES game_participants(id) ON DELETE CASCADE,
+    principal DECIMAL(15, 2) NOT NULL CHECK (principal > 0),
+    interest_percent DECIMAL(7, 2) NOT NULL DEFAULT 0.00,
+    interest_period VARCHAR(10) NOT NULL DEFAULT 'lap', -- lap, turn
+    due_after_periods INT, -- NULL = open-ended
+    periods_elapsed INT NOT NULL DEFAULT 0,
+    outstanding DECIMAL(15, 2) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'PENDING', -- PENDING, ACTIVE, REPAID, DEFAULTED, REJECTED
+    version INT NOT NULL DEFAULT 0, -- Optimistic concurrency: bumped on every update
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    settled_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX idx_loans_game ON loans(game_id);
+
+-- ==========================================
+-- PAYMENT REQUESTS
+-- ==========================================
+
+CREATE TABLE payment_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    game_id UUID NOT NULL REFERENCES game_sessions(id) ON DELETE CASCADE,
+    payee_participant_id UUID REFERENCES game_participants(id) ON DELETE CASCADE, -- NULL = Bank
+    payer_participant_id UUID NOT NULL REFERENCES game_participants(id) ON DELETE CASCADE,
+    amount DECIMAL(15, 2) NOT NULL CHECK (amount > 0),
+    category VARCHAR(20) NOT NULL DEFAULT 'manual',
+    description TEXT,
+    property_id UUID REFERENCES properties(id),
+    status VARCHAR(20) NOT NULL DEFAULT 'PENDING', -- PENDING, APPROVED, REJECTED, CANCELLED
+    requested_by_user_id UUID NOT NULL REFERENCES users(id),
+    resolved_by_user_id UUID REFERENCES users(id), -- Payer, or the host/banker when force-approved
+    transaction_id UUID REFERENCES transactions(id),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    resolved_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX idx_payment_requests_game ON payment_requests(game_id);
+
+-- ==========================================
+-- BANK IOUS
+-- ==========================================
+
+CREATE TABLE bank_ious (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    game_id UUID NOT NULL REFERENCES game_sessions(id) ON DELETE CASCADE,
+    participant_id UUID NOT NULL REFERENCES game_participants(id) ON DELETE CASCADE,
+    amount DECIMAL(15, 2) NOT NULL CHECK (amount > 0),
+    outstanding DECIMAL(15, 2) NOT NULL,
+    transaction_id UUID REFERENCES transactions(id), -- The payout that came up short
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    settled_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX idx_bank_ious_game ON bank_ious(game_id);
+
+-- ==========================================
+-- IDEMPOTENCY KEYS
+-- ==========================================
+
+-- First response per Idempotency-Key and game, replayed when a client retries
+CREATE TABLE idempotency_keys (
+    game_id UUID NOT NULL REFERENCES game_sessions(id) ON DELETE CASCADE,
+    idempotency_key VARCHAR(255) NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    endpoint TEXT NOT NULL,
+    request_hash CHAR(64) NOT NULL, -- SHA-256 of the request body, hex
+    status_code INT, -- NULL while the first request is in flight
+    content_type TEXT,
+    response_body BYTEA,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (game_id, idempotency_key)
+);